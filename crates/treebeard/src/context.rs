@@ -1,7 +1,28 @@
 //! Evaluation context configuration
 
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::collections::HashSet;
+use std::fmt;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+
+use crate::{EvalError, Value};
+
+/// How many limit checks pass between wall-clock reads (a power of two).
+const DEADLINE_CHECK_INTERVAL: u32 = 1024;
+
+/// Shared writer for trace output.
+#[derive(Clone)]
+struct TraceSink(Arc<Mutex<Box<dyn Write + Send>>>);
+
+impl fmt::Debug for TraceSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TraceSink")
+    }
+}
 
 /// Configuration and state for evaluation.
 ///
@@ -17,6 +38,56 @@ pub struct EvalContext {
 
     /// Whether to trace evaluation (for debugging)
     pub trace: bool,
+
+    /// Whether `while` loops evaluate to the value of a `break` that ends
+    /// them, for frontends whose `while` is an expression. Off by default,
+    /// as in Rust, where `while` is always `()`
+    pub while_break_value: bool,
+
+    /// Whether `if`, `while`, `&&` and `||` accept any value as a
+    /// condition, using [`Value::is_truthy`](crate::Value::is_truthy). Off
+    /// by default, as in Rust, where conditions must be `bool`
+    pub truthy_conditions: bool,
+
+    /// Whether `debug_assert!`, `debug_assert_eq!` and `debug_assert_ne!`
+    /// are checked and `#[cfg(debug_assertions)]` holds. Off by default,
+    /// when the assertions are no-ops and their arguments are not evaluated
+    pub debug_assertions: bool,
+
+    /// Whether a `match` on a registered enum fails before any arm runs if
+    /// its arms do not name every variant. Off by default, when a `match`
+    /// fails only if no arm matches the value
+    pub exhaustive_enum_matches: bool,
+
+    /// Where trace output goes (stderr if unset)
+    trace_sink: Option<TraceSink>,
+
+    /// How long each evaluation may run, if bounded
+    pub timeout: Option<Duration>,
+
+    /// Wall-clock deadline after which the current evaluation times out,
+    /// derived from `timeout` by `restart_timeout`
+    deadline: Option<Instant>,
+
+    /// Limit checks since the clock was last read (shared between clones)
+    ticks: Arc<AtomicU32>,
+
+    /// Maximum number of evaluation steps, if bounded
+    pub step_limit: Option<u64>,
+
+    /// Steps left before `StepLimitExceeded` (shared between clones)
+    steps_remaining: Arc<AtomicU64>,
+
+    /// Largest estimated heap size, in bytes, of a newly created vec or
+    /// string, if bounded
+    pub memory_limit: Option<usize>,
+
+    /// Enabled `cfg` flags: `#[cfg(name)]` and `#[cfg(feature = "name")]`
+    /// hold when `name` is in the set
+    pub cfg_flags: HashSet<String>,
+
+    /// Per-function invocation counts (shared between clones)
+    call_counts: Arc<DashMap<String, u64>>,
 }
 
 impl Default for EvalContext {
@@ -25,6 +96,19 @@ impl Default for EvalContext {
             max_call_depth: 1000,
             interrupt: Arc::new(AtomicBool::new(false)),
             trace: false,
+            while_break_value: false,
+            truthy_conditions: false,
+            debug_assertions: false,
+            exhaustive_enum_matches: false,
+            trace_sink: None,
+            timeout: None,
+            deadline: None,
+            ticks: Arc::new(AtomicU32::new(0)),
+            step_limit: None,
+            steps_remaining: Arc::new(AtomicU64::new(0)),
+            memory_limit: None,
+            cfg_flags: HashSet::new(),
+            call_counts: Arc::new(DashMap::new()),
         }
     }
 }
@@ -43,6 +127,196 @@ impl EvalContext {
         }
     }
 
+    /// Create a context whose evaluations each time out after `timeout`.
+    ///
+    /// The first deadline is `timeout` from now;
+    /// [`Interpreter::eval_str`](crate::Interpreter::eval_str) starts a new
+    /// one for each evaluation.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            deadline: Some(Instant::now() + timeout),
+            ..Default::default()
+        }
+    }
+
+    /// Create a context that allows at most `limit` evaluation steps.
+    ///
+    /// Unlike a timeout this is deterministic: the same program always
+    /// stops at the same point.
+    pub fn with_step_limit(limit: u64) -> Self {
+        Self {
+            step_limit: Some(limit),
+            steps_remaining: Arc::new(AtomicU64::new(limit)),
+            ..Default::default()
+        }
+    }
+
+    /// Create a context that caps the size of newly created values.
+    ///
+    /// Vecs, arrays and strings whose [`heap_size`](crate::Value::heap_size)
+    /// would exceed `limit` bytes raise `MemoryLimitExceeded`. The budget
+    /// applies to each value as it is built, not to the total held.
+    pub fn with_memory_limit(limit: usize) -> Self {
+        Self {
+            memory_limit: Some(limit),
+            ..Default::default()
+        }
+    }
+
+    /// Create a context with the given `cfg` flags enabled.
+    pub fn with_cfg_flags<I, S>(flags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            cfg_flags: flags.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Interpret a value as a condition: a bool, or under
+    /// `truthy_conditions` any value. Returns `None` if the value can't be
+    /// used as a condition.
+    pub fn condition(&self, value: &Value) -> Option<bool> {
+        match value {
+            Value::Bool(b) => Some(*b),
+            other if self.truthy_conditions => Some(other.is_truthy()),
+            _ => None,
+        }
+    }
+
+    /// Check that a new value of `bytes` estimated heap bytes fits the
+    /// memory budget.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MemoryLimitExceeded` if `bytes` exceeds the limit.
+    pub fn check_allocation(&self, bytes: usize) -> Result<(), EvalError> {
+        match self.memory_limit {
+            Some(limit) if bytes > limit => Err(EvalError::MemoryLimitExceeded {
+                limit,
+                requested: bytes,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Get the number of steps left, if a step limit is set.
+    pub fn steps_remaining(&self) -> Option<u64> {
+        self.step_limit
+            .map(|_| self.steps_remaining.load(Ordering::Relaxed))
+    }
+
+    /// Consume one step. Returns `false` if the budget was already spent.
+    fn consume_step(&self) -> bool {
+        self.step_limit.is_none()
+            || self
+                .steps_remaining
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+                .is_ok()
+    }
+
+    /// Get the deadline of the current evaluation, if it has a timeout.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Set the deadline `timeout` from now, for an evaluation about to
+    /// start. Does nothing without a timeout.
+    pub fn restart_timeout(&mut self) {
+        if let Some(timeout) = self.timeout {
+            self.deadline = Some(Instant::now() + timeout);
+            self.ticks.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Check if the deadline (if any) has passed.
+    ///
+    /// The clock is only read every `DEADLINE_CHECK_INTERVAL` calls to keep
+    /// the per-expression overhead low.
+    pub fn is_timed_out(&self) -> bool {
+        match self.deadline {
+            Some(deadline) => {
+                let ticks = self.ticks.fetch_add(1, Ordering::Relaxed);
+                ticks & (DEADLINE_CHECK_INTERVAL - 1) == 0 && Instant::now() >= deadline
+            }
+            None => false,
+        }
+    }
+
+    /// Check the interrupt flag, deadline, and step budget.
+    ///
+    /// Called once per expression, statement, and loop iteration; each
+    /// call consumes one step.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Interrupted` if interruption was requested, `Timeout` if
+    /// the deadline has passed, or `StepLimitExceeded` if the step budget
+    /// is spent.
+    pub fn check_limits(&self) -> Result<(), EvalError> {
+        if self.is_interrupted() {
+            return Err(EvalError::Interrupted);
+        }
+        if self.is_timed_out() {
+            return Err(EvalError::Timeout);
+        }
+        if !self.consume_step() {
+            return Err(EvalError::StepLimitExceeded {
+                limit: self.step_limit.unwrap_or_default(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Record one invocation of the named function.
+    pub fn record_call(&self, name: &str) {
+        *self.call_counts.entry(name.to_string()).or_insert(0) += 1;
+    }
+
+    /// Get how many times the named function has been called.
+    pub fn call_count(&self, name: &str) -> u64 {
+        self.call_counts.get(name).map(|c| *c).unwrap_or(0)
+    }
+
+    /// Get the names of functions called more than `threshold` times, sorted.
+    ///
+    /// Embedders can use this to pick candidates for native compilation.
+    pub fn hot_functions(&self, threshold: u64) -> Vec<String> {
+        let mut hot: Vec<String> = self
+            .call_counts
+            .iter()
+            .filter(|entry| *entry.value() > threshold)
+            .map(|entry| entry.key().clone())
+            .collect();
+        hot.sort();
+        hot
+    }
+
+    /// Send trace output to `sink` instead of stderr.
+    ///
+    /// Tracing itself is enabled with the `trace` field.
+    pub fn set_trace_sink(&mut self, sink: Box<dyn Write + Send>) {
+        self.trace_sink = Some(TraceSink(Arc::new(Mutex::new(sink))));
+    }
+
+    /// Write one trace line, indented by `depth`.
+    ///
+    /// Write failures are ignored: tracing must never change evaluation.
+    pub fn emit_trace(&self, depth: usize, message: &str) {
+        let indent = "  ".repeat(depth);
+        match &self.trace_sink {
+            Some(TraceSink(sink)) => {
+                if let Ok(mut sink) = sink.lock() {
+                    let _ = writeln!(sink, "{}{}", indent, message);
+                }
+            }
+            None => eprintln!("{}{}", indent, message),
+        }
+    }
+
     /// Check if evaluation has been interrupted.
     pub fn is_interrupted(&self) -> bool {
         self.interrupt.load(Ordering::Relaxed)
@@ -60,9 +334,23 @@ impl EvalContext {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
+    /// A `Write` sink tests can read back after handing it to a context.
+    pub(crate) struct SharedBuf(pub(crate) Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuf {
+        fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(data);
+            Ok(data.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
     #[test]
     fn test_default_context() {
         let ctx = EvalContext::default();
@@ -119,6 +407,89 @@ mod tests {
         assert!(!ctx2.is_interrupted());
     }
 
+    #[test]
+    fn test_with_timeout() {
+        let ctx = EvalContext::with_timeout(Duration::from_secs(60));
+        assert!(ctx.deadline.is_some());
+        assert!(!ctx.is_timed_out());
+        assert!(ctx.check_limits().is_ok());
+    }
+
+    #[test]
+    fn test_expired_deadline() {
+        let ctx = EvalContext::with_timeout(Duration::ZERO);
+        assert!(matches!(ctx.check_limits(), Err(EvalError::Timeout)));
+    }
+
+    #[test]
+    fn test_restart_timeout() {
+        let mut ctx = EvalContext::with_timeout(Duration::from_millis(20));
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(matches!(ctx.check_limits(), Err(EvalError::Timeout)));
+
+        ctx.restart_timeout();
+        assert!(ctx.check_limits().is_ok());
+
+        let mut ctx = EvalContext::new();
+        ctx.restart_timeout();
+        assert!(ctx.deadline.is_none());
+    }
+
+    #[test]
+    fn test_step_limit_counts_down() {
+        let ctx = EvalContext::with_step_limit(2);
+        assert!(ctx.check_limits().is_ok());
+        assert!(ctx.check_limits().is_ok());
+        assert_eq!(ctx.steps_remaining(), Some(0));
+        assert!(matches!(
+            ctx.check_limits(),
+            Err(EvalError::StepLimitExceeded { limit: 2 })
+        ));
+        assert_eq!(ctx.steps_remaining(), Some(0));
+    }
+
+    #[test]
+    fn test_no_step_limit() {
+        let ctx = EvalContext::new();
+        assert_eq!(ctx.steps_remaining(), None);
+        assert!(ctx.check_limits().is_ok());
+    }
+
+    #[test]
+    fn test_no_deadline_never_times_out() {
+        let ctx = EvalContext::new();
+        assert!(ctx.deadline.is_none());
+        assert!(!ctx.is_timed_out());
+    }
+
+    #[test]
+    fn test_emit_trace_to_sink() {
+        let buf = Arc::new(Mutex::new(Vec::new()));
+        let mut ctx = EvalContext::new();
+        ctx.set_trace_sink(Box::new(SharedBuf(buf.clone())));
+
+        ctx.emit_trace(0, "outer");
+        ctx.emit_trace(2, "inner");
+
+        let out = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+        assert_eq!(out, "outer\n    inner\n");
+    }
+
+    #[test]
+    fn test_record_call_counts() {
+        let ctx = EvalContext::new();
+        assert_eq!(ctx.call_count("f"), 0);
+
+        ctx.record_call("f");
+        ctx.record_call("f");
+        ctx.clone().record_call("g");
+
+        assert_eq!(ctx.call_count("f"), 2);
+        assert_eq!(ctx.call_count("g"), 1);
+        assert_eq!(ctx.hot_functions(0), vec!["f", "g"]);
+        assert_eq!(ctx.hot_functions(1), vec!["f"]);
+    }
+
     #[test]
     fn test_trace_flag() {
         let mut ctx = EvalContext::new();
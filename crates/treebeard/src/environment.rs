@@ -4,8 +4,11 @@ mod frame;
 mod prelude;
 
 pub use frame::ScopeGuard;
+pub use prelude::PreludeBuilder;
+use prelude::{callable_arity, describe_type};
 
 use proc_macro2::Span;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 use crate::error::EnvironmentError;
@@ -25,6 +28,23 @@ pub struct Binding {
 
     /// Where this binding was defined (for error messages)
     pub span: Option<Span>,
+
+    /// For items such as nested `fn`s, the item scope they were defined in
+    /// (see [`Environment::item_scope`]); `None` for locals
+    pub item_scope: Option<u64>,
+}
+
+/// An active function body.
+#[derive(Debug, Clone, Copy)]
+struct FunctionFrame {
+    /// Depth of the body's frame
+    depth: usize,
+
+    /// Item scope of this call
+    scope: u64,
+
+    /// Item scope the function was defined in
+    parent: u64,
 }
 
 /// Binding mode for let statements.
@@ -78,11 +98,29 @@ pub struct Environment {
     /// Each entry marks where a scope begins
     frames: Vec<usize>,
 
-    /// Current call depth (for recursion limiting)
-    call_depth: usize,
+    /// Frames opened by function calls. The innermost one hides the
+    /// locals of enclosing non-global frames.
+    function_frames: Vec<FunctionFrame>,
+
+    /// The most recently allocated item scope
+    last_scope: u64,
+
+    /// Active interpreted calls: (function name, call-site span).
+    /// Its length is the current call depth.
+    call_stack: Vec<(String, Option<Span>)>,
 
     /// Maximum allowed call depth
     max_call_depth: usize,
+
+    /// Registered enum types (type name -> variant names, in declaration order)
+    enums: HashMap<String, Vec<String>>,
+
+    /// Registered struct types (type name -> field names, in declaration order)
+    structs: HashMap<String, Vec<String>>,
+
+    /// Constants from `const` items, folded once at definition time.
+    /// Consulted after bindings, and never reassigned.
+    constants: HashMap<String, Value>,
 }
 
 impl Default for Environment {
@@ -97,18 +135,33 @@ impl Environment {
         Self {
             bindings: Vec::new(),
             frames: vec![0], // Start with one frame (global scope)
-            call_depth: 0,
+            function_frames: Vec::new(),
+            last_scope: 0,
+            call_stack: Vec::new(),
             max_call_depth: 1000,
+            enums: HashMap::new(),
+            structs: HashMap::new(),
+            constants: HashMap::new(),
         }
     }
 
     /// Create an environment with a custom call depth limit.
+    ///
+    /// The limit applies to [`enter_call`](Self::enter_call) only; it has no
+    /// effect on evaluation, which enforces
+    /// [`EvalContext::max_call_depth`](crate::EvalContext) instead.
+    #[deprecated(note = "evaluation ignores this limit; use `EvalContext::with_max_call_depth`")]
     pub fn with_max_call_depth(max_depth: usize) -> Self {
         Self {
             bindings: Vec::new(),
             frames: vec![0],
-            call_depth: 0,
+            function_frames: Vec::new(),
+            last_scope: 0,
+            call_stack: Vec::new(),
             max_call_depth: max_depth,
+            enums: HashMap::new(),
+            structs: HashMap::new(),
+            constants: HashMap::new(),
         }
     }
 
@@ -124,6 +177,34 @@ impl Environment {
         self.frames.push(self.bindings.len());
     }
 
+    /// Enter the scope of a function body.
+    ///
+    /// Like [`push_frame`](Self::push_frame), but until the frame is popped
+    /// the bindings of enclosing non-global frames are hidden. Only items
+    /// defined in `defined_in`, the item scope the function was defined
+    /// in, or in a scope enclosing that one stay visible. As in Rust, a
+    /// function sees globals, constants and the items around its
+    /// definition, never the locals of the scope it was defined or called
+    /// in.
+    pub fn push_function_frame(&mut self, defined_in: u64) {
+        self.push_frame();
+        self.last_scope += 1;
+        self.function_frames.push(FunctionFrame {
+            depth: self.frames.len(),
+            scope: self.last_scope,
+            parent: defined_in,
+        });
+    }
+
+    /// Get the item scope of the innermost function body, or `0` outside
+    /// any function.
+    ///
+    /// Each call gets a fresh scope. Functions defined with it see the
+    /// items defined with it.
+    pub fn item_scope(&self) -> u64 {
+        self.function_frames.last().map_or(0, |frame| frame.scope)
+    }
+
     /// Exit the current scope (pop a frame).
     ///
     /// Removes all bindings defined since the matching `push_frame()`.
@@ -131,6 +212,13 @@ impl Environment {
     pub fn pop_frame(&mut self) {
         // Never pop the global frame
         if self.frames.len() > 1 {
+            if self
+                .function_frames
+                .last()
+                .is_some_and(|frame| frame.depth == self.frames.len())
+            {
+                self.function_frames.pop();
+            }
             if let Some(boundary) = self.frames.pop() {
                 self.bindings.truncate(boundary);
             }
@@ -151,26 +239,45 @@ impl Environment {
     // Call Depth Tracking (Stack Overflow Protection)
     // ═══════════════════════════════════════════════════════════════════
 
-    /// Enter a function call. Returns error if max depth exceeded.
+    /// Enter an anonymous function call. Returns error if max depth exceeded.
     pub fn enter_call(&mut self) -> Result<(), EnvironmentError> {
-        if self.call_depth >= self.max_call_depth {
+        self.enter_named_call("<anonymous>", None, self.max_call_depth)
+    }
+
+    /// Enter a function call, recording its name and call site for backtraces.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StackOverflow` if `max_depth` calls are already active.
+    pub fn enter_named_call(
+        &mut self,
+        name: impl Into<String>,
+        span: Option<Span>,
+        max_depth: usize,
+    ) -> Result<(), EnvironmentError> {
+        if self.call_stack.len() >= max_depth {
             return Err(EnvironmentError::StackOverflow {
-                depth: self.call_depth,
-                max: self.max_call_depth,
+                depth: self.call_stack.len(),
+                max: max_depth,
             });
         }
-        self.call_depth += 1;
+        self.call_stack.push((name.into(), span));
         Ok(())
     }
 
     /// Exit a function call.
     pub fn exit_call(&mut self) {
-        self.call_depth = self.call_depth.saturating_sub(1);
+        self.call_stack.pop();
     }
 
     /// Get current call depth.
     pub fn call_depth(&self) -> usize {
-        self.call_depth
+        self.call_stack.len()
+    }
+
+    /// Get the active calls, outermost first.
+    pub fn call_stack(&self) -> &[(String, Option<Span>)] {
+        &self.call_stack
     }
 
     // ═══════════════════════════════════════════════════════════════════
@@ -187,6 +294,7 @@ impl Environment {
             value,
             mutable: false,
             span: None,
+            item_scope: None,
         });
     }
 
@@ -197,6 +305,21 @@ impl Environment {
             value,
             mutable: mode == BindingMode::Mutable,
             span: None,
+            item_scope: None,
+        });
+    }
+
+    /// Define an item, such as a `fn` or `static`, in the current scope.
+    ///
+    /// Unlike other bindings, items stay visible to the bodies of functions
+    /// defined in the same item scope.
+    pub fn define_item(&mut self, name: impl Into<String>, value: Value, mode: BindingMode) {
+        self.bindings.push(Binding {
+            name: name.into(),
+            value,
+            mutable: mode == BindingMode::Mutable,
+            span: None,
+            item_scope: Some(self.item_scope()),
         });
     }
 
@@ -213,6 +336,7 @@ impl Environment {
             value,
             mutable,
             span: Some(span),
+            item_scope: None,
         });
     }
 
@@ -227,6 +351,32 @@ impl Environment {
         self.define(name, Value::Function(Arc::new(func)));
     }
 
+    /// Define a constant.
+    ///
+    /// Constants live outside the scope stack, so they stay visible for
+    /// the life of the environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConstantRedefinition` if a constant of that name exists.
+    pub fn define_constant(
+        &mut self,
+        name: impl Into<String>,
+        value: Value,
+    ) -> Result<(), EnvironmentError> {
+        let name = name.into();
+        if self.constants.contains_key(&name) {
+            return Err(EnvironmentError::ConstantRedefinition { name });
+        }
+        self.constants.insert(name, value);
+        Ok(())
+    }
+
+    /// Check if a constant is defined.
+    pub fn is_constant(&self, name: &str) -> bool {
+        self.constants.contains_key(name)
+    }
+
     /// Register a built-in function.
     pub fn define_builtin(&mut self, builtin: BuiltinFn) {
         let name = builtin.name.clone();
@@ -240,34 +390,66 @@ impl Environment {
     /// Look up a binding by name.
     ///
     /// Returns the most recent binding with the given name (shadowing),
-    /// or `None` if not found.
+    /// then any constant of that name, or `None` if not found.
     pub fn get(&self, name: &str) -> Option<&Value> {
-        // Search backwards to find most recent binding
-        self.bindings
-            .iter()
-            .rev()
-            .find(|b| b.name == name)
+        self.get_binding(name)
             .map(|b| &b.value)
+            .or_else(|| self.constants.get(name))
     }
 
     /// Look up a binding and return the full Binding struct.
     pub fn get_binding(&self, name: &str) -> Option<&Binding> {
-        self.bindings.iter().rev().find(|b| b.name == name)
+        self.find(name).map(|i| &self.bindings[i])
+    }
+
+    /// Find the index of the most recent visible binding named `name`.
+    fn find(&self, name: &str) -> Option<usize> {
+        let hidden = self.hidden_range();
+        self.bindings
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(i, b)| {
+                b.name == name
+                    && (!hidden.contains(i)
+                        || b.item_scope.is_some_and(|scope| self.is_lexical(scope)))
+            })
+            .map(|(i, _)| i)
+    }
+
+    /// The bindings hidden from the innermost function body: the locals
+    /// between the global frame and that body's frame.
+    fn hidden_range(&self) -> std::ops::Range<usize> {
+        match self.function_frames.last() {
+            Some(frame) => self.frames[1]..self.frames[frame.depth - 1],
+            None => 0..0,
+        }
+    }
+
+    /// Whether items of `scope` are visible to the innermost function body:
+    /// it is the global scope, the body's own, or that of an active call
+    /// the body's function is nested in.
+    fn is_lexical(&self, scope: u64) -> bool {
+        let mut frame = self.function_frames.last();
+        while let Some(current) = frame {
+            if current.scope == scope {
+                return true;
+            }
+            frame = self
+                .function_frames
+                .iter()
+                .find(|f| f.scope == current.parent);
+        }
+        scope == 0
     }
 
     /// Look up a mutable reference to a binding's value.
     ///
     /// Returns `None` if the binding doesn't exist.
-    /// Returns `Err` if the binding exists but is immutable.
+    /// Returns `Err` if the binding exists but is immutable, or if the
+    /// name refers to a constant.
     pub fn get_mut(&mut self, name: &str) -> Result<Option<&mut Value>, EnvironmentError> {
-        // Find the index of the binding
-        let idx = self
-            .bindings
-            .iter()
-            .enumerate()
-            .rev()
-            .find(|(_, b)| b.name == name)
-            .map(|(i, _)| i);
+        let idx = self.find(name);
 
         match idx {
             Some(i) => {
@@ -280,13 +462,16 @@ impl Environment {
                 }
                 Ok(Some(&mut self.bindings[i].value))
             }
+            None if self.is_constant(name) => Err(EnvironmentError::ConstantRedefinition {
+                name: name.to_string(),
+            }),
             None => Ok(None),
         }
     }
 
-    /// Check if a binding exists.
+    /// Check if a binding or constant exists.
     pub fn contains(&self, name: &str) -> bool {
-        self.bindings.iter().any(|b| b.name == name)
+        self.find(name).is_some() || self.is_constant(name)
     }
 
     /// Check if a binding exists in the current (innermost) scope only.
@@ -305,15 +490,9 @@ impl Environment {
     ///
     /// - `UndefinedVariable` if the binding doesn't exist
     /// - `ImmutableBinding` if the binding is not mutable
+    /// - `ConstantRedefinition` if the name refers to a constant
     pub fn assign(&mut self, name: &str, value: Value) -> Result<(), EnvironmentError> {
-        // Find the binding (reverse search for shadowing)
-        let idx = self
-            .bindings
-            .iter()
-            .enumerate()
-            .rev()
-            .find(|(_, b)| b.name == name)
-            .map(|(i, _)| i);
+        let idx = self.find(name);
 
         match idx {
             Some(i) => {
@@ -326,12 +505,43 @@ impl Environment {
                 self.bindings[i].value = value;
                 Ok(())
             }
+            None if self.is_constant(name) => Err(EnvironmentError::ConstantRedefinition {
+                name: name.to_string(),
+            }),
             None => Err(EnvironmentError::UndefinedVariable {
                 name: name.to_string(),
             }),
         }
     }
 
+    // ═══════════════════════════════════════════════════════════════════
+    // Type Registry
+    // ═══════════════════════════════════════════════════════════════════
+
+    /// Register an enum type and its variant names.
+    ///
+    /// Re-registering a name replaces the previous definition.
+    pub fn register_enum(&mut self, name: impl Into<String>, variants: Vec<String>) {
+        self.enums.insert(name.into(), variants);
+    }
+
+    /// Look up the variant names of a registered enum type.
+    pub fn enum_variants(&self, name: &str) -> Option<&[String]> {
+        self.enums.get(name).map(Vec::as_slice)
+    }
+
+    /// Register a struct type and its field names, in declaration order.
+    ///
+    /// Re-registering a name replaces the previous definition.
+    pub fn register_struct(&mut self, name: impl Into<String>, fields: Vec<String>) {
+        self.structs.insert(name.into(), fields);
+    }
+
+    /// Look up the field names of a registered struct type.
+    pub fn struct_fields(&self, name: &str) -> Option<&[String]> {
+        self.structs.get(name).map(Vec::as_slice)
+    }
+
     // ═══════════════════════════════════════════════════════════════════
     // Iteration and Inspection
     // ═══════════════════════════════════════════════════════════════════
@@ -355,6 +565,65 @@ impl Environment {
         self.bindings.iter().map(|b| b.name.as_str()).collect()
     }
 
+    /// Get the names of all bindings and constants starting with `prefix`,
+    /// sorted.
+    ///
+    /// Each name appears once, however many times it is shadowed.
+    pub fn completions(&self, prefix: &str) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .bindings
+            .iter()
+            .map(|b| &b.name)
+            .chain(self.constants.keys())
+            .filter(|name| name.starts_with(prefix))
+            .cloned()
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Get the visible non-callable bindings and constants with their
+    /// types, as `type_of` reports them, sorted by name.
+    ///
+    /// Shadowed bindings are omitted; callables are listed by
+    /// [`describe_funcs`](Self::describe_funcs).
+    pub fn describe_vars(&self) -> Vec<(String, String)> {
+        self.visible()
+            .filter(|(_, value)| !value.is_callable())
+            .map(|(name, value)| (name.to_string(), describe_type(value)))
+            .collect()
+    }
+
+    /// Get the visible callables with the number of parameters each takes
+    /// (`None` if variadic), sorted by name.
+    pub fn describe_funcs(&self) -> Vec<(String, Option<usize>)> {
+        self.visible()
+            .filter(|(_, value)| value.is_callable())
+            .map(|(name, value)| (name.to_string(), callable_arity(value)))
+            .collect()
+    }
+
+    /// Get the type of the value `name` resolves to, as `type_of` reports
+    /// it.
+    pub fn type_of_binding(&self, name: &str) -> Option<String> {
+        self.get(name).map(describe_type)
+    }
+
+    /// The value each visible name resolves to, sorted by name.
+    fn visible(&self) -> impl Iterator<Item = (&str, &Value)> {
+        let mut visible = BTreeMap::new();
+        for binding in self.bindings.iter().rev() {
+            if let Some(value) = self.get(&binding.name) {
+                visible.entry(binding.name.as_str()).or_insert(value);
+            }
+        }
+        for (name, value) in &self.constants {
+            visible.entry(name.as_str()).or_insert(value);
+        }
+        visible.into_iter()
+    }
+
     /// Get the number of bindings.
     pub fn len(&self) -> usize {
         self.bindings.len()
@@ -369,7 +638,11 @@ impl Environment {
     pub fn clear(&mut self) {
         self.bindings.clear();
         self.frames = vec![0];
-        self.call_depth = 0;
+        self.function_frames.clear();
+        self.call_stack.clear();
+        self.enums.clear();
+        self.structs.clear();
+        self.constants.clear();
     }
 }
 
@@ -394,6 +667,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_with_max_call_depth() {
         let env = Environment::with_max_call_depth(500);
         assert_eq!(env.max_call_depth, 500);
@@ -448,6 +722,22 @@ mod tests {
         assert!(env.is_global_scope());
     }
 
+    #[test]
+    fn test_function_frame_hides_enclosing_locals() {
+        let mut env = Environment::new();
+        env.define("global", Value::I64(1));
+        env.push_frame();
+        env.define("local", Value::I64(2));
+
+        env.push_function_frame(0);
+        assert_eq!(env.get("global"), Some(&Value::I64(1)));
+        assert_eq!(env.get("local"), None);
+        assert!(env.assign("local", Value::I64(3)).is_err());
+
+        env.pop_frame();
+        assert_eq!(env.get("local"), Some(&Value::I64(2)));
+    }
+
     #[test]
     fn test_frame_scoping() {
         let mut env = Environment::new();
@@ -544,6 +834,7 @@ mod tests {
             name: "test_builtin".to_string(),
             arity: 1,
             func: Arc::new(|_| Ok(Value::Unit)),
+            hooks: None,
         };
 
         env.define_builtin(builtin);
@@ -681,6 +972,21 @@ mod tests {
     }
 
     #[test]
+    fn test_enter_named_call_records_stack() {
+        let mut env = Environment::new();
+
+        env.enter_named_call("outer", None, 10).unwrap();
+        env.enter_named_call("inner", None, 10).unwrap();
+        let names: Vec<&str> = env.call_stack().iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["outer", "inner"]);
+
+        env.exit_call();
+        assert_eq!(env.call_stack().len(), 1);
+        assert_eq!(env.call_stack()[0].0, "outer");
+    }
+
+    #[test]
+    #[allow(deprecated)]
     fn test_stack_overflow_protection() {
         let mut env = Environment::with_max_call_depth(3);
 
@@ -745,6 +1051,67 @@ mod tests {
         assert!(names.contains(&"y"));
     }
 
+    #[test]
+    fn test_completions() {
+        let mut env = Environment::new();
+        env.define("count", Value::I64(1));
+        env.define("counter", Value::I64(2));
+        env.define("total", Value::I64(3));
+        env.push_frame();
+        env.define("count", Value::I64(10)); // Shadows outer count
+
+        assert_eq!(env.completions("cou"), vec!["count", "counter"]);
+        assert!(env.completions("z").is_empty());
+    }
+
+    #[test]
+    fn test_describe_bindings() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str(
+                "let x = 1; let x = \"one\"; const N: u8 = 2u8; fn f() {} fn g(a: i64, b: i64) {}",
+            )
+            .unwrap();
+        let env = interp.env();
+
+        let vars = env.describe_vars();
+        assert!(vars.contains(&("x".to_string(), "String".to_string())));
+        assert!(vars.contains(&("N".to_string(), "u8".to_string())));
+        assert_eq!(vars.iter().filter(|(name, _)| name == "x").count(), 1);
+
+        let funcs = env.describe_funcs();
+        assert!(funcs.contains(&("f".to_string(), Some(0))));
+        assert!(funcs.contains(&("g".to_string(), Some(2))));
+        assert!(funcs.contains(&("println".to_string(), None)));
+        assert!(!funcs.iter().any(|(name, _)| name == "x"));
+
+        assert_eq!(env.type_of_binding("x").as_deref(), Some("String"));
+        assert_eq!(env.type_of_binding("f").as_deref(), Some("fn"));
+        assert_eq!(env.type_of_binding("missing"), None);
+    }
+
+    #[test]
+    fn test_constants() {
+        let mut env = Environment::new();
+        env.define_constant("MAX", Value::I64(10)).unwrap();
+        assert_eq!(env.get("MAX"), Some(&Value::I64(10)));
+        assert!(env.contains("MAX"));
+
+        assert!(matches!(
+            env.define_constant("MAX", Value::I64(20)),
+            Err(EnvironmentError::ConstantRedefinition { .. })
+        ));
+        assert!(matches!(
+            env.assign("MAX", Value::I64(20)),
+            Err(EnvironmentError::ConstantRedefinition { .. })
+        ));
+        assert!(env.get_mut("MAX").is_err());
+
+        // Bindings shadow constants
+        env.define("MAX", Value::I64(1));
+        assert_eq!(env.get("MAX"), Some(&Value::I64(1)));
+    }
+
     #[test]
     fn test_len() {
         let mut env = Environment::new();
@@ -1,8 +1,11 @@
 //! Standard prelude with built-in functions
 
 use super::Environment;
-use crate::value::{BuiltinFn, Value};
-use std::sync::Arc;
+use crate::value::{
+    BuiltinAllocFn, BuiltinControlFnPtr, BuiltinFn, BuiltinFnPtr, BuiltinHooks, Value, ValueRefMut,
+};
+use crate::EvalError;
+use std::sync::{Arc, RwLockWriteGuard};
 
 impl Environment {
     /// Create an environment with standard built-in functions.
@@ -14,55 +17,310 @@ impl Environment {
 
     /// Load the standard prelude into this environment.
     pub fn load_prelude(&mut self) {
-        // Printing
-        self.define_builtin(BuiltinFn {
-            name: "print".to_string(),
-            arity: -1, // Variadic
-            func: Arc::new(builtin_print),
-        });
+        for (name, value) in default_prelude() {
+            self.define(name, value);
+        }
+        self.register_prelude_types();
+    }
 
-        self.define_builtin(BuiltinFn {
-            name: "println".to_string(),
-            arity: -1,
-            func: Arc::new(builtin_println),
-        });
+    /// Register the enums built-in values use, such as `Ordering`.
+    fn register_prelude_types(&mut self) {
+        self.register_enum(
+            "Ordering",
+            vec![
+                "Less".to_string(),
+                "Equal".to_string(),
+                "Greater".to_string(),
+            ],
+        );
+    }
+}
 
-        // Type inspection
-        self.define_builtin(BuiltinFn {
-            name: "type_of".to_string(),
-            arity: 1,
-            func: Arc::new(builtin_type_of),
-        });
+/// Assembles a customized prelude.
+///
+/// Starts from the standard prelude; builtins can be added or replaced
+/// with [`with_builtin`](Self::with_builtin) and removed with
+/// [`without`](Self::without).
+///
+/// # Example
+///
+/// ```
+/// use treebeard::{PreludeBuilder, Value};
+///
+/// let env = PreludeBuilder::new()
+///     .with_builtin("answer", 0, |_| Ok(Value::I64(42)))
+///     .without("panic")
+///     .build();
+///
+/// assert!(env.contains("answer"));
+/// assert!(!env.contains("panic"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PreludeBuilder {
+    entries: Vec<(String, Value)>,
+}
 
-        // Debug representation
-        self.define_builtin(BuiltinFn {
-            name: "dbg".to_string(),
-            arity: 1,
-            func: Arc::new(builtin_dbg),
-        });
+impl Default for PreludeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-        // Assertions
-        self.define_builtin(BuiltinFn {
-            name: "assert".to_string(),
-            arity: 1,
-            func: Arc::new(builtin_assert),
-        });
+impl PreludeBuilder {
+    /// Create a builder holding the standard prelude.
+    pub fn new() -> Self {
+        Self {
+            entries: default_prelude(),
+        }
+    }
+
+    /// Create a builder with nothing in it.
+    pub fn empty() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Add a builtin, replacing any prelude entry of the same name.
+    ///
+    /// An `arity` of -1 makes the builtin variadic.
+    pub fn with_builtin(
+        self,
+        name: impl Into<String>,
+        arity: i32,
+        func: impl Fn(&[Value]) -> Result<Value, String> + Send + Sync + 'static,
+    ) -> Self {
+        let name = name.into();
+        let builtin = BuiltinFn {
+            name: name.clone(),
+            arity,
+            func: Arc::new(func),
+            hooks: None,
+        };
+        self.with_value(name, Value::BuiltinFn(builtin))
+    }
+
+    /// Add a plain value, replacing any prelude entry of the same name.
+    pub fn with_value(mut self, name: impl Into<String>, value: Value) -> Self {
+        let name = name.into();
+        self.entries.retain(|(n, _)| *n != name);
+        self.entries.push((name, value));
+        self
+    }
+
+    /// Remove the prelude entry called `name`, if present.
+    pub fn without(mut self, name: &str) -> Self {
+        self.entries.retain(|(n, _)| n != name);
+        self
+    }
+
+    /// Get the names of the entries, in definition order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(n, _)| n.as_str())
+    }
 
-        self.define_builtin(BuiltinFn {
-            name: "assert_eq".to_string(),
-            arity: 2,
-            func: Arc::new(builtin_assert_eq),
+    /// Create an environment with this prelude loaded.
+    pub fn build(self) -> Environment {
+        let mut env = Environment::new();
+        for (name, value) in self.entries {
+            env.define(name, value);
+        }
+        env.register_prelude_types();
+        env
+    }
+}
+
+/// The standard prelude entries, in definition order.
+fn default_prelude() -> Vec<(String, Value)> {
+    let builtin = |name: &str, arity: i32, func: BuiltinFnPtr| {
+        let value = Value::BuiltinFn(BuiltinFn {
+            name: name.to_string(),
+            arity,
+            func,
+            hooks: None,
+        });
+        (name.to_string(), value)
+    };
+    // A builtin that materializes a collection, checked against the memory budget
+    let allocating = |name: &str, arity: i32, func: BuiltinFnPtr, estimate: BuiltinAllocFn| {
+        let value = Value::BuiltinFn(BuiltinFn {
+            name: name.to_string(),
+            arity,
+            func,
+            hooks: Some(Arc::new(BuiltinHooks {
+                allocation: Some(estimate),
+                control: None,
+            })),
         });
+        (name.to_string(), value)
+    };
+    // A builtin that ends evaluation with its own error, such as `Exit`
+    let control = |name: &str, arity: i32, func: BuiltinControlFnPtr| {
+        let value = Value::BuiltinFn(BuiltinFn::control(name, arity, move |args| func(args)));
+        (name.to_string(), value)
+    };
 
+    let mut prelude = vec![
+        // Printing
+        builtin("print", -1, Arc::new(builtin_print)),
+        builtin("println", -1, Arc::new(builtin_println)),
+        // Type inspection
+        builtin("type_of", 1, Arc::new(builtin_type_of)),
+        // Callable introspection
+        builtin("arity_of", 1, Arc::new(builtin_arity_of)),
+        builtin(
+            "is_callable",
+            1,
+            Arc::new(|args| Ok(Value::Bool(args[0].is_callable()))),
+        ),
+        // Enum and struct reflection
+        builtin(
+            "variant_name",
+            1,
+            Arc::new(|args| Ok(option_value(args[0].variant_name().map(Value::string)))),
+        ),
+        builtin("field_names", 1, Arc::new(builtin_field_names)),
+        // Debug representation
+        builtin("dbg", 1, Arc::new(builtin_dbg)),
+        // Assertions
+        control("assert", 1, Arc::new(builtin_assert)),
+        control("assert_eq", 2, Arc::new(builtin_assert_eq)),
         // Panic
-        self.define_builtin(BuiltinFn {
-            name: "panic".to_string(),
-            arity: -1,
-            func: Arc::new(builtin_panic),
-        });
+        control("panic", -1, Arc::new(builtin_panic)),
+        // String conversion
+        builtin(
+            "String::from",
+            1,
+            Arc::new(|args| Ok(Value::string(args[0].to_string()))),
+        ),
+        builtin("from_utf8", 1, Arc::new(builtin_from_utf8)),
+        builtin("String::from_utf8", 1, Arc::new(builtin_from_utf8)),
+        // Integer ranges, materialized like `start..end`
+        allocating(
+            "range",
+            -1,
+            Arc::new(builtin_range),
+            Arc::new(range_allocation),
+        ),
+        allocating(
+            "range_step",
+            3,
+            Arc::new(builtin_range_step),
+            Arc::new(range_step_allocation),
+        ),
+        // Repetition, materialized like `range`
+        allocating(
+            "repeat_n",
+            2,
+            Arc::new(builtin_repeat_n),
+            Arc::new(repeat_n_allocation),
+        ),
+        builtin(
+            "once",
+            1,
+            Arc::new(|args| Ok(Value::vec(vec![args[0].clone()]))),
+        ),
+        // Option and Result constructors
+        ("None".to_string(), Value::none()),
+        builtin("Some", 1, Arc::new(|args| Ok(Value::some(args[0].clone())))),
+        builtin("Ok", 1, Arc::new(|args| Ok(Value::ok(args[0].clone())))),
+        builtin("Err", 1, Arc::new(|args| Ok(Value::err(args[0].clone())))),
+    ];
+
+    // Mutation through `&mut` references, also reachable as `std::mem::*`
+    let mem: [(&str, i32, BuiltinFnPtr); 3] = [
+        ("swap", 2, Arc::new(builtin_swap)),
+        ("replace", 2, Arc::new(builtin_replace)),
+        ("take", 1, Arc::new(builtin_take)),
+    ];
+    for (name, arity, func) in mem {
+        for prefix in ["", "mem::", "std::mem::"] {
+            prelude.push(builtin(&format!("{}{}", prefix, name), arity, func.clone()));
+        }
+    }
+
+    // Ordering helpers, also reachable as `std::cmp::*`
+    let cmp: [(&str, BuiltinFnPtr); 2] = [
+        ("min", Arc::new(builtin_min)),
+        ("max", Arc::new(builtin_max)),
+    ];
+    for (name, func) in cmp {
+        for prefix in ["", "cmp::", "std::cmp::"] {
+            prelude.push(builtin(&format!("{}{}", prefix, name), 2, func.clone()));
+        }
+    }
+    prelude.push(builtin("clamp", 3, Arc::new(builtin_clamp)));
+
+    // Smart pointers. Values are already reference counted, so these
+    // return their argument: arithmetic, methods and fields work on it
+    for pointer in ["Box", "Rc", "Arc"] {
+        prelude.push(builtin(
+            &format!("{}::new", pointer),
+            1,
+            Arc::new(|args| Ok(args[0].clone())),
+        ));
+    }
+
+    // Empty collections. Capacity hints are accepted and ignored
+    for ty in ["String", "Vec", "HashMap"] {
+        let empty = Value::default_for_type_name(ty).expect("collection default");
+        let new = empty.clone();
+        prelude.push(builtin(
+            &format!("{}::new", ty),
+            0,
+            Arc::new(move |_| Ok(new.clone())),
+        ));
+        prelude.push(builtin(
+            &format!("{}::with_capacity", ty),
+            1,
+            Arc::new(move |_| Ok(empty.clone())),
+        ));
+    }
+
+    // Process control, also reachable as `std::process::*`. These raise
+    // `Exit` and `Panic` so the embedder decides whether the process ends
+    let process: [(&str, i32, BuiltinControlFnPtr); 2] = [
+        ("exit", 1, Arc::new(builtin_exit)),
+        ("abort", 0, Arc::new(builtin_abort)),
+    ];
+    for (name, arity, func) in process {
+        for prefix in ["", "process::", "std::process::"] {
+            prelude.push(control(&format!("{}{}", prefix, name), arity, func.clone()));
+        }
+    }
+
+    prelude
+}
+
+/// The error a control builtin raises for bad arguments.
+fn control_error(name: &str, message: String) -> EvalError {
+    EvalError::BuiltinError {
+        name: name.to_string(),
+        message,
+        span: None,
     }
 }
 
+/// The error a control builtin raises to panic.
+fn panic_error(message: impl Into<String>) -> EvalError {
+    EvalError::Panic {
+        message: message.into(),
+        span: None,
+    }
+}
+
+fn exit_code(args: &[Value]) -> Result<i32, EvalError> {
+    args[0]
+        .as_i64()
+        .and_then(|code| i32::try_from(code).ok())
+        .ok_or_else(|| {
+            control_error(
+                "exit",
+                format!("exit expects an i32 code, got {}", describe_type(&args[0])),
+            )
+        })
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 // Built-in Function Implementations
 // ═══════════════════════════════════════════════════════════════════════
@@ -88,7 +346,266 @@ fn builtin_type_of(args: &[Value]) -> Result<Value, String> {
         return Err(format!("type_of expects 1 argument, got {}", args.len()));
     }
 
-    let type_name = match &args[0] {
+    Ok(Value::string(describe_type(&args[0])))
+}
+
+/// Get the number of parameters a callable takes, or `None` if variadic.
+fn builtin_arity_of(args: &[Value]) -> Result<Value, String> {
+    if !args[0].is_callable() {
+        return Err(format!(
+            "arity_of expects a callable, got {}",
+            describe_type(&args[0])
+        ));
+    }
+
+    Ok(Value::Option(Arc::new(
+        callable_arity(&args[0]).map(Value::Usize),
+    )))
+}
+
+/// The number of parameters a callable takes, or `None` if it is variadic
+/// or not callable.
+pub(super) fn callable_arity(value: &Value) -> Option<usize> {
+    match value {
+        Value::Function(f) => (!f.variadic).then_some(f.params.len()),
+        Value::Closure(c) => Some(c.params.len()),
+        Value::BuiltinFn(b) => usize::try_from(b.arity).ok(),
+        Value::CompiledFn(c) => Some(c.arity),
+        _ => None,
+    }
+}
+
+/// Decode UTF-8 bytes, from a byte string or a `Vec` of `u8`, into a
+/// `Result<String, String>`.
+fn builtin_field_names(args: &[Value]) -> Result<Value, String> {
+    let names = args[0]
+        .field_names()
+        .map(|names| Value::vec(names.into_iter().map(Value::string).collect()));
+    Ok(option_value(names))
+}
+
+fn option_value(value: Option<Value>) -> Value {
+    value.map_or_else(Value::none, Value::some)
+}
+
+fn builtin_from_utf8(args: &[Value]) -> Result<Value, String> {
+    let bytes = match &args[0] {
+        Value::Bytes(b) => b.as_ref().clone(),
+        Value::Vec(items) | Value::Array(items) => items
+            .iter()
+            .map(|item| match item {
+                Value::U8(byte) => Ok(*byte),
+                other => Err(format!(
+                    "from_utf8 expects bytes, found element of type {}",
+                    describe_type(other)
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        other => {
+            return Err(format!(
+                "from_utf8 expects bytes, got {}",
+                describe_type(other)
+            ))
+        }
+    };
+
+    Ok(match String::from_utf8(bytes) {
+        Ok(s) => Value::ok(Value::string(s)),
+        Err(e) => Value::err(Value::string(e.to_string())),
+    })
+}
+
+/// The integers in `0..end` or `start..end`, as a `Vec`.
+fn builtin_range(args: &[Value]) -> Result<Value, String> {
+    let (start, end) = match args {
+        [end] => (0, range_bound("range", end)?),
+        [start, end] => (range_bound("range", start)?, range_bound("range", end)?),
+        _ => {
+            return Err(format!(
+                "range expects 1 or 2 arguments, got {}",
+                args.len()
+            ))
+        }
+    };
+
+    Ok(Value::vec((start..end).map(Value::I64).collect()))
+}
+
+/// Every `step`th integer in `start..end`, as a `Vec`.
+fn builtin_range_step(args: &[Value]) -> Result<Value, String> {
+    let start = range_bound("range_step", &args[0])?;
+    let end = range_bound("range_step", &args[1])?;
+    let step = range_bound("range_step", &args[2])?;
+    if step <= 0 {
+        return Err(format!("range_step: step must be positive, got {}", step));
+    }
+
+    Ok(Value::vec(
+        (start..end)
+            .step_by(step as usize)
+            .map(Value::I64)
+            .collect(),
+    ))
+}
+
+/// `n` copies of a value, as a `Vec`.
+fn builtin_repeat_n(args: &[Value]) -> Result<Value, String> {
+    let n = range_bound("repeat_n", &args[1])?;
+    let n = usize::try_from(n)
+        .map_err(|_| format!("repeat_n: count must be non-negative, got {}", n))?;
+
+    Ok(Value::vec(vec![args[0].clone(); n]))
+}
+
+/// Bytes `range` would allocate; bad arguments are left for the builtin to report.
+fn range_allocation(args: &[Value]) -> usize {
+    let (start, end) = match args {
+        [end] => (Some(0), end.as_i64()),
+        [start, end] => (start.as_i64(), end.as_i64()),
+        _ => (None, None),
+    };
+    match (start, end) {
+        (Some(start), Some(end)) => vec_bytes(i128::from(end) - i128::from(start), 0),
+        _ => 0,
+    }
+}
+
+/// Bytes `range_step` would allocate.
+fn range_step_allocation(args: &[Value]) -> usize {
+    match (args[0].as_i64(), args[1].as_i64(), args[2].as_i64()) {
+        (Some(start), Some(end), Some(step)) if step > 0 => {
+            let span = i128::from(end) - i128::from(start);
+            let step = i128::from(step);
+            vec_bytes((span + step - 1) / step, 0)
+        }
+        _ => 0,
+    }
+}
+
+/// Bytes `repeat_n` would allocate.
+fn repeat_n_allocation(args: &[Value]) -> usize {
+    match args[1].as_i64() {
+        Some(n) => vec_bytes(i128::from(n), args[0].heap_size()),
+        None => 0,
+    }
+}
+
+/// Bytes for a `Vec` of `len` values, each owning `each` heap bytes.
+fn vec_bytes(len: i128, each: usize) -> usize {
+    let len = usize::try_from(len.max(0)).unwrap_or(usize::MAX);
+    len.saturating_mul(std::mem::size_of::<Value>().saturating_add(each))
+}
+
+fn range_bound(name: &str, value: &Value) -> Result<i64, String> {
+    value.as_i64().ok_or_else(|| {
+        format!(
+            "{} expects integer arguments, got {}",
+            name,
+            describe_type(value)
+        )
+    })
+}
+
+/// The smaller of two values, or the first if they are equal.
+fn builtin_min(args: &[Value]) -> Result<Value, String> {
+    let ordering = order("min", &args[0], &args[1])?;
+    Ok(if ordering.is_le() { &args[0] } else { &args[1] }.clone())
+}
+
+/// The larger of two values, or the second if they are equal.
+fn builtin_max(args: &[Value]) -> Result<Value, String> {
+    let ordering = order("max", &args[0], &args[1])?;
+    Ok(if ordering.is_gt() { &args[0] } else { &args[1] }.clone())
+}
+
+/// Restrict a value to the inclusive range `lo..=hi`.
+fn builtin_clamp(args: &[Value]) -> Result<Value, String> {
+    let (x, lo, hi) = (&args[0], &args[1], &args[2]);
+    if order("clamp", lo, hi)?.is_gt() {
+        return Err(format!(
+            "clamp: lower bound {} exceeds upper bound {}",
+            lo, hi
+        ));
+    }
+
+    Ok(if order("clamp", x, lo)?.is_lt() {
+        lo
+    } else if order("clamp", x, hi)?.is_gt() {
+        hi
+    } else {
+        x
+    }
+    .clone())
+}
+
+fn order(name: &str, a: &Value, b: &Value) -> Result<std::cmp::Ordering, String> {
+    a.try_cmp(b).map_err(|e| format!("{}: {}", name, e))
+}
+
+/// Exchange the values behind two mutable references.
+fn builtin_swap(args: &[Value]) -> Result<Value, String> {
+    let (a, b) = (
+        expect_ref_mut("swap", &args[0])?,
+        expect_ref_mut("swap", &args[1])?,
+    );
+    if Arc::ptr_eq(&a.value, &b.value) {
+        return Ok(Value::Unit);
+    }
+
+    let mut a = write_lock("swap", &args[0])?;
+    let mut b = write_lock("swap", &args[1])?;
+    std::mem::swap(&mut *a, &mut *b);
+    Ok(Value::Unit)
+}
+
+/// Store a new value behind a mutable reference, returning the old one.
+fn builtin_replace(args: &[Value]) -> Result<Value, String> {
+    let mut slot = write_lock("replace", &args[0])?;
+    Ok(std::mem::replace(&mut *slot, args[1].clone()))
+}
+
+/// Take the value behind a mutable reference, leaving its type's default.
+fn builtin_take(args: &[Value]) -> Result<Value, String> {
+    let mut slot = write_lock("take", &args[0])?;
+    let default = default_of(&slot);
+    Ok(std::mem::replace(&mut *slot, default))
+}
+
+fn expect_ref_mut<'a>(name: &str, value: &'a Value) -> Result<&'a ValueRefMut, String> {
+    match value {
+        Value::RefMut(r) => Ok(r),
+        other => Err(format!(
+            "{} expects a mutable reference, got {}",
+            name,
+            describe_type(other)
+        )),
+    }
+}
+
+fn write_lock<'a>(name: &str, value: &'a Value) -> Result<RwLockWriteGuard<'a, Value>, String> {
+    expect_ref_mut(name, value)?;
+    value.deref_mut().map_err(|e| format!("{}: {}", name, e))
+}
+
+/// The `Default` value of `value`'s type, or `()` if it has none.
+fn default_of(value: &Value) -> Value {
+    match value {
+        Value::Bytes(_) => Value::Bytes(Arc::new(Vec::new())),
+        Value::Array(items) => Value::array(items.iter().map(default_of).collect()),
+        Value::Tuple(items) => Value::tuple(items.iter().map(default_of).collect()),
+        other => {
+            Value::default_for_type_name(crate::error::type_name(other)).unwrap_or(Value::Unit)
+        }
+    }
+}
+
+/// Describe the type of a value as precisely as is cheap to determine.
+///
+/// Element types of generic containers are inferred from their contents:
+/// a `Vec` takes its first element's type if every element agrees, and
+/// `?` stands for a type that cannot be determined.
+pub(super) fn describe_type(value: &Value) -> String {
+    let type_name = match value {
         Value::Unit => "()",
         Value::Bool(_) => "bool",
         Value::Char(_) => "char",
@@ -108,23 +625,61 @@ fn builtin_type_of(args: &[Value]) -> Result<Value, String> {
         Value::F64(_) => "f64",
         Value::String(_) => "String",
         Value::Bytes(_) => "Vec<u8>",
-        Value::Vec(_) => "Vec",
+        Value::Vec(items) if items.is_empty() => "Vec",
+        Value::Vec(items) => return format!("Vec<{}>", element_type(items)),
         Value::Tuple(_) => "tuple",
         Value::Array(_) => "array",
-        Value::Struct(s) => return Ok(Value::string(&s.type_name)),
-        Value::Enum(e) => return Ok(Value::string(&e.type_name)),
+        Value::Struct(s) => return s.type_name.clone(),
+        Value::Enum(e) => return e.type_name.clone(),
         Value::HashMap(_) => "HashMap",
-        Value::Option(_) => "Option",
-        Value::Result(_) => "Result",
+        Value::Option(opt) => {
+            let inner = opt.as_ref().as_ref().map_or("?".to_string(), describe_type);
+            return format!("Option<{}>", inner);
+        }
+        Value::Result(res) => {
+            return match res.as_ref() {
+                Ok(v) => format!("Result<{}, ?>", describe_type(v)),
+                Err(e) => format!("Result<?, {}>", describe_type(e)),
+            };
+        }
+        Value::Iter(iter) if iter.remaining().is_empty() => "Iter",
+        Value::Iter(iter) => return format!("Iter<{}>", element_type(iter.remaining())),
         Value::Function(_) => "fn",
         Value::Closure(_) => "closure",
         Value::BuiltinFn(_) => "builtin_fn",
         Value::CompiledFn(_) => "compiled_fn",
-        Value::Ref(_) => "ref",
-        Value::RefMut(_) => "ref_mut",
+        Value::Ref(r) => return format!("&{}", describe_type(&r.value)),
+        Value::RefMut(r) => {
+            return match r.value.read() {
+                Ok(inner) => format!("&mut {}", describe_type(&inner)),
+                Err(_) => "&mut ?".to_string(),
+            };
+        }
     };
 
-    Ok(Value::string(type_name))
+    type_name.to_string()
+}
+
+/// How many elements `element_type` looks at.
+const ELEMENT_SAMPLE: usize = 16;
+
+/// The type of the first of `items`, or `?` if the first few are of
+/// different kinds. Only the first is described in full, so the cost does
+/// not grow with the collection.
+fn element_type(items: &[Value]) -> String {
+    let Some(first) = items.first() else {
+        return "?".to_string();
+    };
+    let same_kind = |item: &Value| match (first, item) {
+        (Value::Struct(a), Value::Struct(b)) => a.type_name == b.type_name,
+        (Value::Enum(a), Value::Enum(b)) => a.type_name == b.type_name,
+        _ => std::mem::discriminant(first) == std::mem::discriminant(item),
+    };
+    if items.iter().take(ELEMENT_SAMPLE).all(same_kind) {
+        describe_type(first)
+    } else {
+        "?".to_string()
+    }
 }
 
 fn builtin_dbg(args: &[Value]) -> Result<Value, String> {
@@ -136,37 +691,56 @@ fn builtin_dbg(args: &[Value]) -> Result<Value, String> {
     Ok(args[0].clone())
 }
 
-fn builtin_assert(args: &[Value]) -> Result<Value, String> {
+fn builtin_assert(args: &[Value]) -> Result<Value, EvalError> {
     if args.len() != 1 {
-        return Err(format!("assert expects 1 argument, got {}", args.len()));
+        let message = format!("assert expects 1 argument, got {}", args.len());
+        return Err(control_error("assert", message));
     }
 
     match &args[0] {
         Value::Bool(true) => Ok(Value::Unit),
-        Value::Bool(false) => Err("assertion failed".to_string()),
-        other => Err(format!(
-            "assert expects bool, got {:?}",
-            builtin_type_of(std::slice::from_ref(other))?
+        Value::Bool(false) => Err(panic_error("assertion failed")),
+        other => Err(control_error(
+            "assert",
+            format!("assert expects bool, got {}", describe_type(other)),
         )),
     }
 }
 
-fn builtin_assert_eq(args: &[Value]) -> Result<Value, String> {
+fn builtin_assert_eq(args: &[Value]) -> Result<Value, EvalError> {
     if args.len() != 2 {
-        return Err(format!("assert_eq expects 2 arguments, got {}", args.len()));
+        let message = format!("assert_eq expects 2 arguments, got {}", args.len());
+        return Err(control_error("assert_eq", message));
     }
 
     if args[0] == args[1] {
         Ok(Value::Unit)
+    } else if args[0].is_callable() && args[1].is_callable() {
+        // Callables compare by identity, so say so rather than print two
+        // indistinguishable values
+        Err(panic_error(format!(
+            "assertion failed: `{}` and `{}` are different functions (callables compare by identity)",
+            args[0], args[1]
+        )))
     } else {
-        Err(format!(
+        Err(panic_error(format!(
             "assertion failed: `{:?}` != `{:?}`",
             args[0], args[1]
-        ))
+        )))
     }
 }
 
-fn builtin_panic(args: &[Value]) -> Result<Value, String> {
+fn builtin_exit(args: &[Value]) -> Result<Value, EvalError> {
+    Err(EvalError::Exit {
+        code: exit_code(args)?,
+    })
+}
+
+fn builtin_abort(_args: &[Value]) -> Result<Value, EvalError> {
+    Err(panic_error("aborted"))
+}
+
+fn builtin_panic(args: &[Value]) -> Result<Value, EvalError> {
     let message = if args.is_empty() {
         "explicit panic".to_string()
     } else {
@@ -176,12 +750,36 @@ fn builtin_panic(args: &[Value]) -> Result<Value, String> {
             .join(" ")
     };
 
-    Err(format!("panic: {}", message))
+    Err(panic_error(message))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::value::ClosureValue;
+
+    #[test]
+    fn test_materializing_builtins_respect_memory_limit() {
+        let ctx = crate::EvalContext::with_memory_limit(1024);
+        let mut interp = crate::Interpreter::with_context(ctx);
+
+        assert!(interp.eval_str("range(4)").is_ok());
+        for src in [
+            "range(9223372036854775807)",
+            "range(0, 1000000)",
+            "range_step(0, 1000000, 2)",
+            "repeat_n(0, 1000000)",
+            "repeat_n(\"xxxxxxxxxx\", 100)",
+        ] {
+            let err = interp.eval_str(src).unwrap_err();
+            assert!(
+                matches!(err.inner(), EvalError::MemoryLimitExceeded { .. }),
+                "{} should exceed the budget, got {:?}",
+                src,
+                err
+            );
+        }
+    }
 
     #[test]
     fn test_with_prelude_creates_environment() {
@@ -195,6 +793,13 @@ mod tests {
         assert!(env.contains("assert"));
         assert!(env.contains("assert_eq"));
         assert!(env.contains("panic"));
+        assert!(env.contains("Some"));
+        assert!(env.contains("None"));
+        assert!(env.contains("Ok"));
+        assert!(env.contains("Err"));
+        assert!(env.contains("String::from"));
+        assert!(env.contains("arity_of"));
+        assert!(env.contains("is_callable"));
     }
 
     #[test]
@@ -335,6 +940,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_builtin_type_of_generics() {
+        let ints = Value::vec(vec![Value::I64(1), Value::I64(2)]);
+        assert_eq!(
+            builtin_type_of(&[ints.clone()]).unwrap(),
+            Value::string("Vec<i64>")
+        );
+        assert_eq!(
+            builtin_type_of(&[Value::vec(vec![Value::I64(1), Value::Bool(true)])]).unwrap(),
+            Value::string("Vec<?>")
+        );
+        assert_eq!(
+            builtin_type_of(&[Value::vec(vec![Value::I64(1); 100_000])]).unwrap(),
+            Value::string("Vec<i64>")
+        );
+        assert_eq!(
+            builtin_type_of(&[Value::some(Value::Bool(true))]).unwrap(),
+            Value::string("Option<bool>")
+        );
+        assert_eq!(
+            builtin_type_of(&[Value::err(Value::string("bad"))]).unwrap(),
+            Value::string("Result<?, String>")
+        );
+        assert_eq!(
+            builtin_type_of(&[Value::Ref(crate::ValueRef {
+                value: Arc::new(ints),
+                tag: 0,
+            })])
+            .unwrap(),
+            Value::string("&Vec<i64>")
+        );
+    }
+
     #[test]
     fn test_builtin_type_of_wrong_arity() {
         let result = builtin_type_of(&[]);
@@ -346,6 +984,304 @@ mod tests {
         assert!(result.unwrap_err().contains("expects 1 argument"));
     }
 
+    #[test]
+    fn test_builtin_arity_of() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str("fn three(a: i64, b: i64, c: i64) {}")
+            .unwrap();
+
+        let some = |n| Value::some(Value::Usize(n));
+        assert_eq!(interp.eval_str("arity_of(|a, b| a + b)").unwrap(), some(2));
+        assert_eq!(interp.eval_str("arity_of(three)").unwrap(), some(3));
+        assert_eq!(interp.eval_str("arity_of(type_of)").unwrap(), some(1));
+        assert_eq!(interp.eval_str("arity_of(println)").unwrap(), Value::none());
+        assert!(interp.eval_str("arity_of(5)").is_err());
+    }
+
+    #[test]
+    fn test_builtin_is_callable() {
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(
+            interp.eval_str("is_callable(5)").unwrap(),
+            Value::Bool(false)
+        );
+        assert_eq!(
+            interp.eval_str("is_callable(|| 1)").unwrap(),
+            Value::Bool(true)
+        );
+        assert_eq!(
+            interp.eval_str("is_callable(Some)").unwrap(),
+            Value::Bool(true)
+        );
+    }
+
+    #[test]
+    fn test_builtin_reflection() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str("enum Color { Red, Green } struct Point { x: i64, y: i64 }")
+            .unwrap();
+
+        assert_eq!(
+            interp.eval_str("variant_name(Color::Green)").unwrap(),
+            Value::some(Value::string("Green"))
+        );
+        assert_eq!(
+            interp
+                .eval_str("field_names(Point { x: 1, y: 2 })")
+                .unwrap(),
+            Value::some(Value::vec(vec![Value::string("x"), Value::string("y")]))
+        );
+        assert_eq!(interp.eval_str("variant_name(1)").unwrap(), Value::none());
+        assert_eq!(
+            interp.eval_str("field_names(Color::Red)").unwrap(),
+            Value::none()
+        );
+    }
+
+    #[test]
+    fn test_builtin_smart_pointer_constructors() {
+        let mut interp = crate::Interpreter::new();
+        interp.eval_str("struct Point { x: i64, y: i64 }").unwrap();
+
+        assert_eq!(interp.eval_str("Box::new(5) + 1").unwrap(), Value::I64(6));
+        assert_eq!(
+            interp
+                .eval_str("let v = \"abc\".to_string(); Rc::new(v).len()")
+                .unwrap(),
+            Value::Usize(3)
+        );
+        assert_eq!(
+            interp
+                .eval_str("let p = Rc::new(Point { x: 1, y: 2 }); p.x + p.y")
+                .unwrap(),
+            Value::I64(3)
+        );
+        assert_eq!(
+            interp
+                .eval_str("let a = Arc::new(Box::new((4, 5))); a.1")
+                .unwrap(),
+            Value::I64(5)
+        );
+    }
+
+    #[test]
+    fn test_builtin_collection_constructors() {
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(interp.eval_str("Vec::new()").unwrap(), Value::vec(vec![]));
+        assert_eq!(
+            interp.eval_str("Vec::<i64>::with_capacity(10)").unwrap(),
+            Value::vec(vec![])
+        );
+        assert_eq!(interp.eval_str("String::new()").unwrap(), Value::string(""));
+        assert_eq!(
+            interp.eval_str("HashMap::new()").unwrap(),
+            Value::HashMap(Arc::new(std::collections::HashMap::new()))
+        );
+        assert_eq!(
+            interp
+                .eval_str("let mut v = Vec::new(); v.push(1); v")
+                .unwrap(),
+            Value::vec(vec![Value::I64(1)])
+        );
+    }
+
+    #[test]
+    fn test_ordering_enum_is_registered() {
+        let env = Environment::with_prelude();
+        assert_eq!(
+            env.enum_variants("Ordering"),
+            Some(
+                &[
+                    "Less".to_string(),
+                    "Equal".to_string(),
+                    "Greater".to_string()
+                ][..]
+            )
+        );
+        assert!(PreludeBuilder::empty()
+            .build()
+            .enum_variants("Ordering")
+            .is_some());
+    }
+
+    #[test]
+    fn test_builtin_swap_exchanges_bindings() {
+        let mut interp = crate::Interpreter::new();
+        let result = interp
+            .eval_str("let mut a = 1; let mut b = 2; swap(&mut a, &mut b); (a, b)")
+            .unwrap();
+        assert_eq!(result, Value::tuple(vec![Value::I64(2), Value::I64(1)]));
+
+        let result = interp
+            .eval_str("let mut v = [1, 2]; std::mem::swap(&mut v[0], &mut a); (v, a)")
+            .unwrap();
+        assert_eq!(
+            result,
+            Value::tuple(vec![
+                Value::array(vec![Value::I64(2), Value::I64(2)]),
+                Value::I64(1)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_builtin_replace_and_take() {
+        let mut interp = crate::Interpreter::new();
+        let result = interp
+            .eval_str(
+                "let mut s = String::from(\"old\");
+                 let prev = replace(&mut s, String::from(\"new\"));
+                 let mut n = 7;
+                 let taken = mem::take(&mut n);
+                 (prev, s, taken, n)",
+            )
+            .unwrap();
+        assert_eq!(
+            result,
+            Value::tuple(vec![
+                Value::string("old"),
+                Value::string("new"),
+                Value::I64(7),
+                Value::I64(0)
+            ])
+        );
+
+        assert!(interp.eval_str("take(5)").is_err());
+    }
+
+    #[test]
+    fn test_builtin_from_utf8() {
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(
+            interp.eval_str("from_utf8(b\"hi\")").unwrap(),
+            Value::ok(Value::string("hi"))
+        );
+        assert_eq!(
+            interp
+                .eval_str("String::from_utf8(b\"abc\".to_vec())")
+                .unwrap(),
+            Value::ok(Value::string("abc"))
+        );
+        assert!(matches!(
+            interp.eval_str("from_utf8(b\"\\xff\")").unwrap(),
+            Value::Result(r) if r.is_err()
+        ));
+        assert!(interp.eval_str("from_utf8(5)").is_err());
+    }
+
+    #[test]
+    fn test_builtin_range() {
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(
+            interp
+                .eval_str("{ let mut total = 0; for i in range(1, 4) { total += i; } total }")
+                .unwrap(),
+            Value::I64(6)
+        );
+        assert_eq!(
+            interp.eval_str("range(3)").unwrap(),
+            Value::vec(vec![Value::I64(0), Value::I64(1), Value::I64(2)])
+        );
+        assert_eq!(
+            interp.eval_str("range_step(0, 10, 2)").unwrap(),
+            Value::vec([0, 2, 4, 6, 8].into_iter().map(Value::I64).collect())
+        );
+
+        assert!(interp.eval_str("range_step(0, 10, 0)").is_err());
+        assert!(interp.eval_str("range_step(10, 0, -1)").is_err());
+        assert!(interp.eval_str("range()").is_err());
+        assert!(interp.eval_str("range(\"a\")").is_err());
+    }
+
+    #[test]
+    fn test_builtin_repeat_n_and_once() {
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(
+            interp.eval_str("repeat_n(\"x\", 3)").unwrap(),
+            Value::vec(vec![Value::string("x"); 3])
+        );
+        assert_eq!(
+            interp.eval_str("repeat_n(1, 0)").unwrap(),
+            Value::vec(vec![])
+        );
+        assert_eq!(
+            interp.eval_str("once(1)").unwrap(),
+            Value::vec(vec![Value::I64(1)])
+        );
+
+        assert!(interp.eval_str("repeat_n(1, -1)").is_err());
+        assert!(interp.eval_str("repeat_n(1, 1.5)").is_err());
+    }
+
+    #[test]
+    fn test_builtin_min_max_clamp() {
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(interp.eval_str("max(3, 7)").unwrap(), Value::I64(7));
+        assert_eq!(
+            interp.eval_str("std::cmp::min(3, 7)").unwrap(),
+            Value::I64(3)
+        );
+        assert_eq!(interp.eval_str("clamp(10, 0, 5)").unwrap(), Value::I64(5));
+        assert_eq!(interp.eval_str("clamp(-1, 0, 5)").unwrap(), Value::I64(0));
+        assert_eq!(interp.eval_str("clamp(3, 0, 5)").unwrap(), Value::I64(3));
+        assert_eq!(
+            interp.eval_str("min(\"a\", \"b\")").unwrap(),
+            Value::string("a")
+        );
+
+        assert!(interp.eval_str("max(1, \"a\")").is_err());
+        assert!(interp.eval_str("clamp(1, 5, 0)").is_err());
+    }
+
+    #[test]
+    fn test_builtin_exit_is_catchable() {
+        let mut interp = crate::Interpreter::new();
+
+        let err = interp.eval_str("exit(3)").unwrap_err();
+        assert!(matches!(err, EvalError::Exit { code: 3 }));
+        let err = interp.eval_str("std::process::exit(0)").unwrap_err();
+        assert_eq!(err.exit_code(), Some(0));
+
+        interp.eval_str("fn quit() { exit(7) }").unwrap();
+        assert_eq!(interp.eval_str("quit()").unwrap_err().exit_code(), Some(7));
+
+        assert!(interp.eval_str("abort()").unwrap_err().is_panic());
+        assert!(matches!(
+            interp.eval_str("exit(\"3\")"),
+            Err(EvalError::BuiltinError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_builtin_named_exit_is_not_intercepted() {
+        let env = PreludeBuilder::new()
+            .with_builtin("exit", 1, |_| Ok(Value::string("bye")))
+            .with_value(
+                "halt",
+                Value::BuiltinFn(BuiltinFn::control("halt", 0, builtin_abort)),
+            )
+            .with_builtin("fake_exit", 0, |_| {
+                Err(EvalError::Exit { code: 3 }.to_string())
+            })
+            .build();
+        let mut interp = crate::Interpreter::new();
+        *interp.env_mut() = env;
+
+        assert_eq!(interp.eval_str("exit(3)").unwrap(), Value::string("bye"));
+        assert!(interp.eval_str("halt()").unwrap_err().is_panic());
+        // An ordinary builtin's message is only ever a failure
+        assert!(matches!(
+            interp.eval_str("fake_exit()"),
+            Err(EvalError::BuiltinError { .. })
+        ));
+        assert_eq!(
+            interp.eval_str("process::exit(4)").unwrap_err().exit_code(),
+            Some(4)
+        );
+    }
+
     #[test]
     fn test_builtin_dbg_returns_value() {
         let value = Value::I64(42);
@@ -367,88 +1303,173 @@ mod tests {
     #[test]
     fn test_builtin_assert_true() {
         let result = builtin_assert(&[Value::Bool(true)]);
-        assert_eq!(result, Ok(Value::Unit));
+        assert!(matches!(result, Ok(Value::Unit)));
     }
 
     #[test]
     fn test_builtin_assert_false() {
         let result = builtin_assert(&[Value::Bool(false)]);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("assertion failed"));
+        assert_eq!(panic_message(result), "assertion failed");
     }
 
     #[test]
     fn test_builtin_assert_non_bool() {
         let result = builtin_assert(&[Value::I64(42)]);
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("expects bool"));
+        assert!(result.unwrap_err().to_string().contains("expects bool"));
     }
 
     #[test]
     fn test_builtin_assert_wrong_arity() {
         let result = builtin_assert(&[]);
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("expects 1 argument"));
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("expects 1 argument"));
 
         let result = builtin_assert(&[Value::Bool(true), Value::Bool(true)]);
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("expects 1 argument"));
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("expects 1 argument"));
     }
 
     #[test]
     fn test_builtin_assert_eq_equal() {
         let result = builtin_assert_eq(&[Value::I64(42), Value::I64(42)]);
-        assert_eq!(result, Ok(Value::Unit));
+        assert!(matches!(result, Ok(Value::Unit)));
     }
 
     #[test]
     fn test_builtin_assert_eq_not_equal() {
         let result = builtin_assert_eq(&[Value::I64(42), Value::I64(43)]);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("assertion failed"));
+        assert!(panic_message(result).starts_with("assertion failed"));
     }
 
     #[test]
     fn test_builtin_assert_eq_different_types() {
         let result = builtin_assert_eq(&[Value::I64(42), Value::string("42")]);
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("assertion failed"));
+        assert!(panic_message(result).contains("assertion failed"));
+    }
+
+    #[test]
+    fn test_builtin_assert_eq_distinct_closures() {
+        // ALLOW: ClosureValue is Send + Sync (syn::Expr is Send + Sync),
+        // but clippy can't verify this automatically
+        #[allow(clippy::arc_with_non_send_sync)]
+        let closure = || {
+            let c = ClosureValue::new(vec!["x".to_string()], syn::parse_quote!(x + 1), vec![]);
+            Value::Closure(Arc::new(c))
+        };
+
+        let err = panic_message(builtin_assert_eq(&[closure(), closure()]));
+        assert!(err.contains("different functions"));
+        assert!(err.contains("identity"));
+        assert!(!err.contains("!="));
+    }
+
+    #[test]
+    fn test_builtin_assert_eq_same_closure() {
+        // ALLOW: ClosureValue is Send + Sync (syn::Expr is Send + Sync),
+        // but clippy can't verify this automatically
+        #[allow(clippy::arc_with_non_send_sync)]
+        let f = Value::Closure(Arc::new(ClosureValue::new(
+            vec![],
+            syn::parse_quote!(1),
+            vec![],
+        )));
+        assert!(matches!(
+            builtin_assert_eq(&[f.clone(), f]),
+            Ok(Value::Unit)
+        ));
     }
 
     #[test]
     fn test_builtin_assert_eq_wrong_arity() {
         let result = builtin_assert_eq(&[Value::I64(1)]);
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("expects 2 arguments"));
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("expects 2 arguments"));
 
         let result = builtin_assert_eq(&[Value::I64(1), Value::I64(2), Value::I64(3)]);
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("expects 2 arguments"));
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("expects 2 arguments"));
+    }
+
+    /// The message of the panic a control builtin raises.
+    fn panic_message(result: Result<Value, EvalError>) -> String {
+        match result {
+            Err(EvalError::Panic { message, .. }) => message,
+            other => panic!("expected a panic, got {:?}", other),
+        }
     }
 
     #[test]
     fn test_builtin_panic_no_args() {
         let result = builtin_panic(&[]);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("explicit panic"));
+        assert_eq!(panic_message(result), "explicit panic");
     }
 
     #[test]
     fn test_builtin_panic_with_message() {
         let result = builtin_panic(&[Value::string("something went wrong")]);
-        assert!(result.is_err());
-        let err = result.unwrap_err();
-        assert!(err.contains("panic:"));
-        assert!(err.contains("something went wrong"));
+        assert_eq!(panic_message(result), "something went wrong");
     }
 
     #[test]
     fn test_builtin_panic_with_multiple_args() {
         let result = builtin_panic(&[Value::string("error"), Value::I64(42)]);
-        assert!(result.is_err());
-        let err = result.unwrap_err();
-        assert!(err.contains("panic:"));
-        assert!(err.contains("error"));
-        assert!(err.contains("42"));
+        assert_eq!(panic_message(result), "error 42");
+    }
+
+    #[test]
+    fn test_panicking_builtins_raise_panic() {
+        let mut interp = crate::Interpreter::new();
+
+        for src in ["panic(\"boom\")", "assert(false)", "assert_eq(1, 2)"] {
+            let err = interp.eval_str(src).unwrap_err();
+            assert!(err.is_panic(), "{} should panic, got {:?}", src, err);
+        }
+        let err = interp.eval_str("assert(1)").unwrap_err();
+        assert!(!err.is_panic());
+        assert!(matches!(err, EvalError::BuiltinError { .. }));
+    }
+
+    #[test]
+    fn test_prelude_builder_add_and_remove() {
+        let env = PreludeBuilder::new()
+            .with_builtin("sum", -1, |args| {
+                let mut total = 0;
+                for arg in args {
+                    total += arg.as_i64().ok_or("sum expects integers")?;
+                }
+                Ok(Value::I64(total))
+            })
+            .without("panic")
+            .build();
+
+        assert!(!env.contains("panic"));
+        assert!(env.contains("println"));
+
+        let Some(Value::BuiltinFn(sum)) = env.get("sum") else {
+            panic!("expected sum builtin");
+        };
+        let result = (sum.func)(&[Value::I64(1), Value::I64(2), Value::I64(3)]);
+        assert_eq!(result, Ok(Value::I64(6)));
+    }
+
+    #[test]
+    fn test_prelude_builder_replaces_existing() {
+        let builder = PreludeBuilder::new().with_builtin("dbg", 1, |args| Ok(args[0].clone()));
+        assert_eq!(builder.names().filter(|n| *n == "dbg").count(), 1);
+        assert!(PreludeBuilder::empty().build().is_empty());
     }
 }
@@ -144,6 +144,31 @@ pub enum EvalError {
     #[error("evaluation interrupted")]
     Interrupted,
 
+    /// Evaluation exceeded its time budget
+    #[error("evaluation timed out")]
+    Timeout,
+
+    /// REPL input is incomplete (unbalanced delimiters); more lines are
+    /// needed before it can be evaluated.
+    #[error("incomplete input")]
+    NeedMoreInput,
+
+    /// Evaluation exceeded its step budget
+    #[error("step limit of {limit} exceeded")]
+    StepLimitExceeded {
+        /// Configured step limit
+        limit: u64,
+    },
+
+    /// A value would exceed the memory budget
+    #[error("memory limit of {limit} bytes exceeded: {requested} bytes requested")]
+    MemoryLimitExceeded {
+        /// Configured memory limit in bytes
+        limit: usize,
+        /// Estimated heap size of the value being created
+        requested: usize,
+    },
+
     /// Stack overflow (too much recursion)
     #[error("stack overflow: maximum call depth ({max}) exceeded")]
     StackOverflow {
@@ -219,6 +244,38 @@ pub enum EvalError {
         span: Option<Span>,
     },
 
+    /// Panic raised by interpreted code (`panic!`, failed assertions,
+    /// `unwrap` on `None` or `Err`).
+    #[error("panicked: {message}")]
+    Panic {
+        /// Panic message
+        message: String,
+        /// Source span
+        span: Option<Span>,
+    },
+
+    /// Interpreted code reached a stub (`todo!` or `unimplemented!`).
+    ///
+    /// Kept apart from `Panic` so a host can tell an unfinished program
+    /// from a failing one.
+    #[error("{message}")]
+    NotImplementedRuntime {
+        /// Message, e.g. `not yet implemented: parser`
+        message: String,
+        /// Source span
+        span: Option<Span>,
+    },
+
+    /// Interpreted code asked to end the process (`std::process::exit`).
+    ///
+    /// The host process keeps running; the embedder decides what the exit
+    /// code means.
+    #[error("exited with code {code}")]
+    Exit {
+        /// Requested exit code
+        code: i32,
+    },
+
     /// Invalid assignment target.
     #[error("cannot assign to {kind}")]
     InvalidAssignTarget {
@@ -239,6 +296,15 @@ pub enum EvalError {
         span: Option<Span>,
     },
 
+    /// Negative value used as an index.
+    #[error("cannot index with negative value {index}")]
+    NegativeIndex {
+        /// Index that was used
+        index: i128,
+        /// Source span
+        span: Option<Span>,
+    },
+
     /// Key not found in map.
     #[error("key not found: {key}")]
     KeyNotFound {
@@ -284,9 +350,49 @@ pub enum EvalError {
         span: Option<Span>,
     },
 
+    /// Expanding a macro invocation failed.
+    #[error("failed to expand macro `{name}!`: {message}")]
+    MacroExpansionError {
+        /// Macro name
+        name: String,
+        /// Error message
+        message: String,
+        /// Source span
+        span: Option<Span>,
+    },
+
+    /// Native compilation of a function failed.
+    #[error("failed to compile `{name}`: {message}")]
+    CompileError {
+        /// Function name
+        name: String,
+        /// Error message
+        message: String,
+        /// Source span
+        span: Option<Span>,
+    },
+
     /// Environment error wrapper
     #[error(transparent)]
     Environment(#[from] EnvironmentError),
+
+    /// Error annotated with the interpreted call stack at the point of failure.
+    #[error("{error}{}", recursion_note(.error, .backtrace))]
+    WithBacktrace {
+        /// The underlying error
+        error: Box<EvalError>,
+        /// Active calls (function name, call-site span), outermost first
+        backtrace: Vec<(String, Option<Span>)>,
+    },
+}
+
+impl From<syn::Error> for EvalError {
+    fn from(err: syn::Error) -> Self {
+        EvalError::ParseError {
+            message: err.to_string(),
+            span: Some(err.span()),
+        }
+    }
 }
 
 impl EvalError {
@@ -302,6 +408,10 @@ impl EvalError {
             EvalError::UnsupportedExpr { span, .. } => *span,
             EvalError::UnsupportedLiteral { span, .. } => *span,
             EvalError::Interrupted => None,
+            EvalError::Timeout => None,
+            EvalError::NeedMoreInput => None,
+            EvalError::StepLimitExceeded { .. } => None,
+            EvalError::MemoryLimitExceeded { .. } => None,
             EvalError::StackOverflow { .. } => None,
             EvalError::ControlFlow(_) => None,
             EvalError::BreakOutsideLoop { span } => *span,
@@ -311,17 +421,172 @@ impl EvalError {
             EvalError::RefutablePattern { span, .. } => *span,
             EvalError::ArityMismatch { span, .. } => *span,
             EvalError::BuiltinError { span, .. } => *span,
+            EvalError::Panic { span, .. } => *span,
+            EvalError::NotImplementedRuntime { span, .. } => *span,
+            EvalError::Exit { .. } => None,
             EvalError::InvalidAssignTarget { span, .. } => *span,
             EvalError::IndexOutOfBounds { span, .. } => *span,
+            EvalError::NegativeIndex { span, .. } => *span,
             EvalError::KeyNotFound { span, .. } => *span,
             EvalError::UndefinedField { span, .. } => *span,
             EvalError::NonDivergingLetElse { span } => *span,
             EvalError::ParseError { span, .. } => *span,
             EvalError::TemplateError { span, .. } => *span,
+            EvalError::MacroExpansionError { span, .. } => *span,
+            EvalError::CompileError { span, .. } => *span,
             EvalError::Environment(_) => None,
+            EvalError::WithBacktrace { error, .. } => error.span(),
+        }
+    }
+
+    /// Fill in `span` if this error does not carry a span yet.
+    ///
+    /// Used at call sites to point errors raised by built-in code, which
+    /// has no source location of its own, at the call that raised them.
+    pub fn or_span(mut self, span: Option<Span>) -> Self {
+        if let Some(slot) = self.span_mut() {
+            if slot.is_none() {
+                *slot = span;
+            }
+        }
+        self
+    }
+
+    /// Get the span field of this error mutably, if it has one.
+    fn span_mut(&mut self) -> Option<&mut Option<Span>> {
+        match self {
+            EvalError::UndefinedVariable { span, .. }
+            | EvalError::TypeError { span, .. }
+            | EvalError::DivisionByZero { span }
+            | EvalError::IntegerOverflow { span }
+            | EvalError::InvalidUnaryOperand { span, .. }
+            | EvalError::InvalidBinaryOperands { span, .. }
+            | EvalError::UnsupportedExpr { span, .. }
+            | EvalError::UnsupportedLiteral { span, .. }
+            | EvalError::BreakOutsideLoop { span }
+            | EvalError::ContinueOutsideLoop { span }
+            | EvalError::ReturnOutsideFunction { span }
+            | EvalError::NonExhaustiveMatch { span, .. }
+            | EvalError::RefutablePattern { span, .. }
+            | EvalError::ArityMismatch { span, .. }
+            | EvalError::BuiltinError { span, .. }
+            | EvalError::Panic { span, .. }
+            | EvalError::NotImplementedRuntime { span, .. }
+            | EvalError::InvalidAssignTarget { span, .. }
+            | EvalError::IndexOutOfBounds { span, .. }
+            | EvalError::NegativeIndex { span, .. }
+            | EvalError::KeyNotFound { span, .. }
+            | EvalError::UndefinedField { span, .. }
+            | EvalError::NonDivergingLetElse { span }
+            | EvalError::ParseError { span, .. }
+            | EvalError::TemplateError { span, .. }
+            | EvalError::MacroExpansionError { span, .. }
+            | EvalError::CompileError { span, .. } => Some(span),
+            EvalError::WithBacktrace { error, .. } => error.span_mut(),
+            EvalError::Interrupted
+            | EvalError::Timeout
+            | EvalError::NeedMoreInput
+            | EvalError::StepLimitExceeded { .. }
+            | EvalError::MemoryLimitExceeded { .. }
+            | EvalError::StackOverflow { .. }
+            | EvalError::ControlFlow(_)
+            | EvalError::Exit { .. }
+            | EvalError::Environment(_) => None,
         }
     }
 
+    /// Attach a call-stack backtrace to this error.
+    ///
+    /// Control flow and errors that already carry a backtrace are returned
+    /// unchanged, so the innermost (most complete) backtrace wins.
+    pub fn with_backtrace(self, backtrace: Vec<(String, Option<Span>)>) -> Self {
+        match self {
+            EvalError::ControlFlow(_) | EvalError::WithBacktrace { .. } => self,
+            error => EvalError::WithBacktrace {
+                error: Box::new(error),
+                backtrace,
+            },
+        }
+    }
+
+    /// Get the call-stack backtrace, outermost call first, if one was captured.
+    pub fn backtrace(&self) -> Option<&[(String, Option<Span>)]> {
+        match self {
+            EvalError::WithBacktrace { backtrace, .. } => Some(backtrace),
+            _ => None,
+        }
+    }
+
+    /// Get the function that probably recursed without a base case.
+    ///
+    /// Only stack overflows are considered. The function is reported when
+    /// it occupies every one of the most recent frames of the backtrace.
+    pub fn possible_infinite_recursion(&self) -> Option<&str> {
+        let EvalError::WithBacktrace { error, backtrace } = self else {
+            return None;
+        };
+        recursive_function(error, backtrace)
+    }
+
+    /// Whether this error is a panic raised by interpreted code, looking
+    /// through any backtrace annotation.
+    pub fn is_panic(&self) -> bool {
+        matches!(self.inner(), EvalError::Panic { .. })
+    }
+
+    /// Get the exit code requested by interpreted code, looking through
+    /// any backtrace annotation.
+    pub fn exit_code(&self) -> Option<i32> {
+        match self.inner() {
+            EvalError::Exit { code } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// Get the underlying error, looking through any backtrace annotation.
+    pub fn inner(&self) -> &EvalError {
+        match self {
+            EvalError::WithBacktrace { error, .. } => error.inner(),
+            _ => self,
+        }
+    }
+
+    /// Render this error against the source it came from.
+    ///
+    /// When the error has a span that falls within `source`, the
+    /// offending line is shown with the span underlined by carets.
+    /// Otherwise only the message is rendered.
+    pub fn render(&self, source: &str) -> String {
+        let message = format!("error: {}", self.inner());
+
+        let Some(range) = self.span().map(|span| span.byte_range()) else {
+            return message;
+        };
+        if range.is_empty() || range.end > source.len() || !source.is_char_boundary(range.start) {
+            return message;
+        }
+
+        let line_start = source[..range.start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[range.start..]
+            .find('\n')
+            .map_or(source.len(), |i| range.start + i);
+        let line = &source[line_start..line_end];
+        let line_no = source[..line_start].matches('\n').count() + 1;
+
+        let column = source[line_start..range.start].chars().count();
+        let width = source
+            .get(range.start..range.end.min(line_end))
+            .map_or(1, |s| s.chars().count().max(1));
+
+        let gutter = " ".repeat(line_no.to_string().len());
+        format!(
+            "{message}\n{gutter}--> {line_no}:{col}\n{gutter} |\n{line_no} | {line}\n{gutter} | {pad}{carets}",
+            col = column + 1,
+            pad = " ".repeat(column),
+            carets = "^".repeat(width),
+        )
+    }
+
     /// Check if this is a control flow "error" (not a real error).
     pub fn is_control_flow(&self) -> bool {
         matches!(self, EvalError::ControlFlow(_))
@@ -336,6 +601,41 @@ impl EvalError {
     }
 }
 
+/// Number of most recent frames that must all be the same function for a
+/// stack overflow to be reported as possible infinite recursion.
+const RECURSION_WINDOW: usize = 16;
+
+/// The function filling the recent frames of `backtrace`, if `error` is
+/// a stack overflow.
+fn recursive_function<'a>(
+    error: &EvalError,
+    backtrace: &'a [(String, Option<Span>)],
+) -> Option<&'a str> {
+    let overflow = matches!(
+        error.inner(),
+        EvalError::StackOverflow { .. }
+            | EvalError::Environment(EnvironmentError::StackOverflow { .. })
+    );
+    if !overflow {
+        return None;
+    }
+
+    let recent = &backtrace[backtrace.len().saturating_sub(RECURSION_WINDOW)..];
+    let (name, _) = recent.last()?;
+    recent
+        .iter()
+        .all(|(frame, _)| frame == name)
+        .then_some(name.as_str())
+}
+
+/// Suffix for a backtraced error's message naming a runaway function.
+fn recursion_note(error: &EvalError, backtrace: &[(String, Option<Span>)]) -> String {
+    match recursive_function(error, backtrace) {
+        Some(name) => format!(" (possible infinite recursion in `{}`)", name),
+        None => String::new(),
+    }
+}
+
 /// Helper to get a type name for error messages.
 pub fn type_name(value: &crate::Value) -> &'static str {
     match value {
@@ -366,6 +666,7 @@ pub fn type_name(value: &crate::Value) -> &'static str {
         crate::Value::HashMap(_) => "HashMap",
         crate::Value::Option(_) => "Option",
         crate::Value::Result(_) => "Result",
+        crate::Value::Iter(_) => "Iter",
         crate::Value::Function(_) => "fn",
         crate::Value::Closure(_) => "closure",
         crate::Value::BuiltinFn(_) => "builtin_fn",
@@ -381,6 +682,19 @@ mod tests {
     use crate::Value;
     use std::sync::Arc;
 
+    #[test]
+    fn test_with_backtrace_keeps_innermost() {
+        let span = Span::call_site();
+        let err = EvalError::DivisionByZero { span: Some(span) }
+            .with_backtrace(vec![("inner".to_string(), None)])
+            .with_backtrace(vec![]);
+
+        assert_eq!(err.backtrace().unwrap().len(), 1);
+        assert!(err.span().is_some());
+        assert_eq!(err.to_string(), "division by zero");
+        assert!(EvalError::Interrupted.backtrace().is_none());
+    }
+
     #[test]
     fn test_treebeard_error_type_error_display() {
         let err = TreebeardError::TypeError {
@@ -540,4 +854,27 @@ mod tests {
         let err = EvalError::ReturnOutsideFunction { span: None };
         assert!(format!("{}", err).contains("return"));
     }
+
+    #[test]
+    fn test_render_points_at_span() {
+        let source = "1 / 0";
+        let expr: syn::Expr = syn::parse_str(source).unwrap();
+        let err = crate::Evaluate::eval(
+            &expr,
+            &mut crate::Environment::new(),
+            &crate::EvalContext::default(),
+        )
+        .unwrap_err();
+
+        let rendered = err.render(source);
+        assert!(rendered.starts_with("error: division by zero"));
+        assert!(rendered.contains("--> 1:3"));
+        assert!(rendered.ends_with("1 | 1 / 0\n  |   ^"));
+    }
+
+    #[test]
+    fn test_render_without_span() {
+        let err = EvalError::DivisionByZero { span: None };
+        assert_eq!(err.render("1 / 0"), "error: division by zero");
+    }
 }
@@ -2,6 +2,8 @@
 
 use crate::{EvalContext, EvalError, Value};
 
+use syn::spanned::Spanned;
+
 use super::Evaluate;
 
 /// Evaluate an array literal expression.
@@ -15,6 +17,7 @@ use super::Evaluate;
 ///
 /// Returns errors from evaluating array elements.
 /// Returns `TypeError` if repeat count is not an integer.
+/// Returns `MemoryLimitExceeded` if the array exceeds the memory budget.
 pub fn eval_array(
     array: &syn::ExprArray,
     env: &mut crate::Environment,
@@ -26,14 +29,22 @@ pub fn eval_array(
         .map(|elem| elem.eval(env, ctx))
         .collect::<Result<Vec<_>, _>>()?;
 
-    Ok(Value::array(elements))
+    let array = Value::array(elements);
+    if ctx.memory_limit.is_some() {
+        ctx.check_allocation(array.heap_size())?;
+    }
+    Ok(array)
 }
 
 /// Evaluate an array repeat expression `[value; count]`.
 ///
+/// The value is evaluated once and cloned into every element. Heap
+/// values share their storage until one of the copies is mutated.
+///
 /// # Errors
 ///
-/// Returns `TypeError` if count is not an integer.
+/// Returns `TypeError` if count is not a non-negative integer, or
+/// `MemoryLimitExceeded` if the array would exceed the memory budget.
 pub fn eval_array_repeat(
     repeat: &syn::ExprRepeat,
     env: &mut crate::Environment,
@@ -45,13 +56,24 @@ pub fn eval_array_repeat(
     // Evaluate the count
     let count_val = repeat.len.eval(env, ctx)?;
     let count = count_val.as_usize().ok_or_else(|| EvalError::TypeError {
-        message: format!(
-            "array repeat count must be integer, got {}",
-            crate::error::type_name(&count_val)
-        ),
-        span: None,
+        message: if count_val.is_integer() {
+            format!("array repeat count must be non-negative, got {}", count_val)
+        } else {
+            format!(
+                "array repeat count must be integer, got {}",
+                crate::error::type_name(&count_val)
+            )
+        },
+        span: Some(repeat.len.span()),
     })?;
 
+    // Check the budget before allocating
+    if ctx.memory_limit.is_some() {
+        ctx.check_allocation(
+            count.saturating_mul(std::mem::size_of::<Value>().saturating_add(value.heap_size())),
+        )?;
+    }
+
     // Create array with repeated value
     let elements = vec![value; count];
 
@@ -176,6 +198,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_array_repeat_heap_value() {
+        let mut interp = crate::Interpreter::new();
+        let result = interp.eval_str("[\"a\"; 3]").unwrap();
+        assert_eq!(result, Value::array(vec![Value::string("a"); 3]));
+
+        let result = interp.eval_str("const N: usize = 2; [[1, 2]; N]").unwrap();
+        let row = Value::array(vec![Value::I64(1), Value::I64(2)]);
+        assert_eq!(result, Value::array(vec![row.clone(), row]));
+    }
+
+    #[test]
+    fn test_array_repeat_bad_count() {
+        let mut interp = crate::Interpreter::new();
+
+        let Err(EvalError::TypeError { message, .. }) = interp.eval_str("[0; -1]") else {
+            panic!("expected a type error");
+        };
+        assert!(message.contains("non-negative"));
+
+        let Err(EvalError::TypeError { message, .. }) = interp.eval_str("[0; 1.5]") else {
+            panic!("expected a type error");
+        };
+        assert!(message.contains("must be integer"));
+    }
+
     #[test]
     fn test_nested_arrays() {
         let expr: syn::Expr = syn::parse_str("[[1, 2], [3, 4]]").unwrap();
@@ -195,4 +243,32 @@ mod tests {
             panic!("Expected Array");
         }
     }
+
+    #[test]
+    fn test_memory_limit() {
+        let mut interp = crate::Interpreter::with_context(EvalContext::with_memory_limit(1024));
+
+        assert!(interp.eval_str("[0; 4]").is_ok());
+        for src in ["[0; 1000000]", "0..1000000", "[[1, 2, 3]; 100]"] {
+            assert!(
+                matches!(
+                    interp.eval_str(src),
+                    Err(EvalError::MemoryLimitExceeded { limit: 1024, .. })
+                ),
+                "{} should exceed the budget",
+                src
+            );
+        }
+
+        // Doubling a string eventually outgrows the budget
+        let err = interp
+            .eval_str(
+                "let mut s = String::from(\"x\"); \
+                 let mut i = 0; \
+                 while i < 11 { s += s.clone(); i += 1; }",
+            )
+            .unwrap_err();
+        assert!(matches!(err, EvalError::MemoryLimitExceeded { .. }));
+        assert_eq!(interp.eval_str("s.len()").unwrap(), Value::Usize(1024));
+    }
 }
@@ -1,5 +1,10 @@
 //! Assignment expression evaluation
 
+use std::sync::Arc;
+
+use quote::ToTokens;
+use syn::spanned::Spanned;
+
 use crate::{Environment, EvalContext, EvalError, Value};
 
 use super::Evaluate;
@@ -23,53 +28,329 @@ pub fn eval_assign(
     Ok(Value::Unit)
 }
 
+/// Check whether `expr` is a place that `assign_to_expr` can write to.
+pub(crate) fn is_place(expr: &syn::Expr) -> bool {
+    match expr {
+        syn::Expr::Path(_) => true,
+        syn::Expr::Field(field) => is_place(&field.base),
+        syn::Expr::Index(index) => is_place(&index.expr),
+        _ => false,
+    }
+}
+
+/// A resolved assignment target.
+///
+/// Places nest, so `a.b[i]` is an element of a field of the variable `a`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Place {
+    /// A variable: `x`
+    Var(String),
+    /// An element of a place: `v[i]`, with the index already evaluated
+    Index(Box<Place>, Value),
+    /// A field of a place: `p.x` or `t.0`
+    Field(Box<Place>, String),
+}
+
+impl Place {
+    /// The name of the variable this place lives in.
+    pub fn root(&self) -> &str {
+        match self {
+            Place::Var(name) => name,
+            Place::Index(base, _) | Place::Field(base, _) => base.root(),
+        }
+    }
+}
+
+/// Resolve an lvalue expression to a [`Place`].
+///
+/// Index expressions are evaluated here, exactly once, outermost first.
+///
+/// # Errors
+///
+/// Returns `InvalidAssignTarget` for expressions that are not places,
+/// such as `f().x`.
+pub fn resolve_place(
+    expr: &syn::Expr,
+    env: &mut Environment,
+    ctx: &EvalContext,
+) -> Result<Place, EvalError> {
+    match expr {
+        syn::Expr::Path(path) => Ok(Place::Var(path_to_string(path))),
+
+        syn::Expr::Index(index_expr) => {
+            let base = resolve_place(&index_expr.expr, env, ctx)?;
+            let index = index_expr.index.eval(env, ctx)?;
+            Ok(Place::Index(Box::new(base), index))
+        }
+
+        syn::Expr::Field(field_expr) => {
+            let base = resolve_place(&field_expr.base, env, ctx)?;
+            let key = match &field_expr.member {
+                syn::Member::Named(ident) => ident.to_string(),
+                syn::Member::Unnamed(index) => index.index.to_string(),
+            };
+            Ok(Place::Field(Box::new(base), key))
+        }
+
+        syn::Expr::Paren(paren) => resolve_place(&paren.expr, env, ctx),
+
+        syn::Expr::Unary(unary) if matches!(unary.op, syn::UnOp::Deref(_)) => {
+            Err(EvalError::InvalidAssignTarget {
+                kind: "a place through a reference (not yet supported)".to_string(),
+                span: Some(expr.span()),
+            })
+        }
+
+        _ => Err(EvalError::InvalidAssignTarget {
+            kind: format!("`{}`", expr.to_token_stream()),
+            span: Some(expr.span()),
+        }),
+    }
+}
+
+/// Read the current value stored at a place.
+///
+/// # Errors
+///
+/// Returns the same errors as evaluating the equivalent expression.
+pub fn read_place(place: &Place, env: &Environment) -> Result<Value, EvalError> {
+    match place {
+        Place::Var(name) => env
+            .get(name)
+            .cloned()
+            .ok_or_else(|| EvalError::UndefinedVariable {
+                name: name.clone(),
+                span: None,
+            }),
+        Place::Index(base, index) => get_index(&read_place(base, env)?, index),
+        Place::Field(base, key) => get_field(&read_place(base, env)?, key),
+    }
+}
+
+/// Write a value to a place, copying shared containers on write.
+///
+/// # Errors
+///
+/// Returns `ImmutableBinding` if the variable is not `mut`, or an index or
+/// field error if the place does not exist in the container.
+pub fn assign_to_place(place: Place, value: Value, env: &mut Environment) -> Result<(), EvalError> {
+    match place {
+        Place::Var(name) => env.assign(&name, value).map_err(EvalError::from),
+        Place::Index(base, index) => set_index(place_slot(&base, env)?, index, value),
+        Place::Field(base, key) => set_field(place_slot(&base, env)?, &key, value),
+    }
+}
+
+/// Mutate the value stored at a place in situ.
+///
+/// The closure receives the stored value directly, so containers it
+/// updates with `Arc::make_mut` are only copied when shared.
+///
+/// # Errors
+///
+/// Returns `ImmutableBinding` if the variable is not `mut`, an index or
+/// field error if the place does not exist, or the closure's error.
+pub fn modify_place<R>(
+    place: &Place,
+    env: &mut Environment,
+    f: impl FnOnce(&mut Value) -> Result<R, EvalError>,
+) -> Result<R, EvalError> {
+    f(place_slot(place, env)?)
+}
+
+/// Get a mutable reference to the value stored at a place, copying shared
+/// containers along the way.
+fn place_slot<'a>(place: &Place, env: &'a mut Environment) -> Result<&'a mut Value, EvalError> {
+    match place {
+        Place::Var(name) => env
+            .get_mut(name)?
+            .ok_or_else(|| EvalError::UndefinedVariable {
+                name: name.clone(),
+                span: None,
+            }),
+        Place::Index(base, index) => index_slot(place_slot(base, env)?, index),
+        Place::Field(base, key) => field_slot(place_slot(base, env)?, key),
+    }
+}
+
 /// Assign a value to an expression (lvalue).
 ///
 /// # Errors
 ///
 /// Returns `InvalidAssignTarget` for unsupported assignment targets.
-fn assign_to_expr(
+pub(crate) fn assign_to_expr(
     target: &syn::Expr,
     value: Value,
     env: &mut Environment,
-    _ctx: &EvalContext,
+    ctx: &EvalContext,
 ) -> Result<(), EvalError> {
+    let place = resolve_place(target, env, ctx)?;
+    assign_to_place(place, value, env)
+}
+
+/// Read an element of a vec, array, or hashmap.
+fn get_index(target: &Value, index: &Value) -> Result<Value, EvalError> {
+    match target {
+        Value::Vec(items) | Value::Array(items) => {
+            let idx = index_as_usize(index)?;
+            items.get(idx).cloned().ok_or(EvalError::IndexOutOfBounds {
+                index: idx,
+                len: items.len(),
+                span: None,
+            })
+        }
+
+        Value::HashMap(map) => map
+            .get(&crate::value::HashableValue(index.clone()))
+            .cloned()
+            .ok_or_else(|| EvalError::KeyNotFound {
+                key: format!("{:?}", index),
+                span: None,
+            }),
+
+        other => Err(EvalError::TypeError {
+            message: format!("cannot index into {}", crate::error::type_name(other)),
+            span: None,
+        }),
+    }
+}
+
+/// Read a field of a struct or tuple.
+fn get_field(target: &Value, key: &str) -> Result<Value, EvalError> {
+    match target {
+        Value::Struct(s) => s
+            .fields
+            .get(key)
+            .cloned()
+            .ok_or_else(|| EvalError::UndefinedField {
+                field: key.to_string(),
+                type_name: s.type_name.clone(),
+                span: None,
+            }),
+
+        Value::Tuple(items) => {
+            let idx = tuple_index(key, target)?;
+            items.get(idx).cloned().ok_or(EvalError::IndexOutOfBounds {
+                index: idx,
+                len: items.len(),
+                span: None,
+            })
+        }
+
+        other => Err(field_type_error(key, other)),
+    }
+}
+
+/// Overwrite a field of a struct or tuple in place.
+///
+/// Shared data is copied first (`Arc::make_mut`), so other holders of the
+/// old value are unaffected.
+fn set_field(target: &mut Value, key: &str, value: Value) -> Result<(), EvalError> {
+    *field_slot(target, key)? = value;
+    Ok(())
+}
+
+/// Get a mutable reference to a field of a struct or tuple.
+fn field_slot<'a>(target: &'a mut Value, key: &str) -> Result<&'a mut Value, EvalError> {
     match target {
-        // Simple variable assignment
-        syn::Expr::Path(path) => {
-            let name = path_to_string(path);
-            env.assign(&name, value).map_err(EvalError::from)?;
-            Ok(())
+        Value::Struct(s) => {
+            let s = Arc::make_mut(s);
+            let type_name = s.type_name.clone();
+            s.fields
+                .get_mut(key)
+                .ok_or_else(|| EvalError::UndefinedField {
+                    field: key.to_string(),
+                    type_name,
+                    span: None,
+                })
         }
 
-        // Index assignment: vec[i] = value
-        syn::Expr::Index(_index_expr) => {
-            // Index assignment requires mutable reference tracking
-            // Will be implemented in src/eval/index.rs
-            Err(EvalError::UnsupportedExpr {
-                kind: "index assignment (use index module)".to_string(),
+        Value::Tuple(_) => {
+            let idx = tuple_index(key, target)?;
+            let Value::Tuple(items) = target else {
+                unreachable!()
+            };
+            let items = Arc::make_mut(items);
+            let len = items.len();
+            items.get_mut(idx).ok_or(EvalError::IndexOutOfBounds {
+                index: idx,
+                len,
                 span: None,
             })
         }
 
-        // Field assignment: struct.field = value
-        syn::Expr::Field(_field_expr) => {
-            // Field assignment requires mutable reference tracking
-            // Will be implemented in src/eval/field.rs
-            Err(EvalError::UnsupportedExpr {
-                kind: "field assignment (use field module)".to_string(),
+        other => Err(field_type_error(key, other)),
+    }
+}
+
+/// Get a mutable reference to an existing element of a vec, array, or
+/// hashmap.
+fn index_slot<'a>(target: &'a mut Value, index: &Value) -> Result<&'a mut Value, EvalError> {
+    match target {
+        Value::Vec(_) | Value::Array(_) => {
+            let idx = index_as_usize(index)?;
+            let items = target.vec_make_mut()?;
+            let len = items.len();
+            items.get_mut(idx).ok_or(EvalError::IndexOutOfBounds {
+                index: idx,
+                len,
                 span: None,
             })
         }
 
-        // Invalid assignment target
-        _ => Err(EvalError::InvalidAssignTarget {
-            kind: format!("{:?}", target),
+        Value::HashMap(map) => Arc::make_mut(map)
+            .get_mut(&crate::value::HashableValue(index.clone()))
+            .ok_or_else(|| EvalError::KeyNotFound {
+                key: format!("{:?}", index),
+                span: None,
+            }),
+
+        other => Err(EvalError::TypeError {
+            message: format!("cannot index into {}", crate::error::type_name(other)),
             span: None,
         }),
     }
 }
 
+/// Parse a tuple field name (`0`, `1`, ...) into an index.
+fn tuple_index(key: &str, target: &Value) -> Result<usize, EvalError> {
+    key.parse().map_err(|_| field_type_error(key, target))
+}
+
+fn field_type_error(key: &str, target: &Value) -> EvalError {
+    EvalError::TypeError {
+        message: format!("no field `{}` on {}", key, crate::error::type_name(target)),
+        span: None,
+    }
+}
+
+fn index_as_usize(index: &Value) -> Result<usize, EvalError> {
+    super::index::index_to_usize(index, "collection", None)
+}
+
+/// Overwrite an element of a vec, array, or hashmap in place.
+///
+/// Hashmap assignment inserts the key if it is missing, mirroring
+/// `insert`; vec and array indices must already be in bounds.
+fn set_index(target: &mut Value, index: Value, value: Value) -> Result<(), EvalError> {
+    if let Value::HashMap(map) = target {
+        if !crate::value::HashableValue::is_hashable(&index) {
+            return Err(EvalError::TypeError {
+                message: format!(
+                    "hashmap key must be hashable, got {}",
+                    crate::error::type_name(&index)
+                ),
+                span: None,
+            });
+        }
+        Arc::make_mut(map).insert(crate::value::HashableValue(index), value);
+        return Ok(());
+    }
+
+    *index_slot(target, &index)? = value;
+    Ok(())
+}
+
 /// Convert a path to a string identifier.
 fn path_to_string(path: &syn::ExprPath) -> String {
     path.path
@@ -132,4 +413,172 @@ mod tests {
             panic!("Expected Assign");
         }
     }
+
+    #[test]
+    fn test_field_assignment() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str("struct P { x: i64, y: i64 } let mut p = P { x: 1, y: 2 };")
+            .unwrap();
+
+        interp.eval_str("p.x = 5").unwrap();
+        assert_eq!(interp.eval_str("p.x").unwrap(), Value::I64(5));
+        assert_eq!(interp.eval_str("p.y").unwrap(), Value::I64(2));
+    }
+
+    #[test]
+    fn test_resolve_place() {
+        let mut env = Environment::new();
+        let ctx = EvalContext::default();
+        env.define("i".to_string(), Value::I64(2));
+
+        let place = |src: &str, env: &mut Environment| {
+            let expr: syn::Expr = syn::parse_str(src).unwrap();
+            resolve_place(&expr, env, &ctx)
+        };
+
+        assert_eq!(place("x", &mut env).unwrap(), Place::Var("x".into()));
+        let var = |name: &str| Box::new(Place::Var(name.into()));
+        assert_eq!(
+            place("v[i]", &mut env).unwrap(),
+            Place::Index(var("v"), Value::I64(2))
+        );
+        assert_eq!(
+            place("p.x", &mut env).unwrap(),
+            Place::Field(var("p"), "x".into())
+        );
+        assert_eq!(
+            place("m[i].x", &mut env).unwrap(),
+            Place::Field(Box::new(Place::Index(var("m"), Value::I64(2))), "x".into())
+        );
+        assert!(matches!(
+            place("f().x", &mut env),
+            Err(EvalError::InvalidAssignTarget { .. })
+        ));
+    }
+
+    #[test]
+    fn test_assignment_through_reference_unsupported() {
+        let mut interp = crate::Interpreter::new();
+        interp.eval_str("let mut x = 1; let r = &mut x;").unwrap();
+        for source in ["*r = 5", "*r += 5"] {
+            let err = interp.eval_str(source).unwrap_err();
+            assert!(
+                matches!(
+                    err.inner(),
+                    EvalError::InvalidAssignTarget { kind, span: Some(_) }
+                        if kind.contains("through a reference")
+                ),
+                "{}",
+                source
+            );
+        }
+
+        let err = interp.eval_str("f().x = 1").unwrap_err();
+        assert_eq!(err.inner().to_string(), "cannot assign to `f ()`");
+    }
+
+    #[test]
+    fn test_index_assignment() {
+        let mut interp = crate::Interpreter::new();
+        interp.env_mut().define_with_mode(
+            "v".to_string(),
+            Value::vec(vec![Value::I64(1), Value::I64(2), Value::I64(3)]),
+            crate::BindingMode::Mutable,
+        );
+
+        interp.eval_str("v[0] = 9").unwrap();
+        assert_eq!(
+            interp.env().get("v"),
+            Some(&Value::vec(vec![
+                Value::I64(9),
+                Value::I64(2),
+                Value::I64(3)
+            ]))
+        );
+        assert!(matches!(
+            interp.eval_str("v[3] = 0"),
+            Err(EvalError::IndexOutOfBounds {
+                index: 3,
+                len: 3,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_index_assignment_copies_shared_vec() {
+        let mut env = Environment::new();
+        let shared = Value::vec(vec![Value::I64(1)]);
+        env.define_with_mode("v".to_string(), shared.clone(), crate::BindingMode::Mutable);
+
+        assign_to_place(
+            Place::Index(Box::new(Place::Var("v".into())), Value::I64(0)),
+            Value::I64(7),
+            &mut env,
+        )
+        .unwrap();
+        assert_eq!(shared, Value::vec(vec![Value::I64(1)]));
+        assert_eq!(env.get("v"), Some(&Value::vec(vec![Value::I64(7)])));
+    }
+
+    #[test]
+    fn test_nested_field_assignment() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str(
+                "struct Window { width: i64, height: i64 }
+                 struct Config { window: Window, title: String }
+                 let mut config = Config {
+                     window: Window { width: 640, height: 480 },
+                     title: String::from(\"app\"),
+                 };
+                 let before = config.window.width;",
+            )
+            .unwrap();
+
+        interp.eval_str("config.window.width = 800").unwrap();
+        assert_eq!(
+            interp.eval_str("config.window.width").unwrap(),
+            Value::I64(800)
+        );
+        assert_eq!(
+            interp.eval_str("config.window.height").unwrap(),
+            Value::I64(480)
+        );
+        assert_eq!(interp.eval_str("before").unwrap(), Value::I64(640));
+
+        interp.eval_str("config.window.height += 20").unwrap();
+        assert_eq!(
+            interp.eval_str("config.window.height").unwrap(),
+            Value::I64(500)
+        );
+    }
+
+    #[test]
+    fn test_nested_index_and_field_assignment() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str(
+                "struct P { xs: Vec<i64> }
+                 let mut grid = [[0, 0], [0, 0]];
+                 let mut p = P { xs: Vec::new() };
+                 p.xs.push(1);",
+            )
+            .unwrap();
+
+        interp.eval_str("grid[1][0] = 7; p.xs[0] = 9;").unwrap();
+        assert_eq!(interp.eval_str("grid[1][0]").unwrap(), Value::I64(7));
+        assert_eq!(interp.eval_str("grid[0][0]").unwrap(), Value::I64(0));
+        assert_eq!(interp.eval_str("p.xs[0]").unwrap(), Value::I64(9));
+    }
+
+    #[test]
+    fn test_field_init_shorthand() {
+        let mut interp = crate::Interpreter::new();
+        let result = interp
+            .eval_str("struct Point { x: i64, y: i64 } let x = 1; let y = 2; let p = Point { x, y }; p.x * 10 + p.y")
+            .unwrap();
+        assert_eq!(result, Value::I64(12));
+    }
 }
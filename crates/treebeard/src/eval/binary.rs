@@ -1,5 +1,7 @@
 //! Binary operation evaluation
 
+use std::cmp::Ordering;
+
 use crate::error::type_name;
 use crate::{Environment, EvalContext, EvalError, Value};
 
@@ -10,8 +12,8 @@ impl Evaluate for syn::ExprBinary {
     fn eval(&self, env: &mut Environment, ctx: &EvalContext) -> Result<Value, EvalError> {
         // Short-circuit evaluation for && and ||
         match &self.op {
-            syn::BinOp::And(_) => return eval_and(&self.left, &self.right, env, ctx),
-            syn::BinOp::Or(_) => return eval_or(&self.left, &self.right, env, ctx),
+            syn::BinOp::And(_) => return eval_logical(self, "&&", false, env, ctx),
+            syn::BinOp::Or(_) => return eval_logical(self, "||", true, env, ctx),
             _ => {}
         }
 
@@ -24,10 +26,18 @@ impl Evaluate for syn::ExprBinary {
         let left = self.left.eval(env, ctx)?;
         let right = self.right.eval(env, ctx)?;
         let span = Some(self.op.span());
+        let (left, right) = if is_shift(&self.op) {
+            (left, right)
+        } else {
+            adapt_literal_operands(&self.left, left, &self.right, right)?
+        };
 
         match &self.op {
             // Arithmetic
-            syn::BinOp::Add(_) => eval_add(left, right, span),
+            syn::BinOp::Add(_) => {
+                check_concat(&left, &right, ctx)?;
+                eval_add(left, right, span)
+            }
             syn::BinOp::Sub(_) => eval_sub(left, right, span),
             syn::BinOp::Mul(_) => eval_mul(left, right, span),
             syn::BinOp::Div(_) => eval_div(left, right, span),
@@ -36,10 +46,10 @@ impl Evaluate for syn::ExprBinary {
             // Comparison
             syn::BinOp::Eq(_) => Ok(Value::Bool(left == right)),
             syn::BinOp::Ne(_) => Ok(Value::Bool(left != right)),
-            syn::BinOp::Lt(_) => eval_lt(left, right, span),
-            syn::BinOp::Le(_) => eval_le(left, right, span),
-            syn::BinOp::Gt(_) => eval_gt(left, right, span),
-            syn::BinOp::Ge(_) => eval_ge(left, right, span),
+            syn::BinOp::Lt(_) => eval_ordering(left, right, "<", Ordering::is_lt, span),
+            syn::BinOp::Le(_) => eval_ordering(left, right, "<=", Ordering::is_le, span),
+            syn::BinOp::Gt(_) => eval_ordering(left, right, ">", Ordering::is_gt, span),
+            syn::BinOp::Ge(_) => eval_ordering(left, right, ">=", Ordering::is_ge, span),
 
             // Bitwise
             syn::BinOp::BitAnd(_) => eval_bitand(left, right, span),
@@ -71,6 +81,40 @@ impl Evaluate for syn::ExprBinary {
     }
 }
 
+/// Check if a binary operator is a shift, whose operands' types are
+/// independent of each other.
+fn is_shift(op: &syn::BinOp) -> bool {
+    matches!(
+        op,
+        syn::BinOp::Shl(_)
+            | syn::BinOp::Shr(_)
+            | syn::BinOp::ShlAssign(_)
+            | syn::BinOp::ShrAssign(_)
+    )
+}
+
+/// Give an untyped literal operand the numeric type of the other operand,
+/// so `n * 2` with `n: u32` multiplies two `u32`s.
+fn adapt_literal_operands(
+    left_expr: &syn::Expr,
+    left: Value,
+    right_expr: &syn::Expr,
+    right: Value,
+) -> Result<(Value, Value), EvalError> {
+    let left = adapt_literal(left_expr, left, &right)?;
+    let right = adapt_literal(right_expr, right, &left)?;
+    Ok((left, right))
+}
+
+/// Adapt `value` to the type of `other` if `expr` is an untyped literal.
+fn adapt_literal(expr: &syn::Expr, value: Value, other: &Value) -> Result<Value, EvalError> {
+    if super::literal::is_untyped_literal(expr) {
+        super::literal::adapt_untyped(value, other).map_err(|e| e.or_span(Some(expr.span())))
+    } else {
+        Ok(value)
+    }
+}
+
 /// Check if a binary operator is a compound assignment operator.
 fn is_assignment_op(op: &syn::BinOp) -> bool {
     matches!(
@@ -96,8 +140,20 @@ fn eval_compound_assignment(
     env: &mut Environment,
     ctx: &EvalContext,
 ) -> Result<Value, EvalError> {
+    // Resolve the target once, so an index expression is evaluated once
+    let place = match super::assign::resolve_place(&binary.left, env, ctx) {
+        Ok(place) => place,
+        Err(EvalError::UnsupportedExpr { kind, .. }) => {
+            return Err(EvalError::InvalidAssignTarget {
+                kind: format!("compound assignment to {} (not yet supported)", kind),
+                span: Some(binary.op.span()),
+            })
+        }
+        Err(e) => return Err(e),
+    };
+
     // Get the current value of the left side
-    let left_val = binary.left.eval(env, ctx)?;
+    let left_val = super::assign::read_place(&place, env)?;
 
     // Evaluate the right side
     let right_val = binary.right.eval(env, ctx)?;
@@ -106,7 +162,10 @@ fn eval_compound_assignment(
 
     // Apply the underlying operation
     let new_val = match &binary.op {
-        syn::BinOp::AddAssign(_) => eval_add(left_val, right_val, span)?,
+        syn::BinOp::AddAssign(_) => {
+            check_concat(&left_val, &right_val, ctx)?;
+            eval_add(left_val, right_val, span)?
+        }
         syn::BinOp::SubAssign(_) => eval_sub(left_val, right_val, span)?,
         syn::BinOp::MulAssign(_) => eval_mul(left_val, right_val, span)?,
         syn::BinOp::DivAssign(_) => eval_div(left_val, right_val, span)?,
@@ -119,80 +178,55 @@ fn eval_compound_assignment(
         _ => unreachable!(),
     };
 
-    // Assign the new value back
-    // For simple variable assignments only (complex lvalues not yet supported)
-    if let syn::Expr::Path(path) = binary.left.as_ref() {
-        let name = super::path::path_to_string(&path.path);
-        env.assign(&name, new_val).map_err(EvalError::from)?;
-        Ok(Value::Unit)
-    } else {
-        Err(EvalError::InvalidAssignTarget {
-            kind: "compound assignment to complex lvalue (not yet supported)".to_string(),
-            span,
-        })
-    }
+    // Assign the new value back through the same place as plain assignment
+    super::assign::assign_to_place(place, new_val, env)?;
+    Ok(Value::Unit)
 }
 
 // ═══════════════════════════════════════════════════════════════════════
 // Short-Circuit Logical Operators
 // ═══════════════════════════════════════════════════════════════════════
 
-fn eval_and(
-    left: &syn::Expr,
-    right: &syn::Expr,
-    env: &mut Environment,
-    ctx: &EvalContext,
-) -> Result<Value, EvalError> {
-    let left_val = left.eval(env, ctx)?;
-    match left_val {
-        Value::Bool(false) => Ok(Value::Bool(false)), // Short-circuit
-        Value::Bool(true) => {
-            let right_val = right.eval(env, ctx)?;
-            match right_val {
-                Value::Bool(b) => Ok(Value::Bool(b)),
-                other => Err(EvalError::InvalidBinaryOperands {
-                    op: "&&".to_string(),
-                    left_type: "bool".to_string(),
-                    right_type: type_name(&other).to_string(),
-                    span: None,
-                }),
-            }
-        }
-        other => Err(EvalError::InvalidBinaryOperands {
-            op: "&&".to_string(),
-            left_type: type_name(&other).to_string(),
-            right_type: "?".to_string(),
-            span: None,
-        }),
-    }
-}
-
-fn eval_or(
-    left: &syn::Expr,
-    right: &syn::Expr,
+/// Evaluate `&&` or `||`. The right operand is only evaluated if the left
+/// one doesn't decide the result: `short_circuit` is the left value that
+/// does (`false` for `&&`, `true` for `||`).
+///
+/// Under `truthy_conditions` the operands may be any value; the result is
+/// still a bool.
+///
+/// # Errors
+///
+/// Returns `InvalidBinaryOperands` if either operand isn't a bool. If the
+/// left one isn't, the right is not evaluated and its type is given as `_`.
+fn eval_logical(
+    expr: &syn::ExprBinary,
+    op: &str,
+    short_circuit: bool,
     env: &mut Environment,
     ctx: &EvalContext,
 ) -> Result<Value, EvalError> {
-    let left_val = left.eval(env, ctx)?;
-    match left_val {
-        Value::Bool(true) => Ok(Value::Bool(true)), // Short-circuit
-        Value::Bool(false) => {
-            let right_val = right.eval(env, ctx)?;
-            match right_val {
-                Value::Bool(b) => Ok(Value::Bool(b)),
-                other => Err(EvalError::InvalidBinaryOperands {
-                    op: "||".to_string(),
-                    left_type: "bool".to_string(),
-                    right_type: type_name(&other).to_string(),
-                    span: None,
+    let span = Some(expr.op.span());
+
+    let left = expr.left.eval(env, ctx)?;
+    match ctx.condition(&left) {
+        Some(b) if b == short_circuit => Ok(Value::Bool(b)),
+        Some(_) => {
+            let right = expr.right.eval(env, ctx)?;
+            match ctx.condition(&right) {
+                Some(b) => Ok(Value::Bool(b)),
+                None => Err(EvalError::InvalidBinaryOperands {
+                    op: op.to_string(),
+                    left_type: type_name(&left).to_string(),
+                    right_type: type_name(&right).to_string(),
+                    span,
                 }),
             }
         }
-        other => Err(EvalError::InvalidBinaryOperands {
-            op: "||".to_string(),
-            left_type: type_name(&other).to_string(),
-            right_type: "?".to_string(),
-            span: None,
+        None => Err(EvalError::InvalidBinaryOperands {
+            op: op.to_string(),
+            left_type: type_name(&left).to_string(),
+            right_type: "_".to_string(),
+            span,
         }),
     }
 }
@@ -201,6 +235,14 @@ fn eval_or(
 // Arithmetic Operations
 // ═══════════════════════════════════════════════════════════════════════
 
+/// Check that concatenating two strings fits the memory budget.
+fn check_concat(left: &Value, right: &Value, ctx: &EvalContext) -> Result<(), EvalError> {
+    match (left, right) {
+        (Value::String(a), Value::String(b)) => ctx.check_allocation(a.len() + b.len()),
+        _ => Ok(()),
+    }
+}
+
 fn eval_add(
     left: Value,
     right: Value,
@@ -589,54 +631,27 @@ fn eval_rem(
 // Comparison Operations
 // ═══════════════════════════════════════════════════════════════════════
 
-macro_rules! impl_comparison {
-    ($name:ident, $op:tt, $op_str:expr) => {
-        fn $name(
-            left: Value,
-            right: Value,
-            span: Option<proc_macro2::Span>,
-        ) -> Result<Value, EvalError> {
-            match (&left, &right) {
-                // Integers
-                (Value::I8(a), Value::I8(b)) => Ok(Value::Bool(a $op b)),
-                (Value::I16(a), Value::I16(b)) => Ok(Value::Bool(a $op b)),
-                (Value::I32(a), Value::I32(b)) => Ok(Value::Bool(a $op b)),
-                (Value::I64(a), Value::I64(b)) => Ok(Value::Bool(a $op b)),
-                (Value::I128(a), Value::I128(b)) => Ok(Value::Bool(a $op b)),
-                (Value::Isize(a), Value::Isize(b)) => Ok(Value::Bool(a $op b)),
-                (Value::U8(a), Value::U8(b)) => Ok(Value::Bool(a $op b)),
-                (Value::U16(a), Value::U16(b)) => Ok(Value::Bool(a $op b)),
-                (Value::U32(a), Value::U32(b)) => Ok(Value::Bool(a $op b)),
-                (Value::U64(a), Value::U64(b)) => Ok(Value::Bool(a $op b)),
-                (Value::U128(a), Value::U128(b)) => Ok(Value::Bool(a $op b)),
-                (Value::Usize(a), Value::Usize(b)) => Ok(Value::Bool(a $op b)),
-
-                // Floats
-                (Value::F32(a), Value::F32(b)) => Ok(Value::Bool(a $op b)),
-                (Value::F64(a), Value::F64(b)) => Ok(Value::Bool(a $op b)),
-
-                // Chars
-                (Value::Char(a), Value::Char(b)) => Ok(Value::Bool(a $op b)),
-
-                // Strings
-                (Value::String(a), Value::String(b)) => Ok(Value::Bool(a $op b)),
-
-                _ => Err(EvalError::InvalidBinaryOperands {
-                    op: $op_str.to_string(),
-                    left_type: type_name(&left).to_string(),
-                    right_type: type_name(&right).to_string(),
-                    span,
-                }),
-            }
-        }
-    };
+/// Evaluate an ordering comparison, mapping the result with `test`.
+///
+/// Comparisons involving NaN are false, as in Rust.
+fn eval_ordering(
+    left: Value,
+    right: Value,
+    op: &str,
+    test: fn(Ordering) -> bool,
+    span: Option<proc_macro2::Span>,
+) -> Result<Value, EvalError> {
+    match left.partial_order(&right) {
+        Ok(ord) => Ok(Value::Bool(ord.is_some_and(test))),
+        Err(_) => Err(EvalError::InvalidBinaryOperands {
+            op: op.to_string(),
+            left_type: type_name(&left).to_string(),
+            right_type: type_name(&right).to_string(),
+            span,
+        }),
+    }
 }
 
-impl_comparison!(eval_lt, <, "<");
-impl_comparison!(eval_le, <=, "<=");
-impl_comparison!(eval_gt, >, ">");
-impl_comparison!(eval_ge, >=, ">=");
-
 // ═══════════════════════════════════════════════════════════════════════
 // Bitwise Operations
 // ═══════════════════════════════════════════════════════════════════════
@@ -852,6 +867,26 @@ fn eval_shr(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_eval_ordering() {
+        let tuple = |a, b| Value::tuple(vec![Value::I64(a), Value::I64(b)]);
+        let lt = |l, r| eval_ordering(l, r, "<", Ordering::is_lt, None);
+
+        assert_eq!(lt(tuple(1, 2), tuple(1, 3)).unwrap(), Value::Bool(true));
+        assert_eq!(
+            lt(Value::Bool(false), Value::Bool(true)).unwrap(),
+            Value::Bool(true)
+        );
+        assert_eq!(
+            lt(Value::F64(f64::NAN), Value::F64(1.0)).unwrap(),
+            Value::Bool(false)
+        );
+        assert!(matches!(
+            lt(Value::I64(1), Value::string("1")),
+            Err(EvalError::InvalidBinaryOperands { .. })
+        ));
+    }
+
     #[test]
     fn test_eval_add_integers() {
         let result = eval_add(Value::I64(5), Value::I64(3), None).unwrap();
@@ -940,6 +975,58 @@ mod tests {
         assert_eq!(result, Value::Bool(false));
     }
 
+    #[test]
+    fn test_logical_operand_errors() {
+        let mut env = crate::Environment::new();
+        let ctx = crate::EvalContext::default();
+
+        let expr: syn::ExprBinary = syn::parse_quote!(1 && true);
+        match expr.eval(&mut env, &ctx).unwrap_err() {
+            EvalError::InvalidBinaryOperands {
+                left_type,
+                right_type,
+                span,
+                ..
+            } => {
+                assert_eq!(left_type, "i64");
+                assert_eq!(right_type, "_");
+                assert!(span.is_some());
+            }
+            other => panic!("expected invalid operands, got {:?}", other),
+        }
+
+        let expr: syn::ExprBinary = syn::parse_quote!(false || "s");
+        match expr.eval(&mut env, &ctx).unwrap_err() {
+            EvalError::InvalidBinaryOperands {
+                left_type,
+                right_type,
+                span,
+                ..
+            } => {
+                assert_eq!(left_type, "bool");
+                assert_eq!(right_type, "String");
+                assert!(span.is_some());
+            }
+            other => panic!("expected invalid operands, got {:?}", other),
+        }
+
+        // The right side is not evaluated when the left decides the result
+        let expr: syn::ExprBinary = syn::parse_quote!(false && undefined);
+        assert_eq!(expr.eval(&mut env, &ctx).unwrap(), Value::Bool(false));
+    }
+
+    #[test]
+    fn test_logical_truthy_conditions() {
+        let mut env = crate::Environment::new();
+        let mut ctx = crate::EvalContext::default();
+        ctx.truthy_conditions = true;
+
+        let expr: syn::ExprBinary = syn::parse_quote!(1 && "s");
+        assert_eq!(expr.eval(&mut env, &ctx).unwrap(), Value::Bool(true));
+        let expr: syn::ExprBinary = syn::parse_quote!(0 || "");
+        assert_eq!(expr.eval(&mut env, &ctx).unwrap(), Value::Bool(false));
+    }
+
     #[test]
     fn test_eval_shl() {
         let result = eval_shl(Value::I64(4), Value::U32(2), None).unwrap();
@@ -963,4 +1050,28 @@ mod tests {
         let op: syn::BinOp = syn::parse_quote!(+);
         assert!(!is_assignment_op(&op));
     }
+
+    #[test]
+    fn test_compound_assign_index() {
+        let mut interp = crate::Interpreter::new();
+        interp.env_mut().define_with_mode(
+            "v".to_string(),
+            Value::vec(vec![Value::I64(1), Value::I64(2), Value::I64(3)]),
+            crate::BindingMode::Mutable,
+        );
+        let result = interp.eval_str("v[1] += 10; v").unwrap();
+        assert_eq!(
+            result,
+            Value::vec(vec![Value::I64(1), Value::I64(12), Value::I64(3)])
+        );
+    }
+
+    #[test]
+    fn test_compound_assign_field() {
+        let mut interp = crate::Interpreter::new();
+        let result = interp
+            .eval_str("struct P { x: i64 } let mut p = P { x: 21 }; p.x *= 2; p.x")
+            .unwrap();
+        assert_eq!(result, Value::I64(42));
+    }
 }
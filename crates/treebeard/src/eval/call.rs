@@ -2,30 +2,134 @@
 
 use std::sync::Arc;
 
+use syn::spanned::Spanned;
+
 use crate::eval::control::ControlFlow;
-use crate::{BuiltinFn, ClosureValue, Environment, EvalContext, EvalError, FunctionValue, Value};
+use crate::{
+    BindingMode, BuiltinFn, ClosureValue, CompiledFn, Environment, EvalContext, EvalError,
+    FunctionValue, HashableValue, IterValue, Value,
+};
 
-use super::Evaluate;
+use super::{assign, Evaluate};
 
 impl Evaluate for syn::ExprCall {
     fn eval(&self, env: &mut Environment, ctx: &EvalContext) -> Result<Value, EvalError> {
         // Evaluate the function expression
         let func_value = self.func.eval(env, ctx)?;
 
-        // Evaluate arguments
-        let args: Vec<Value> = self
-            .args
-            .iter()
-            .map(|arg| arg.eval(env, ctx))
-            .collect::<Result<Vec<_>, _>>()?;
+        // Evaluate arguments, remembering where `&mut place` ones point
+        let mut args = Vec::with_capacity(self.args.len());
+        let mut borrowed = Vec::new();
+        for arg in &self.args {
+            match mut_borrowed_place(arg) {
+                Some(target) => {
+                    let place = assign::resolve_place(target, env, ctx)?;
+                    let reference = Value::reference_mut(assign::read_place(&place, env)?);
+                    borrowed.push((place, reference.clone()));
+                    args.push(reference);
+                }
+                None => args.push(arg.eval(env, ctx)?),
+            }
+        }
 
         // Call the function
-        call_value(func_value, args, env, ctx, None)
+        let result = call_value(func_value, args, env, ctx, Some(self.span()))?;
+
+        // References hold a copy of their referent, so write back anything
+        // the callee changed through them
+        for (place, reference) in borrowed {
+            if let Value::RefMut(r) = reference {
+                let value = r
+                    .value
+                    .read()
+                    .map_err(|_| EvalError::TypeError {
+                        message: "failed to acquire read lock on RefMut".to_string(),
+                        span: Some(self.span()),
+                    })?
+                    .clone();
+                assign::assign_to_place(place, value, env)?;
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// The place borrowed by a `&mut place` argument, if `arg` is one.
+fn mut_borrowed_place(arg: &syn::Expr) -> Option<&syn::Expr> {
+    match arg {
+        syn::Expr::Reference(reference)
+            if reference.mutability.is_some() && assign::is_place(&reference.expr) =>
+        {
+            Some(&reference.expr)
+        }
+        _ => None,
     }
 }
 
 impl Evaluate for syn::ExprMethodCall {
     fn eval(&self, env: &mut Environment, ctx: &EvalContext) -> Result<Value, EvalError> {
+        let method_name = self.method.to_string();
+
+        // Built-in methods have no source location, so their errors point
+        // at the method name
+        let at_method = |e: EvalError| e.or_span(Some(self.method.span()));
+
+        // Mutating Vec, String, HashMap and iterator methods update the
+        // receiver's place in situ
+        if (is_vec_mutator(&method_name)
+            || is_vec_sorter(&method_name)
+            || is_string_mutator(&method_name)
+            || is_map_mutator(&method_name)
+            || is_iter_mutator(&method_name))
+            && assign::is_place(&self.receiver)
+        {
+            let place = assign::resolve_place(&self.receiver, env, ctx)?;
+            let current = assign::read_place(&place, env)?;
+            let mutates = match &current {
+                Value::Vec(_) => is_vec_mutator(&method_name) || is_vec_sorter(&method_name),
+                Value::String(_) => is_string_mutator(&method_name),
+                Value::HashMap(_) => is_map_mutator(&method_name),
+                Value::Iter(_) => is_iter_mutator(&method_name),
+                _ => false,
+            };
+            if mutates {
+                let args = self
+                    .args
+                    .iter()
+                    .map(|arg| arg.eval(env, ctx))
+                    .collect::<Result<Vec<_>, _>>()?;
+                // `current` shares the receiver's data, so it must be
+                // dropped before the update, or the update copies it all
+                let sort_items = match &current {
+                    Value::Vec(items) if is_vec_sorter(&method_name) => Some(items.to_vec()),
+                    _ => None,
+                };
+                if let Value::HashMap(_) = &current {
+                    return get_or_insert_with(&place, current, &args, env, ctx).map_err(at_method);
+                }
+                let is_iter = matches!(current, Value::Iter(_));
+                drop(current);
+
+                if is_iter {
+                    return assign::modify_place(&place, env, advance_iter).map_err(at_method);
+                }
+                if let Some(items) = sort_items {
+                    let sorted =
+                        sort_with(&method_name, items, &args, env, ctx).map_err(at_method)?;
+                    return assign::modify_place(&place, env, |slot| {
+                        *slot.vec_make_mut()? = sorted;
+                        Ok(Value::Unit)
+                    });
+                }
+                return assign::modify_place(&place, env, |slot| match slot {
+                    Value::String(s) => call_string_mutator(&method_name, Arc::make_mut(s), &args),
+                    slot => call_vec_mutator(&method_name, slot.vec_make_mut()?, &args, ctx),
+                })
+                .map_err(at_method);
+            }
+        }
+
         // Evaluate the receiver
         let receiver = self.receiver.eval(env, ctx)?;
 
@@ -35,14 +139,29 @@ impl Evaluate for syn::ExprMethodCall {
             args.push(arg.eval(env, ctx)?);
         }
 
-        // Look up the method by name
-        let method_name = self.method.to_string();
-
         // First, try built-in methods on the receiver type
-        if let Some(result) = try_builtin_method(&method_name, &args)? {
+        if let Some(result) =
+            try_combinator_method(&method_name, &args, env, ctx).map_err(at_method)?
+        {
+            return Ok(result);
+        }
+        if let Some(result) = try_builtin_method(&method_name, &args).map_err(at_method)? {
             return Ok(result);
         }
 
+        // Mutating methods on a temporary update a copy that is then dropped
+        match &args[0] {
+            Value::Vec(v) if is_vec_mutator(&method_name) => {
+                let mut v = v.as_ref().clone();
+                return call_vec_mutator(&method_name, &mut v, &args[1..], ctx).map_err(at_method);
+            }
+            Value::String(s) if is_string_mutator(&method_name) => {
+                let mut s = s.as_ref().clone();
+                return call_string_mutator(&method_name, &mut s, &args[1..]).map_err(at_method);
+            }
+            _ => {}
+        }
+
         // Otherwise, look up as a regular function
         let func = env
             .get(&method_name)
@@ -52,6 +171,18 @@ impl Evaluate for syn::ExprMethodCall {
                 span: Some(self.method.span()),
             })?;
 
+        // `&mut self` methods write their final `self` back to the receiver
+        if let Value::Function(f) = &func {
+            if f.mut_self && assign::is_place(&self.receiver) {
+                let (value, new_self) =
+                    invoke_function(f, args, env, ctx, Some(self.method.span()))?;
+                if let Some(new_self) = new_self {
+                    assign::assign_to_expr(&self.receiver, new_self, env, ctx)?;
+                }
+                return Ok(value);
+            }
+        }
+
         call_value(func, args, env, ctx, Some(self.method.span()))
     }
 }
@@ -70,9 +201,10 @@ pub fn call_value(
     span: Option<proc_macro2::Span>,
 ) -> Result<Value, EvalError> {
     match func {
-        Value::Function(f) => call_function(&f, args, env, ctx),
-        Value::BuiltinFn(f) => call_builtin(&f, args, span),
-        Value::Closure(c) => call_closure(&c, args, env, ctx),
+        Value::Function(f) => call_function(&f, args, env, ctx, span),
+        Value::BuiltinFn(f) => call_builtin(&f, args, ctx, span),
+        Value::Closure(c) => call_closure(&c, args, env, ctx, span),
+        Value::CompiledFn(f) => call_compiled(&f, args, span),
         other => Err(EvalError::TypeError {
             message: format!(
                 "expected function, found `{}`",
@@ -89,47 +221,95 @@ fn call_function(
     args: Vec<Value>,
     env: &mut Environment,
     ctx: &EvalContext,
+    span: Option<proc_macro2::Span>,
 ) -> Result<Value, EvalError> {
-    // Check arity
-    if args.len() != func.params.len() {
+    invoke_function(func, args, env, ctx, span).map(|(value, _)| value)
+}
+
+/// Call a user-defined function, also returning the final value of `self`
+/// for `&mut self` methods (`None` otherwise).
+fn invoke_function(
+    func: &FunctionValue,
+    args: Vec<Value>,
+    env: &mut Environment,
+    ctx: &EvalContext,
+    span: Option<proc_macro2::Span>,
+) -> Result<(Value, Option<Value>), EvalError> {
+    // Check arity (a rest parameter accepts any number of extra arguments)
+    let arity_ok = if func.variadic {
+        args.len() >= func.min_arity()
+    } else {
+        args.len() == func.params.len()
+    };
+    if !arity_ok {
         return Err(EvalError::ArityMismatch {
-            expected: func.params.len(),
+            expected: func.min_arity(),
             got: args.len(),
             name: func.name.clone(),
-            span: None,
+            span,
         });
     }
 
+    // Collect any extra arguments into the rest parameter
+    let mut args = args;
+    if func.variadic && !func.params.is_empty() {
+        let rest = args.split_off(func.min_arity());
+        args.push(Value::vec(rest));
+    }
+
+    // Coerce arguments to the declared parameter types
+    for (arg, ty) in args.iter_mut().zip(&func.param_types) {
+        *arg = arg.coerce_to_type_name(ty).map_err(|e| e.or_span(span))?;
+    }
+
     // Track call depth (stack overflow protection)
-    env.enter_call()?;
+    env.enter_named_call(func.name.clone(), span, ctx.max_call_depth)?;
+    ctx.record_call(&func.name);
 
-    // Create new scope for function body
-    env.push_frame();
+    // Create new scope for function body, hiding the caller's locals
+    env.push_function_frame(func.item_scope);
 
-    // Bind parameters to arguments
-    for (param, arg) in func.params.iter().zip(args.into_iter()) {
-        env.define(param.clone(), arg);
+    // Bind parameters to arguments (`&mut self` is a mutable binding)
+    for (param, arg) in func.params.iter().zip(args) {
+        if func.mut_self && param == "self" {
+            env.define_with_mode(param.clone(), arg, BindingMode::Mutable);
+        } else {
+            env.define(param.clone(), arg);
+        }
     }
 
     // Evaluate the function body
-    let result = eval_function_body(&func.body, env, ctx);
+    let result = eval_function_body(&func.body, env, ctx).map_err(|e| capture_backtrace(e, env));
+    let final_self = if func.mut_self {
+        env.get("self").cloned()
+    } else {
+        None
+    };
 
     // Clean up
     env.pop_frame();
     env.exit_call();
 
     // Handle return control flow
-    match result {
-        Ok(value) => Ok(value),
-        Err(EvalError::ControlFlow(ControlFlow::Return { value })) => Ok(value),
-        Err(e) => Err(e),
-    }
+    let value = match result {
+        Ok(value) => value,
+        Err(EvalError::ControlFlow(ControlFlow::Return { value })) => value,
+        Err(e) => return Err(e),
+    };
+
+    // Coerce the result to the declared return type
+    let value = match &func.return_type {
+        Some(ty) => value.coerce_to_type_name(ty).map_err(|e| e.or_span(span))?,
+        None => value,
+    };
+    Ok((value, final_self))
 }
 
 /// Call a built-in function.
 fn call_builtin(
     func: &BuiltinFn,
     args: Vec<Value>,
+    ctx: &EvalContext,
     span: Option<proc_macro2::Span>,
 ) -> Result<Value, EvalError> {
     // Check arity (if not variadic)
@@ -142,8 +322,45 @@ fn call_builtin(
         });
     }
 
-    // Call the native function
-    (func.func)(&args).map_err(|e| EvalError::BuiltinError {
+    // Check the budget before a builtin that allocates
+    if let (Some(estimate), Some(_)) = (func.allocation(), ctx.memory_limit) {
+        ctx.check_allocation(estimate(&args))?;
+    }
+
+    // Call the native function; control builtins raise their own errors,
+    // such as `Exit`, leaving process control to the embedder
+    if let Some(control) = func.control_fn() {
+        return control(&args).map_err(|e| e.or_span(span));
+    }
+    (func.func)(&args).map_err(|message| EvalError::BuiltinError {
+        name: func.name.clone(),
+        message,
+        span,
+    })
+}
+
+/// Call a natively compiled function.
+fn call_compiled(
+    func: &CompiledFn,
+    args: Vec<Value>,
+    span: Option<proc_macro2::Span>,
+) -> Result<Value, EvalError> {
+    if args.len() != func.arity {
+        return Err(EvalError::ArityMismatch {
+            expected: func.arity,
+            got: args.len(),
+            name: func.name.clone(),
+            span,
+        });
+    }
+
+    let entry = func.func.as_ref().ok_or_else(|| EvalError::BuiltinError {
+        name: func.name.clone(),
+        message: "compiled function is not loaded".to_string(),
+        span,
+    })?;
+
+    entry(&args).map_err(|e| EvalError::BuiltinError {
         name: func.name.clone(),
         message: e,
         span,
@@ -156,6 +373,7 @@ fn call_closure(
     args: Vec<Value>,
     env: &mut Environment,
     ctx: &EvalContext,
+    span: Option<proc_macro2::Span>,
 ) -> Result<Value, EvalError> {
     // Check arity
     if args.len() != closure.params.len() {
@@ -163,12 +381,12 @@ fn call_closure(
             expected: closure.params.len(),
             got: args.len(),
             name: "<closure>".to_string(),
-            span: None,
+            span,
         });
     }
 
     // Track call depth
-    env.enter_call()?;
+    env.enter_named_call("<closure>", span, ctx.max_call_depth)?;
 
     // Create new scope
     env.push_frame();
@@ -179,12 +397,15 @@ fn call_closure(
     }
 
     // Bind parameters
-    for (param, arg) in closure.params.iter().zip(args.into_iter()) {
+    for (param, arg) in closure.params.iter().zip(args) {
         env.define(param.clone(), arg);
     }
 
     // Evaluate the closure body
-    let result = closure.body.eval(env, ctx);
+    let result = closure
+        .body
+        .eval(env, ctx)
+        .map_err(|e| capture_backtrace(e, env));
 
     // Clean up
     env.pop_frame();
@@ -198,6 +419,24 @@ fn call_closure(
     }
 }
 
+/// Attach the current call stack to an error raised inside a call.
+///
+/// `return` is not an error at this level, so it passes through untouched.
+/// `break` and `continue` cannot reach a loop outside the call, so they
+/// become `BreakOutsideLoop` and `ContinueOutsideLoop`.
+fn capture_backtrace(err: EvalError, env: &Environment) -> EvalError {
+    let err = match err {
+        EvalError::ControlFlow(ControlFlow::Break { .. }) => {
+            EvalError::BreakOutsideLoop { span: None }
+        }
+        EvalError::ControlFlow(ControlFlow::Continue { .. }) => {
+            EvalError::ContinueOutsideLoop { span: None }
+        }
+        err => err,
+    };
+    err.with_backtrace(env.call_stack().to_vec())
+}
+
 /// Evaluate a function body (block).
 fn eval_function_body(
     body: &syn::Block,
@@ -239,10 +478,14 @@ fn eval_stmt_in_function(
             super::item::eval_item(item, env, ctx)?;
             Ok(Value::Unit)
         }
-        syn::Stmt::Macro(_) => Err(EvalError::UnsupportedExpr {
-            kind: "macro statement".to_string(),
-            span: None,
-        }),
+        syn::Stmt::Macro(stmt_macro) => {
+            let value = super::macros::eval_macro(&stmt_macro.mac, env, ctx)?;
+            if stmt_macro.semi_token.is_some() {
+                Ok(Value::Unit)
+            } else {
+                Ok(value)
+            }
+        }
     }
 }
 
@@ -299,6 +542,42 @@ fn try_builtin_method(method: &str, args: &[Value]) -> Result<Option<Value>, Eva
             }
         }
 
+        // Char methods
+        (Value::Char(c), "is_alphabetic") if method_args.is_empty() => {
+            Ok(Some(Value::Bool(c.is_alphabetic())))
+        }
+        (Value::Char(c), "is_numeric") if method_args.is_empty() => {
+            Ok(Some(Value::Bool(c.is_numeric())))
+        }
+        (Value::Char(c), "is_alphanumeric") if method_args.is_empty() => {
+            Ok(Some(Value::Bool(c.is_alphanumeric())))
+        }
+        (Value::Char(c), "is_whitespace") if method_args.is_empty() => {
+            Ok(Some(Value::Bool(c.is_whitespace())))
+        }
+        // Multi-char case mappings, like `'ß'` to "SS", give a String
+        (Value::Char(c), "to_uppercase") if method_args.is_empty() => {
+            Ok(Some(case_mapped(c.to_uppercase())))
+        }
+        (Value::Char(c), "to_lowercase") if method_args.is_empty() => {
+            Ok(Some(case_mapped(c.to_lowercase())))
+        }
+        (Value::Char(c), "to_digit") if method_args.len() == 1 => {
+            let radix = match method_args[0].as_i64() {
+                Some(radix @ 2..=36) => radix as u32,
+                _ => {
+                    return Err(EvalError::BuiltinError {
+                        name: "to_digit".to_string(),
+                        message: format!("radix must be in 2..=36, got {}", method_args[0]),
+                        span: None,
+                    })
+                }
+            };
+            Ok(Some(Value::Option(Arc::new(
+                c.to_digit(radix).map(Value::U32),
+            ))))
+        }
+
         // Vec methods
         (Value::Vec(v), "len") if method_args.is_empty() => Ok(Some(Value::Usize(v.len()))),
         (Value::Vec(v), "is_empty") if method_args.is_empty() => {
@@ -328,6 +607,48 @@ fn try_builtin_method(method: &str, args: &[Value]) -> Result<Option<Value>, Eva
         (Value::Vec(v), "contains") if method_args.len() == 1 => {
             Ok(Some(Value::Bool(v.contains(&method_args[0]))))
         }
+        (Value::Vec(v), "enumerate") if method_args.is_empty() => Ok(Some(Value::vec(
+            v.iter()
+                .enumerate()
+                .map(|(i, item)| Value::tuple(vec![Value::Usize(i), item.clone()]))
+                .collect(),
+        ))),
+        (Value::Vec(v), "zip") if method_args.len() == 1 => match &method_args[0] {
+            Value::Vec(other) | Value::Array(other) => Ok(Some(Value::vec(
+                v.iter()
+                    .zip(other.iter())
+                    .map(|(a, b)| Value::tuple(vec![a.clone(), b.clone()]))
+                    .collect(),
+            ))),
+            _ => Ok(None),
+        },
+        (Value::Vec(v) | Value::Array(v), "binary_search") if method_args.len() == 1 => {
+            binary_search(v, &method_args[0]).map(Some)
+        }
+        // Without type-directed collect, a vec collects to itself
+        (Value::Vec(_), "collect") if method_args.is_empty() => Ok(Some(receiver.clone())),
+        (Value::Vec(v) | Value::Array(v), "collect_string") if method_args.is_empty() => {
+            concat_strings("collect_string", v.iter(), "").map(Some)
+        }
+        (Value::Vec(v) | Value::Array(v), "join") if method_args.len() == 1 => {
+            match &method_args[0] {
+                Value::String(sep) => concat_strings("join", v.iter(), sep).map(Some),
+                _ => Ok(None),
+            }
+        }
+
+        // Bytes methods
+        (Value::Bytes(b), "len") if method_args.is_empty() => Ok(Some(Value::Usize(b.len()))),
+        (Value::Bytes(b), "is_empty") if method_args.is_empty() => {
+            Ok(Some(Value::Bool(b.is_empty())))
+        }
+        (Value::Bytes(b), "get") if method_args.len() == 1 => Ok(method_args[0]
+            .as_usize()
+            .map(|idx| Value::Option(Arc::new(b.get(idx).copied().map(Value::U8))))),
+        (Value::Bytes(b), "to_vec") if method_args.is_empty() => {
+            Ok(Some(Value::vec(b.iter().copied().map(Value::U8).collect())))
+        }
+        (Value::Bytes(_), "as_slice") if method_args.is_empty() => Ok(Some(receiver.clone())),
 
         // Array methods (same as Vec)
         (Value::Array(v), "len") if method_args.is_empty() => Ok(Some(Value::Usize(v.len()))),
@@ -341,6 +662,28 @@ fn try_builtin_method(method: &str, args: &[Value]) -> Result<Option<Value>, Eva
             Ok(Some(Value::Option(Arc::new(v.last().cloned()))))
         }
 
+        // Iterator methods
+        (
+            Value::Vec(_) | Value::Array(_) | Value::HashMap(_) | Value::Iter(_),
+            "iter" | "into_iter",
+        ) if method_args.is_empty() => Ok(IterValue::from_value(receiver).map(Value::Iter)),
+        (Value::Iter(iter), "next") if method_args.is_empty() => {
+            Ok(Some(Value::Option(Arc::new(iter.clone().next()))))
+        }
+        (Value::Iter(iter), "collect") if method_args.is_empty() => {
+            Ok(Some(Value::vec(iter.clone().collect())))
+        }
+        (Value::Iter(iter), "collect_string") if method_args.is_empty() => concat_strings(
+            "collect_string",
+            iter.clone().collect::<Vec<_>>().iter(),
+            "",
+        )
+        .map(Some),
+        // Unlike a Value clone, an iterator clone gets its own cursor
+        (Value::Iter(iter), "clone") if method_args.is_empty() => Ok(Some(Value::Iter(
+            IterValue::new(Arc::new(iter.remaining().to_vec())),
+        ))),
+
         // Option methods
         (Value::Option(opt), "is_some") if method_args.is_empty() => {
             Ok(Some(Value::Bool(opt.is_some())))
@@ -350,8 +693,7 @@ fn try_builtin_method(method: &str, args: &[Value]) -> Result<Option<Value>, Eva
         }
         (Value::Option(opt), "unwrap") if method_args.is_empty() => match opt.as_ref() {
             Some(v) => Ok(Some(v.clone())),
-            None => Err(EvalError::BuiltinError {
-                name: "unwrap".to_string(),
+            None => Err(EvalError::Panic {
                 message: "called `Option::unwrap()` on a `None` value".to_string(),
                 span: None,
             }),
@@ -362,6 +704,10 @@ fn try_builtin_method(method: &str, args: &[Value]) -> Result<Option<Value>, Eva
                 None => method_args[0].clone(),
             }))
         }
+        (Value::Option(opt), "ok_or") if method_args.len() == 1 => Ok(Some(match opt.as_ref() {
+            Some(v) => Value::ok(v.clone()),
+            None => Value::err(method_args[0].clone()),
+        })),
 
         // Result methods
         (Value::Result(res), "is_ok") if method_args.is_empty() => {
@@ -372,29 +718,690 @@ fn try_builtin_method(method: &str, args: &[Value]) -> Result<Option<Value>, Eva
         }
         (Value::Result(res), "unwrap") if method_args.is_empty() => match res.as_ref() {
             Ok(v) => Ok(Some(v.clone())),
-            Err(e) => Err(EvalError::BuiltinError {
-                name: "unwrap".to_string(),
+            Err(e) => Err(EvalError::Panic {
                 message: format!("called `Result::unwrap()` on an `Err` value: {:?}", e),
                 span: None,
             }),
         },
         (Value::Result(res), "unwrap_err") if method_args.is_empty() => match res.as_ref() {
-            Ok(v) => Err(EvalError::BuiltinError {
-                name: "unwrap_err".to_string(),
+            Ok(v) => Err(EvalError::Panic {
                 message: format!("called `Result::unwrap_err()` on an `Ok` value: {:?}", v),
                 span: None,
             }),
             Err(e) => Ok(Some(e.clone())),
         },
+        (Value::Result(res), "ok") if method_args.is_empty() => {
+            Ok(Some(Value::Option(Arc::new(res.as_ref().clone().ok()))))
+        }
+        (Value::Result(res), "unwrap_or") if method_args.len() == 1 => {
+            Ok(Some(match res.as_ref() {
+                Ok(v) => v.clone(),
+                Err(_) => method_args[0].clone(),
+            }))
+        }
+
+        // Ordering of comparable values. Structs and enums are left to
+        // user-defined `cmp` methods
+        (receiver, "cmp")
+            if method_args.len() == 1 && !matches!(receiver, Value::Struct(_) | Value::Enum(_)) =>
+        {
+            Ok(Some(Value::ordering(receiver.try_cmp(&method_args[0])?)))
+        }
 
-        // Clone (works on most values)
+        // Clone (works on most values). Shallow: data values copy on
+        // write, so sharing their allocations is not observable
         (_, "clone") if method_args.is_empty() => Ok(Some(receiver.clone())),
 
+        // Display conversion (works on all values)
+        (_, "to_string") if method_args.is_empty() => Ok(Some(Value::string(receiver.to_string()))),
+
         // No built-in method found
         _ => Ok(None),
     }
 }
 
+/// Names of the built-in methods available on `receiver`.
+///
+/// Mirrors the dispatch in [`try_builtin_method`], [`try_combinator_method`],
+/// and the Vec mutators; keep them in sync.
+fn builtin_method_names(receiver: &Value) -> &'static [&'static str] {
+    match receiver {
+        Value::String(_) => &[
+            "chars",
+            "clone",
+            "cmp",
+            "contains",
+            "ends_with",
+            "is_empty",
+            "len",
+            "push",
+            "push_str",
+            "starts_with",
+            "to_lowercase",
+            "to_string",
+            "to_uppercase",
+            "trim",
+        ],
+        Value::Bytes(_) => &[
+            "as_slice",
+            "clone",
+            "get",
+            "is_empty",
+            "len",
+            "to_string",
+            "to_vec",
+        ],
+        Value::Char(_) => &[
+            "clone",
+            "cmp",
+            "is_alphabetic",
+            "is_alphanumeric",
+            "is_numeric",
+            "is_whitespace",
+            "to_digit",
+            "to_lowercase",
+            "to_string",
+            "to_uppercase",
+        ],
+        Value::Vec(_) => &[
+            "binary_search",
+            "clone",
+            "cmp",
+            "collect",
+            "collect_string",
+            "contains",
+            "dedup",
+            "enumerate",
+            "extend",
+            "first",
+            "get",
+            "insert",
+            "into_iter",
+            "is_empty",
+            "iter",
+            "join",
+            "last",
+            "len",
+            "pop",
+            "pop_front",
+            "push",
+            "push_front",
+            "remove",
+            "reverse",
+            "sort",
+            "sort_by",
+            "sort_by_key",
+            "to_string",
+            "zip",
+        ],
+        Value::Array(_) => &[
+            "binary_search",
+            "clone",
+            "cmp",
+            "collect_string",
+            "first",
+            "into_iter",
+            "is_empty",
+            "iter",
+            "join",
+            "last",
+            "len",
+            "to_string",
+        ],
+        Value::HashMap(_) => &[
+            "clone",
+            "get_or_insert_with",
+            "into_iter",
+            "iter",
+            "to_string",
+        ],
+        Value::Iter(_) => &[
+            "clone",
+            "collect",
+            "collect_string",
+            "into_iter",
+            "iter",
+            "next",
+            "to_string",
+        ],
+        Value::Option(_) => &[
+            "and_then",
+            "clone",
+            "is_none",
+            "is_some",
+            "map",
+            "ok_or",
+            "to_string",
+            "unwrap",
+            "unwrap_or",
+            "unwrap_or_else",
+        ],
+        Value::Result(_) => &[
+            "and_then",
+            "clone",
+            "is_err",
+            "is_ok",
+            "map",
+            "map_err",
+            "ok",
+            "to_string",
+            "unwrap",
+            "unwrap_err",
+            "unwrap_or",
+        ],
+        Value::Struct(_) | Value::Enum(_) => &["clone", "to_string"],
+        _ => &["clone", "cmp", "to_string"],
+    }
+}
+
+/// Get the built-in methods on `receiver` whose names start with `prefix`.
+///
+/// Intended for REPL completion; the result is sorted.
+pub fn method_completions(receiver: &Value, prefix: &str) -> Vec<String> {
+    builtin_method_names(receiver)
+        .iter()
+        .filter(|name| name.starts_with(prefix))
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// Try to call a built-in method that takes a callable argument.
+///
+/// These need the environment and context to invoke the callable, so they
+/// are kept apart from [`try_builtin_method`]. The callable is only
+/// invoked when the receiver's variant requires it.
+fn try_combinator_method(
+    method: &str,
+    args: &[Value],
+    env: &mut Environment,
+    ctx: &EvalContext,
+) -> Result<Option<Value>, EvalError> {
+    let [receiver, f] = args else {
+        return Ok(None);
+    };
+
+    match (receiver, method) {
+        // Option combinators
+        (Value::Option(opt), "map") => Ok(Some(match opt.as_ref() {
+            Some(v) => Value::some(call_value(f.clone(), vec![v.clone()], env, ctx, None)?),
+            None => Value::none(),
+        })),
+        (Value::Option(opt), "and_then") => Ok(Some(match opt.as_ref() {
+            Some(v) => expect_variant(
+                "and_then",
+                "Option",
+                call_value(f.clone(), vec![v.clone()], env, ctx, None)?,
+            )?,
+            None => Value::none(),
+        })),
+        (Value::Option(opt), "unwrap_or_else") => Ok(Some(match opt.as_ref() {
+            Some(v) => v.clone(),
+            None => call_value(f.clone(), vec![], env, ctx, None)?,
+        })),
+
+        // Result combinators
+        (Value::Result(res), "map") => Ok(Some(match res.as_ref() {
+            Ok(v) => Value::ok(call_value(f.clone(), vec![v.clone()], env, ctx, None)?),
+            Err(_) => receiver.clone(),
+        })),
+        (Value::Result(res), "map_err") => Ok(Some(match res.as_ref() {
+            Ok(_) => receiver.clone(),
+            Err(e) => Value::err(call_value(f.clone(), vec![e.clone()], env, ctx, None)?),
+        })),
+        (Value::Result(res), "and_then") => Ok(Some(match res.as_ref() {
+            Ok(v) => expect_variant(
+                "and_then",
+                "Result",
+                call_value(f.clone(), vec![v.clone()], env, ctx, None)?,
+            )?,
+            Err(_) => receiver.clone(),
+        })),
+
+        _ => Ok(None),
+    }
+}
+
+/// Search a sorted vec for `needle`: `Ok(index)` if found, otherwise
+/// `Err(index)` where it could be inserted to keep the vec sorted.
+///
+/// The whole vec is checked to be orderable first, so a heterogeneous vec
+/// errors even if the search would not have compared the mismatched items.
+fn binary_search(items: &[Value], needle: &Value) -> Result<Value, EvalError> {
+    for pair in items.windows(2) {
+        pair[0].try_cmp(&pair[1])?;
+    }
+
+    let (mut lo, mut hi) = (0, items.len());
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        match items[mid].try_cmp(needle)? {
+            std::cmp::Ordering::Less => lo = mid + 1,
+            std::cmp::Ordering::Greater => hi = mid,
+            std::cmp::Ordering::Equal => return Ok(Value::ok(Value::Usize(mid))),
+        }
+    }
+    Ok(Value::err(Value::Usize(lo)))
+}
+
+/// Concatenate chars and strings with `sep` between them, for
+/// `collect_string` and `join`.
+fn concat_strings<'a>(
+    method: &str,
+    items: impl Iterator<Item = &'a Value>,
+    sep: &str,
+) -> Result<Value, EvalError> {
+    let mut out = String::new();
+    for (i, item) in items.enumerate() {
+        if i > 0 {
+            out.push_str(sep);
+        }
+        match item {
+            Value::String(s) => out.push_str(s),
+            Value::Char(c) => out.push(*c),
+            other => {
+                return Err(EvalError::BuiltinError {
+                    name: method.to_string(),
+                    message: format!(
+                        "expected chars or strings, got {}",
+                        crate::error::type_name(other)
+                    ),
+                    span: None,
+                })
+            }
+        }
+    }
+    Ok(Value::string(out))
+}
+
+/// Check that a combinator's callable returned an `Option` or `Result`.
+fn expect_variant(method: &str, expected: &str, value: Value) -> Result<Value, EvalError> {
+    match (&value, expected) {
+        (Value::Option(_), "Option") | (Value::Result(_), "Result") => Ok(value),
+        _ => Err(EvalError::TypeError {
+            message: format!(
+                "closure passed to `{}` must return {}, got {}",
+                method,
+                expected,
+                crate::error::type_name(&value)
+            ),
+            span: None,
+        }),
+    }
+}
+
+/// Check whether `method` sorts a Vec with a closure.
+fn is_vec_sorter(method: &str) -> bool {
+    matches!(method, "sort_by" | "sort_by_key")
+}
+
+/// Sort `items` for `sort_by(|a, b| ..)` or `sort_by_key(|x| ..)`.
+///
+/// A `sort_by` comparator returns an `Ordering`, as from `a.cmp(b)`, or
+/// an integer whose sign orders `a` relative to `b`, as in `a - b`. Both
+/// sorts are stable.
+fn sort_with(
+    method: &str,
+    items: Vec<Value>,
+    args: &[Value],
+    env: &mut Environment,
+    ctx: &EvalContext,
+) -> Result<Vec<Value>, EvalError> {
+    let [f] = args else {
+        return Err(EvalError::ArityMismatch {
+            expected: 1,
+            got: args.len(),
+            name: method.to_string(),
+            span: None,
+        });
+    };
+
+    if method == "sort_by" {
+        return merge_sort(items, &mut |a, b| {
+            let result = call_value(f.clone(), vec![a.clone(), b.clone()], env, ctx, None)?;
+            comparator_ordering(&result)
+        });
+    }
+
+    // Compute each key once, then sort the (key, item) pairs by key
+    let keyed = items
+        .into_iter()
+        .map(|item| {
+            Ok((
+                call_value(f.clone(), vec![item.clone()], env, ctx, None)?,
+                item,
+            ))
+        })
+        .collect::<Result<Vec<_>, EvalError>>()?;
+    let sorted = merge_sort(keyed, &mut |(a, _), (b, _)| a.try_cmp(b))?;
+    Ok(sorted.into_iter().map(|(_, item)| item).collect())
+}
+
+/// The ordering a `sort_by` comparator's result stands for.
+fn comparator_ordering(value: &Value) -> Result<std::cmp::Ordering, EvalError> {
+    if let Some(ord) = value.as_ordering() {
+        return Ok(ord);
+    }
+    match value {
+        Value::I128(n) => Ok(n.cmp(&0)),
+        // Integers too large for an i64 are positive
+        n if n.is_integer() => Ok(n
+            .clone()
+            .into_i64()
+            .map_or(std::cmp::Ordering::Greater, |n| n.cmp(&0))),
+        other => Err(EvalError::TypeError {
+            message: format!(
+                "`sort_by` comparator must return an Ordering or integer, got {}",
+                crate::error::type_name(other)
+            ),
+            span: None,
+        }),
+    }
+}
+
+/// A stable merge sort with a fallible comparison. Unlike `slice::sort_by`,
+/// it tolerates comparators that are not a total order.
+fn merge_sort<T>(
+    mut items: Vec<T>,
+    compare: &mut dyn FnMut(&T, &T) -> Result<std::cmp::Ordering, EvalError>,
+) -> Result<Vec<T>, EvalError> {
+    if items.len() <= 1 {
+        return Ok(items);
+    }
+
+    let right = items.split_off(items.len() / 2);
+    let left = merge_sort(items, compare)?;
+    let right = merge_sort(right, compare)?;
+
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+        if compare(a, b)? == std::cmp::Ordering::Greater {
+            merged.extend(right.next());
+        } else {
+            merged.extend(left.next());
+        }
+    }
+    merged.extend(left);
+    merged.extend(right);
+    Ok(merged)
+}
+
+/// Check whether `method` is a built-in method that mutates a Vec.
+fn is_vec_mutator(method: &str) -> bool {
+    matches!(
+        method,
+        "push"
+            | "pop"
+            | "push_front"
+            | "pop_front"
+            | "insert"
+            | "remove"
+            | "sort"
+            | "reverse"
+            | "extend"
+            | "dedup"
+    )
+}
+
+/// Check whether `method` is a built-in method that mutates a String.
+fn is_string_mutator(method: &str) -> bool {
+    matches!(method, "push" | "push_str")
+}
+
+/// The result of a char case mapping: a char, or a String when the mapping
+/// has several chars.
+fn case_mapped(mut chars: impl ExactSizeIterator<Item = char>) -> Value {
+    match (chars.len(), chars.next()) {
+        (1, Some(c)) => Value::Char(c),
+        (_, first) => Value::string(first.into_iter().chain(chars).collect::<String>()),
+    }
+}
+
+/// Check whether `method` is a built-in method that mutates a HashMap.
+fn is_map_mutator(method: &str) -> bool {
+    method == "get_or_insert_with"
+}
+
+/// Check whether `method` is a built-in method that advances an iterator.
+fn is_iter_mutator(method: &str) -> bool {
+    method == "next"
+}
+
+/// Advance the iterator stored in `slot`, returning the next element as an
+/// `Option`.
+///
+/// # Errors
+///
+/// Returns `TypeError` if `slot` does not hold an iterator.
+pub(crate) fn advance_iter(slot: &mut Value) -> Result<Value, EvalError> {
+    match slot {
+        Value::Iter(iter) => Ok(Value::Option(Arc::new(iter.next()))),
+        other => Err(EvalError::TypeError {
+            message: format!("`{}` is not an iterator", crate::error::type_name(other)),
+            span: None,
+        }),
+    }
+}
+
+/// Get the value for a key of the hashmap at `place`, whose current value
+/// is `current`, first inserting the result of calling `default` if the
+/// key is missing.
+///
+/// The default is computed before the map is borrowed, since calling it
+/// re-enters the interpreter. `current` is dropped before the insert so
+/// the map is not copied.
+///
+/// # Errors
+///
+/// Returns `ArityMismatch` for the wrong number of arguments, `TypeError`
+/// for an unhashable key, or the default callable's error.
+fn get_or_insert_with(
+    place: &assign::Place,
+    current: Value,
+    args: &[Value],
+    env: &mut Environment,
+    ctx: &EvalContext,
+) -> Result<Value, EvalError> {
+    let [key, default] = args else {
+        return Err(EvalError::ArityMismatch {
+            expected: 2,
+            got: args.len(),
+            name: "get_or_insert_with".to_string(),
+            span: None,
+        });
+    };
+    if !HashableValue::is_hashable(key) {
+        return Err(EvalError::TypeError {
+            message: format!(
+                "hashmap key must be hashable, got {}",
+                crate::error::type_name(key)
+            ),
+            span: None,
+        });
+    }
+
+    let key = HashableValue(key.clone());
+    if let Value::HashMap(map) = current {
+        if let Some(value) = map.get(&key) {
+            return Ok(value.clone());
+        }
+    }
+
+    let value = call_value(default.clone(), vec![], env, ctx, None)?;
+    assign::modify_place(place, env, |slot| match slot {
+        Value::HashMap(map) => {
+            Arc::make_mut(map).insert(key, value.clone());
+            Ok(value)
+        }
+        other => Err(EvalError::TypeError {
+            message: format!(
+                "`get_or_insert_with` expects a hashmap, got {}",
+                crate::error::type_name(other)
+            ),
+            span: None,
+        }),
+    })
+}
+
+/// Apply a mutating built-in method to a String.
+///
+/// # Errors
+///
+/// Returns `ArityMismatch` for the wrong number of arguments and
+/// `TypeError` when `push` is not given a char or `push_str` a string.
+fn call_string_mutator(method: &str, s: &mut String, args: &[Value]) -> Result<Value, EvalError> {
+    if args.len() != 1 {
+        return Err(EvalError::ArityMismatch {
+            expected: 1,
+            got: args.len(),
+            name: method.to_string(),
+            span: None,
+        });
+    }
+
+    match (method, &args[0]) {
+        ("push", Value::Char(c)) => s.push(*c),
+        ("push_str", Value::String(other)) => s.push_str(other),
+        (_, other) => {
+            return Err(EvalError::TypeError {
+                message: format!(
+                    "`{}` expects a {}, got {}",
+                    method,
+                    if method == "push" { "char" } else { "string" },
+                    crate::error::type_name(other)
+                ),
+                span: None,
+            })
+        }
+    }
+    Ok(Value::Unit)
+}
+
+/// Apply a mutating built-in method to a Vec.
+///
+/// # Errors
+///
+/// Returns `ArityMismatch` for the wrong number of arguments,
+/// `IndexOutOfBounds` for `insert`/`remove` past the end,
+/// `MemoryLimitExceeded` if growing the vec would exceed the memory
+/// budget, and `TypeError` when `sort` meets elements that cannot be
+/// compared.
+fn call_vec_mutator(
+    method: &str,
+    v: &mut Vec<Value>,
+    args: &[Value],
+    ctx: &EvalContext,
+) -> Result<Value, EvalError> {
+    let arity = match method {
+        "pop" | "pop_front" | "sort" | "reverse" | "dedup" => 0,
+        "push" | "push_front" | "remove" | "extend" => 1,
+        _ => 2,
+    };
+    if args.len() != arity {
+        return Err(EvalError::ArityMismatch {
+            expected: arity,
+            got: args.len(),
+            name: method.to_string(),
+            span: None,
+        });
+    }
+
+    let index_arg = |arg: &Value| {
+        arg.as_usize().ok_or_else(|| EvalError::TypeError {
+            message: format!(
+                "`{}` index must be integer, got {}",
+                method,
+                crate::error::type_name(arg)
+            ),
+            span: None,
+        })
+    };
+
+    match method {
+        "push" => {
+            check_vec_growth(ctx, v.len() + 1, &args[..1])?;
+            v.push(args[0].clone());
+            Ok(Value::Unit)
+        }
+        "pop" => Ok(Value::Option(Arc::new(v.pop()))),
+        "push_front" => {
+            check_vec_growth(ctx, v.len() + 1, &args[..1])?;
+            v.insert(0, args[0].clone());
+            Ok(Value::Unit)
+        }
+        "pop_front" => Ok(Value::Option(Arc::new(
+            (!v.is_empty()).then(|| v.remove(0)),
+        ))),
+        "insert" => {
+            let idx = index_arg(&args[0])?;
+            if idx > v.len() {
+                return Err(EvalError::IndexOutOfBounds {
+                    index: idx,
+                    len: v.len(),
+                    span: None,
+                });
+            }
+            check_vec_growth(ctx, v.len() + 1, &args[1..])?;
+            v.insert(idx, args[1].clone());
+            Ok(Value::Unit)
+        }
+        "remove" => {
+            let idx = index_arg(&args[0])?;
+            if idx >= v.len() {
+                return Err(EvalError::IndexOutOfBounds {
+                    index: idx,
+                    len: v.len(),
+                    span: None,
+                });
+            }
+            Ok(v.remove(idx))
+        }
+        "sort" => {
+            // Sort a copy, so the vec is unchanged if two elements are
+            // incomparable
+            *v = merge_sort(v.clone(), &mut |a, b| a.try_cmp(b))?;
+            Ok(Value::Unit)
+        }
+        "reverse" => {
+            v.reverse();
+            Ok(Value::Unit)
+        }
+        "dedup" => {
+            v.dedup();
+            Ok(Value::Unit)
+        }
+        "extend" => match &args[0] {
+            Value::Vec(other) | Value::Array(other) => {
+                check_vec_growth(ctx, v.len() + other.len(), other)?;
+                v.extend(other.iter().cloned());
+                Ok(Value::Unit)
+            }
+            other => Err(EvalError::TypeError {
+                message: format!(
+                    "`extend` expects a vec, got {}",
+                    crate::error::type_name(other)
+                ),
+                span: None,
+            }),
+        },
+        _ => unreachable!("not a vec mutator: {}", method),
+    }
+}
+
+/// Check the memory budget for a vec growing to `len` elements by
+/// adding `added`.
+fn check_vec_growth(ctx: &EvalContext, len: usize, added: &[Value]) -> Result<(), EvalError> {
+    if ctx.memory_limit.is_none() {
+        return Ok(());
+    }
+    let added_heap = added.iter().map(Value::heap_size).sum::<usize>();
+    ctx.check_allocation(
+        len.saturating_mul(std::mem::size_of::<Value>())
+            .saturating_add(added_heap),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -406,18 +1413,62 @@ mod tests {
     }
 
     #[test]
-    fn test_string_is_empty() {
-        let result = try_builtin_method("is_empty", &[Value::string("")]).unwrap();
-        assert_eq!(result, Some(Value::Bool(true)));
+    fn test_string_is_empty() {
+        let result = try_builtin_method("is_empty", &[Value::string("")]).unwrap();
+        assert_eq!(result, Some(Value::Bool(true)));
+
+        let result = try_builtin_method("is_empty", &[Value::string("hi")]).unwrap();
+        assert_eq!(result, Some(Value::Bool(false)));
+    }
+
+    #[test]
+    fn test_string_to_uppercase() {
+        let result = try_builtin_method("to_uppercase", &[Value::string("hello")]).unwrap();
+        assert_eq!(result, Some(Value::string("HELLO")));
+    }
+
+    #[test]
+    fn test_char_predicates() {
+        let result = try_builtin_method("is_alphabetic", &[Value::Char('x')]).unwrap();
+        assert_eq!(result, Some(Value::Bool(true)));
+
+        let result = try_builtin_method("is_numeric", &[Value::Char('x')]).unwrap();
+        assert_eq!(result, Some(Value::Bool(false)));
+
+        let result = try_builtin_method("is_alphanumeric", &[Value::Char('7')]).unwrap();
+        assert_eq!(result, Some(Value::Bool(true)));
+
+        let result = try_builtin_method("is_whitespace", &[Value::Char('\t')]).unwrap();
+        assert_eq!(result, Some(Value::Bool(true)));
+    }
+
+    #[test]
+    fn test_char_case_mapping() {
+        let result = try_builtin_method("to_uppercase", &[Value::Char('a')]).unwrap();
+        assert_eq!(result, Some(Value::Char('A')));
+
+        let result = try_builtin_method("to_lowercase", &[Value::Char('Q')]).unwrap();
+        assert_eq!(result, Some(Value::Char('q')));
+
+        let result = try_builtin_method("to_uppercase", &[Value::Char('ß')]).unwrap();
+        assert_eq!(result, Some(Value::string("SS")));
+        let result = try_builtin_method("to_lowercase", &[Value::Char('İ')]).unwrap();
+        assert_eq!(result, Some(Value::string("i\u{307}")));
+    }
+
+    #[test]
+    fn test_char_to_digit() {
+        let result = try_builtin_method("to_digit", &[Value::Char('7'), Value::I64(10)]).unwrap();
+        assert_eq!(result, Some(Value::some(Value::U32(7))));
 
-        let result = try_builtin_method("is_empty", &[Value::string("hi")]).unwrap();
-        assert_eq!(result, Some(Value::Bool(false)));
-    }
+        let result = try_builtin_method("to_digit", &[Value::Char('f'), Value::I64(16)]).unwrap();
+        assert_eq!(result, Some(Value::some(Value::U32(15))));
 
-    #[test]
-    fn test_string_to_uppercase() {
-        let result = try_builtin_method("to_uppercase", &[Value::string("hello")]).unwrap();
-        assert_eq!(result, Some(Value::string("HELLO")));
+        let result = try_builtin_method("to_digit", &[Value::Char('x'), Value::I64(10)]).unwrap();
+        assert_eq!(result, Some(Value::none()));
+
+        let result = try_builtin_method("to_digit", &[Value::Char('7'), Value::I64(1)]);
+        assert!(matches!(result, Err(EvalError::BuiltinError { .. })));
     }
 
     #[test]
@@ -444,6 +1495,35 @@ mod tests {
         assert_eq!(result, Some(Value::Bool(true)));
     }
 
+    #[test]
+    fn test_bytes_methods() {
+        let b = Value::bytes(b"abc".to_vec());
+        assert_eq!(
+            try_builtin_method("len", &[b.clone()]).unwrap(),
+            Some(Value::Usize(3))
+        );
+        assert_eq!(
+            try_builtin_method("is_empty", &[b.clone()]).unwrap(),
+            Some(Value::Bool(false))
+        );
+        assert_eq!(
+            try_builtin_method("get", &[b.clone(), Value::I64(1)]).unwrap(),
+            Some(Value::some(Value::U8(b'b')))
+        );
+        assert_eq!(
+            try_builtin_method("get", &[b.clone(), Value::I64(3)]).unwrap(),
+            Some(Value::none())
+        );
+        assert_eq!(
+            try_builtin_method("to_vec", &[b]).unwrap(),
+            Some(Value::vec(vec![
+                Value::U8(b'a'),
+                Value::U8(b'b'),
+                Value::U8(b'c')
+            ]))
+        );
+    }
+
     #[test]
     fn test_vec_len() {
         let v = Value::vec(vec![Value::I64(1), Value::I64(2), Value::I64(3)]);
@@ -482,7 +1562,7 @@ mod tests {
 
         let none_val = Value::Option(Arc::new(None));
         let result = try_builtin_method("unwrap", &[none_val]);
-        assert!(result.is_err());
+        assert!(matches!(result, Err(EvalError::Panic { .. })));
     }
 
     #[test]
@@ -516,6 +1596,125 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_collect_string_and_join() {
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(
+            interp.eval_str("\"hi\".chars().collect_string()").unwrap(),
+            Value::string("hi")
+        );
+        assert_eq!(
+            interp
+                .eval_str("[\"a\", \"b\"].iter().collect_string()")
+                .unwrap(),
+            Value::string("ab")
+        );
+        assert_eq!(
+            interp.eval_str("\"ab\".chars().collect()").unwrap(),
+            Value::vec(vec![Value::Char('a'), Value::Char('b')])
+        );
+
+        let words = Value::vec(vec![Value::string("a"), Value::string("b")]);
+        assert_eq!(
+            try_builtin_method("join", &[words, Value::string("-")]).unwrap(),
+            Some(Value::string("a-b"))
+        );
+
+        let err = interp.eval_str("[1, 2].join(\",\")").unwrap_err();
+        assert!(matches!(err.inner(), EvalError::BuiltinError { .. }));
+    }
+
+    #[test]
+    fn test_binary_search_and_dedup() {
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(
+            interp.eval_str("[1, 3, 5].binary_search(3)").unwrap(),
+            Value::ok(Value::Usize(1))
+        );
+        assert_eq!(
+            interp.eval_str("[1, 3, 5].binary_search(4)").unwrap(),
+            Value::err(Value::Usize(2))
+        );
+        assert_eq!(
+            interp.eval_str("[1, 3, 5].binary_search(9)").unwrap(),
+            Value::err(Value::Usize(3))
+        );
+        assert!(interp.eval_str("[1, \"a\", 5].binary_search(5)").is_err());
+
+        let result = interp
+            .eval_str("let mut v = [1, 1, 2, 2, 3, 1].iter().collect(); v.dedup(); v")
+            .unwrap();
+        assert_eq!(
+            result,
+            Value::vec([1, 2, 3, 1].into_iter().map(Value::I64).collect())
+        );
+    }
+
+    #[test]
+    fn test_sort_by_and_sort_by_key() {
+        let mut interp = crate::Interpreter::new();
+        let result = interp
+            .eval_str("let mut v = [3, 1, 2].iter().collect(); v.sort_by(|a, b| b - a); v")
+            .unwrap();
+        assert_eq!(
+            result,
+            Value::vec([3, 2, 1].into_iter().map(Value::I64).collect())
+        );
+
+        let result = interp
+            .eval_str(
+                "let mut w = [\"ccc\", \"a\", \"bb\", \"d\"].iter().collect(); \
+                 w.sort_by_key(|s| s.len()); w",
+            )
+            .unwrap();
+        assert_eq!(
+            result,
+            Value::vec(
+                ["a", "d", "bb", "ccc"]
+                    .into_iter()
+                    .map(Value::string)
+                    .collect()
+            )
+        );
+
+        let err = interp.eval_str("v.sort_by(|a, b| true)").unwrap_err();
+        assert!(matches!(err.inner(), EvalError::TypeError { .. }));
+    }
+
+    #[test]
+    fn test_cmp_returns_ordering() {
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(
+            interp.eval_str("5.cmp(3)").unwrap(),
+            Value::ordering(std::cmp::Ordering::Greater)
+        );
+        assert_eq!(
+            interp
+                .eval_str(
+                    "match 5.cmp(3) { Ordering::Less => -1, Ordering::Equal => 0, \
+                     Ordering::Greater => 1 }"
+                )
+                .unwrap(),
+            Value::I64(1)
+        );
+        assert_eq!(
+            interp
+                .eval_str("\"a\".cmp(\"b\") == Ordering::Less")
+                .unwrap(),
+            Value::Bool(true)
+        );
+
+        let result = interp
+            .eval_str("let mut v = [1, 3, 2].iter().collect(); v.sort_by(|a, b| b.cmp(a)); v")
+            .unwrap();
+        assert_eq!(
+            result,
+            Value::vec([3, 2, 1].into_iter().map(Value::I64).collect())
+        );
+
+        assert!(interp.eval_str("1.cmp(\"a\")").is_err());
+    }
+
     #[test]
     fn test_string_starts_with() {
         let result = try_builtin_method(
@@ -591,6 +1790,111 @@ mod tests {
         assert_eq!(result, Some(Value::Bool(false)));
     }
 
+    #[test]
+    fn test_vec_enumerate() {
+        let v = Value::vec(vec![Value::string("a"), Value::string("b")]);
+        let result = try_builtin_method("enumerate", &[v]).unwrap();
+        assert_eq!(
+            result,
+            Some(Value::vec(vec![
+                Value::tuple(vec![Value::Usize(0), Value::string("a")]),
+                Value::tuple(vec![Value::Usize(1), Value::string("b")]),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_iter_next() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .env_mut()
+            .define("v", Value::vec(vec![Value::I64(1), Value::I64(2)]));
+
+        let result = interp.eval_str("let mut it = v.iter(); it.next()").unwrap();
+        assert_eq!(result, Value::Option(Arc::new(Some(Value::I64(1)))));
+        assert_eq!(
+            interp.eval_str("it.next()").unwrap(),
+            Value::Option(Arc::new(Some(Value::I64(2))))
+        );
+        assert_eq!(
+            interp.eval_str("it.next()").unwrap(),
+            Value::Option(Arc::new(None))
+        );
+    }
+
+    #[test]
+    fn test_for_loop_resumes_iterator() {
+        let mut interp = crate::Interpreter::new();
+        let result = interp
+            .eval_str(
+                "let mut it = [1, 2, 3].iter();
+                 it.next();
+                 let mut sum = 0;
+                 for x in &mut it { sum += x; }
+                 (sum, it.next())",
+            )
+            .unwrap();
+        assert_eq!(
+            result,
+            Value::tuple(vec![Value::I64(5), Value::Option(Arc::new(None))])
+        );
+    }
+
+    #[test]
+    fn test_iter_copies_do_not_alias() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str(
+                "fn drain(it: Iter) -> i64 { let mut it = it; let mut n = 0; for _ in &mut it { n += 1; } n }
+                 let mut it = (0..3).iter();
+                 it.next();
+                 let mut it2 = it;",
+            )
+            .unwrap();
+
+        let some = |n| Value::Option(Arc::new(Some(Value::I64(n))));
+        assert_eq!(interp.eval_str("it2.next()").unwrap(), some(1));
+        assert_eq!(interp.eval_str("drain(it)").unwrap(), Value::I64(2));
+        assert_eq!(interp.eval_str("it.next()").unwrap(), some(1));
+        assert_eq!(interp.eval_str("it2.next()").unwrap(), some(2));
+        assert!(interp.eval_str("let it3 = it; it3.next()").is_err());
+        assert_eq!(
+            interp.eval_str("(0..2).iter().collect()").unwrap(),
+            Value::vec(vec![Value::I64(0), Value::I64(1)])
+        );
+    }
+
+    #[test]
+    fn test_iter_clone_has_own_cursor() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str("let mut it = [1, 2].iter(); let copy = it.clone(); it.next();")
+            .unwrap();
+
+        assert_eq!(
+            interp.eval_str("it.collect()").unwrap(),
+            Value::vec(vec![Value::I64(2)])
+        );
+        assert_eq!(
+            interp.eval_str("copy.collect()").unwrap(),
+            Value::vec(vec![Value::I64(1), Value::I64(2)])
+        );
+    }
+
+    #[test]
+    fn test_vec_zip_truncates() {
+        let v = Value::vec(vec![Value::I64(1), Value::I64(2), Value::I64(3)]);
+        let w = Value::vec(vec![Value::Bool(true), Value::Bool(false)]);
+        let result = try_builtin_method("zip", &[v, w]).unwrap();
+        assert_eq!(
+            result,
+            Some(Value::vec(vec![
+                Value::tuple(vec![Value::I64(1), Value::Bool(true)]),
+                Value::tuple(vec![Value::I64(2), Value::Bool(false)]),
+            ]))
+        );
+    }
+
     #[test]
     fn test_array_methods() {
         let arr = Value::array(vec![Value::I64(1), Value::I64(2)]);
@@ -627,7 +1931,7 @@ mod tests {
 
         let err_val = Value::Result(Arc::new(Err(Value::string("error"))));
         let result = try_builtin_method("unwrap", &[err_val]);
-        assert!(result.is_err());
+        assert!(matches!(result, Err(EvalError::Panic { .. })));
     }
 
     #[test]
@@ -653,10 +1957,11 @@ mod tests {
                 };
                 Ok(Value::I64(sum))
             }),
+            hooks: None,
         };
 
         // Wrong arity
-        let result = call_builtin(&builtin, vec![Value::I64(1)], None);
+        let result = call_builtin(&builtin, vec![Value::I64(1)], &EvalContext::default(), None);
         assert!(result.is_err());
         assert!(matches!(
             result.unwrap_err(),
@@ -678,11 +1983,13 @@ mod tests {
                 }
                 Ok(Value::I64(sum))
             }),
+            hooks: None,
         };
 
         let result = call_builtin(
             &builtin,
             vec![Value::I64(1), Value::I64(2), Value::I64(3)],
+            &EvalContext::default(),
             None,
         );
         assert_eq!(result.unwrap(), Value::I64(6));
@@ -694,9 +2001,10 @@ mod tests {
             name: "fail".to_string(),
             arity: 0,
             func: Arc::new(|_| Err("intentional error".to_string())),
+            hooks: None,
         };
 
-        let result = call_builtin(&builtin, vec![], None);
+        let result = call_builtin(&builtin, vec![], &EvalContext::default(), None);
         assert!(result.is_err());
         assert!(matches!(
             result.unwrap_err(),
@@ -712,7 +2020,7 @@ mod tests {
         let mut env = Environment::new();
         let ctx = EvalContext::default();
 
-        let result = call_function(&func, vec![], &mut env, &ctx).unwrap();
+        let result = call_function(&func, vec![], &mut env, &ctx, None).unwrap();
         assert_eq!(result, Value::I64(42));
     }
 
@@ -724,7 +2032,7 @@ mod tests {
         let mut env = Environment::new();
         let ctx = EvalContext::default();
 
-        let result = call_function(&func, vec![Value::I64(99)], &mut env, &ctx).unwrap();
+        let result = call_function(&func, vec![Value::I64(99)], &mut env, &ctx, None).unwrap();
         assert_eq!(result, Value::I64(99));
     }
 
@@ -736,7 +2044,7 @@ mod tests {
         let mut env = Environment::new();
         let ctx = EvalContext::default();
 
-        let result = call_function(&func, vec![], &mut env, &ctx);
+        let result = call_function(&func, vec![], &mut env, &ctx, None);
         assert!(result.is_err());
         assert!(matches!(
             result.unwrap_err(),
@@ -756,7 +2064,7 @@ mod tests {
         let mut env = Environment::new();
         let ctx = EvalContext::default();
 
-        let result = call_closure(&closure, vec![], &mut env, &ctx).unwrap();
+        let result = call_closure(&closure, vec![], &mut env, &ctx, None).unwrap();
         assert_eq!(result, Value::I64(42));
     }
 
@@ -772,7 +2080,7 @@ mod tests {
         let mut env = Environment::new();
         let ctx = EvalContext::default();
 
-        let result = call_closure(&closure, vec![], &mut env, &ctx).unwrap();
+        let result = call_closure(&closure, vec![], &mut env, &ctx, None).unwrap();
         assert_eq!(result, Value::I64(100));
     }
 
@@ -788,7 +2096,7 @@ mod tests {
         let mut env = Environment::new();
         let ctx = EvalContext::default();
 
-        let result = call_closure(&closure, vec![], &mut env, &ctx);
+        let result = call_closure(&closure, vec![], &mut env, &ctx, None);
         assert!(result.is_err());
         assert!(matches!(
             result.unwrap_err(),
@@ -950,7 +2258,7 @@ mod tests {
         let mut env = Environment::new();
         let ctx = EvalContext::default();
 
-        let result = call_function(&func, vec![], &mut env, &ctx).unwrap();
+        let result = call_function(&func, vec![], &mut env, &ctx, None).unwrap();
         assert_eq!(result, Value::I64(42));
     }
 
@@ -966,7 +2274,631 @@ mod tests {
         let mut env = Environment::new();
         let ctx = EvalContext::default();
 
-        let result = call_closure(&closure, vec![Value::I64(10)], &mut env, &ctx).unwrap();
+        let result = call_closure(&closure, vec![Value::I64(10)], &mut env, &ctx, None).unwrap();
         assert_eq!(result, Value::I64(15));
     }
+
+    #[test]
+    fn test_error_backtrace_three_calls_deep() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str(
+                "fn outer() -> i64 { middle() }
+                 fn middle() -> i64 { inner() }
+                 fn inner() -> i64 { 1 / 0 }",
+            )
+            .unwrap();
+
+        let err = interp.eval_str("outer()").unwrap_err();
+        let names: Vec<&str> = err
+            .backtrace()
+            .expect("backtrace")
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(names, vec!["outer", "middle", "inner"]);
+        assert!(err
+            .backtrace()
+            .unwrap()
+            .iter()
+            .all(|(_, span)| span.is_some()));
+        assert!(matches!(err.inner(), EvalError::DivisionByZero { .. }));
+        assert_eq!(interp.env().call_depth(), 0);
+    }
+
+    #[test]
+    fn test_call_function_reports_hot_functions() {
+        let mut interp = crate::Interpreter::new();
+        interp.eval_str("fn tick(x: i64) -> i64 { x + 1 }").unwrap();
+        for _ in 0..5 {
+            interp.eval_str("tick(1)").unwrap();
+        }
+
+        let ctx = interp.context();
+        assert_eq!(ctx.call_count("tick"), 5);
+        assert_eq!(ctx.hot_functions(4), vec!["tick".to_string()]);
+        assert!(ctx.hot_functions(5).is_empty());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_context_call_depth_limit() {
+        let ctx = EvalContext::with_max_call_depth(5);
+        let mut interp = crate::Interpreter::with_context(ctx);
+        *interp.env_mut() = Environment::with_max_call_depth(1000);
+
+        interp
+            .eval_str("fn down(n: i64) -> i64 { if n == 0 { 0 } else { down(n - 1) } }")
+            .unwrap();
+        assert_eq!(interp.eval_str("down(4)").unwrap(), Value::I64(0));
+
+        let err = interp.eval_str("down(5)").unwrap_err();
+        let EvalError::WithBacktrace { error, backtrace } = err else {
+            panic!("expected a backtrace, got {:?}", err);
+        };
+        assert!(matches!(
+            *error,
+            EvalError::Environment(crate::EnvironmentError::StackOverflow { depth: 5, max: 5 })
+        ));
+        assert_eq!(backtrace.len(), 5);
+
+        let closure = interp.eval_str("let f = |x: i64| x; f(1)");
+        assert_eq!(closure.unwrap(), Value::I64(1));
+    }
+
+    #[test]
+    fn test_stack_overflow_reports_infinite_recursion() {
+        // Keep the limit low enough for the test thread's native stack
+        let ctx = EvalContext::with_max_call_depth(50);
+        let mut interp = crate::Interpreter::with_context(ctx);
+        interp.eval_str("fn f() -> i64 { f() }").unwrap();
+
+        let err = interp.eval_str("f()").unwrap_err();
+        assert_eq!(err.possible_infinite_recursion(), Some("f"));
+        assert!(err
+            .to_string()
+            .ends_with("(possible infinite recursion in `f`)"));
+    }
+
+    #[test]
+    fn test_mutual_recursion_is_not_reported() {
+        let ctx = EvalContext::with_max_call_depth(20);
+        let mut interp = crate::Interpreter::with_context(ctx);
+        interp
+            .eval_str("fn ping() -> i64 { pong() } fn pong() -> i64 { ping() }")
+            .unwrap();
+
+        let err = interp.eval_str("ping()").unwrap_err();
+        assert!(err.backtrace().is_some());
+        assert_eq!(err.possible_infinite_recursion(), None);
+    }
+
+    #[test]
+    fn test_mut_self_method_writes_back() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str(
+                "struct Counter { count: i64 }
+                 impl Counter {
+                     fn inc(&mut self) { self.count += 1 }
+                     fn get(&self) -> i64 { self.count }
+                 }
+                 let mut c = Counter { count: 0 };",
+            )
+            .unwrap();
+
+        interp.eval_str("c.inc()").unwrap();
+        interp.eval_str("c.inc()").unwrap();
+        assert_eq!(interp.eval_str("c.count").unwrap(), Value::I64(2));
+        assert_eq!(interp.eval_str("c.get()").unwrap(), Value::I64(2));
+    }
+
+    #[test]
+    fn test_mut_self_method_on_immutable_binding() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str(
+                "struct Counter { count: i64 }
+                 impl Counter { fn inc(&mut self) { self.count += 1 } }
+                 let c = Counter { count: 0 };",
+            )
+            .unwrap();
+
+        assert!(interp.eval_str("c.inc()").is_err());
+        assert_eq!(interp.eval_str("c.count").unwrap(), Value::I64(0));
+    }
+
+    #[test]
+    fn test_hashmap_get_or_insert_with_word_count() {
+        let mut interp = crate::Interpreter::new();
+        interp.env_mut().define_with_mode(
+            "counts".to_string(),
+            Value::HashMap(Arc::new(std::collections::HashMap::new())),
+            BindingMode::Mutable,
+        );
+
+        interp
+            .eval_str(
+                r#"for word in ["a", "b", "a", "c", "a"] {
+                    counts[word] = counts.get_or_insert_with(word, || 0) + 1;
+                }"#,
+            )
+            .unwrap();
+
+        assert_eq!(interp.eval_str("counts[\"a\"]").unwrap(), Value::I64(3));
+        assert_eq!(interp.eval_str("counts[\"c\"]").unwrap(), Value::I64(1));
+        assert_eq!(
+            interp
+                .eval_str("counts.get_or_insert_with(\"b\", || 99)")
+                .unwrap(),
+            Value::I64(1)
+        );
+        let err = interp
+            .eval_str("counts.get_or_insert_with([1], || 0)")
+            .unwrap_err();
+        assert!(
+            matches!(err.inner(), EvalError::TypeError { message, .. } if message.contains("hashable")),
+            "{:?}",
+            err
+        );
+    }
+
+    fn ints(values: &[i64]) -> Value {
+        Value::vec(values.iter().map(|n| Value::I64(*n)).collect())
+    }
+
+    fn interp_with_vec(values: &[i64]) -> crate::Interpreter {
+        let mut interp = crate::Interpreter::new();
+        interp.env_mut().define_with_mode(
+            "v".to_string(),
+            ints(values),
+            crate::BindingMode::Mutable,
+        );
+        interp
+    }
+
+    #[test]
+    fn test_vec_push_pop_sort() {
+        let mut interp = interp_with_vec(&[3, 1]);
+        interp.eval_str("v.push(2)").unwrap();
+        interp.eval_str("v.push(5)").unwrap();
+        assert_eq!(
+            interp.eval_str("v.pop()").unwrap(),
+            Value::some(Value::I64(5))
+        );
+        interp.eval_str("v.sort()").unwrap();
+        assert_eq!(interp.env().get("v"), Some(&ints(&[1, 2, 3])));
+    }
+
+    #[test]
+    fn test_vec_front_ops() {
+        let mut interp = interp_with_vec(&[2]);
+        interp.eval_str("v.push_front(1)").unwrap();
+        assert_eq!(interp.env().get("v"), Some(&ints(&[1, 2])));
+        assert_eq!(
+            interp.eval_str("v.pop_front()").unwrap(),
+            Value::some(Value::I64(1))
+        );
+        interp.eval_str("v.pop_front()").unwrap();
+        assert_eq!(interp.eval_str("v.pop_front()").unwrap(), Value::none());
+    }
+
+    #[test]
+    fn test_vec_as_fifo_queue() {
+        let mut interp = interp_with_vec(&[1]);
+        let result = interp
+            .eval_str(
+                "let mut order = 0;
+                 while let Some(n) = v.pop_front() {
+                     order = order * 10 + n;
+                     if n < 4 { v.push(n * 2); v.push(n * 2 + 1); }
+                 }
+                 order",
+            )
+            .unwrap();
+        assert_eq!(result, Value::I64(1234567));
+    }
+
+    #[test]
+    fn test_vec_insert_remove_reverse_extend() {
+        let mut interp = interp_with_vec(&[1, 3]);
+        interp.eval_str("v.insert(1, 2)").unwrap();
+        assert_eq!(interp.eval_str("v.remove(0)").unwrap(), Value::I64(1));
+        interp.eval_str("v.reverse()").unwrap();
+        interp.env_mut().define("w".to_string(), ints(&[7, 8]));
+        interp.eval_str("v.extend(w)").unwrap();
+        assert_eq!(interp.env().get("v"), Some(&ints(&[3, 2, 7, 8])));
+    }
+
+    #[test]
+    fn test_vec_remove_out_of_bounds() {
+        let mut interp = interp_with_vec(&[1]);
+        assert!(matches!(
+            interp.eval_str("v.remove(1)"),
+            Err(EvalError::IndexOutOfBounds {
+                index: 1,
+                len: 1,
+                ..
+            })
+        ));
+        assert_eq!(interp.env().get("v"), Some(&ints(&[1])));
+    }
+
+    #[test]
+    fn test_vec_sort_heterogeneous_fails() {
+        let mut interp = crate::Interpreter::new();
+        interp.env_mut().define_with_mode(
+            "v".to_string(),
+            Value::vec(vec![Value::I64(1), Value::string("a")]),
+            crate::BindingMode::Mutable,
+        );
+        assert!(matches!(
+            interp.eval_str("v.sort()"),
+            Err(EvalError::TypeError { .. })
+        ));
+
+        // Incomparable elements that are not adjacent
+        let tuple = |n: i64, second: Value| Value::tuple(vec![Value::I64(n), second]);
+        let mixed = Value::vec(vec![
+            tuple(1, Value::string("x")),
+            tuple(2, Value::I64(5)),
+            tuple(1, Value::I64(5)),
+        ]);
+        interp.env_mut().define_with_mode(
+            "w".to_string(),
+            mixed.clone(),
+            crate::BindingMode::Mutable,
+        );
+        assert!(matches!(
+            interp.eval_str("w.sort()"),
+            Err(EvalError::TypeError { .. })
+        ));
+        assert_eq!(interp.eval_str("w").unwrap(), mixed);
+    }
+
+    #[test]
+    fn test_vec_push_requires_mut_binding() {
+        let mut interp = crate::Interpreter::new();
+        interp.env_mut().define("v".to_string(), ints(&[]));
+        assert!(interp.eval_str("v.push(1)").is_err());
+        assert_eq!(interp.env().get("v"), Some(&ints(&[])));
+    }
+
+    #[test]
+    fn test_mutating_methods_update_in_place() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str("let mut v = range(3); let mut s = String::from(\"a\");")
+            .unwrap();
+        interp.env_mut().define_with_mode(
+            "m".to_string(),
+            Value::HashMap(Arc::new(std::collections::HashMap::new())),
+            BindingMode::Mutable,
+        );
+
+        // The shared allocation, which a copy on write would replace
+        let shared = |interp: &crate::Interpreter| -> Vec<(usize, usize)> {
+            ["v", "s", "m"]
+                .iter()
+                .map(|name| match interp.env().get(name) {
+                    Some(Value::Vec(v)) => (Arc::as_ptr(v) as usize, Arc::strong_count(v)),
+                    Some(Value::String(s)) => (Arc::as_ptr(s) as usize, Arc::strong_count(s)),
+                    Some(Value::HashMap(m)) => (Arc::as_ptr(m) as usize, Arc::strong_count(m)),
+                    other => panic!("unexpected {:?}", other),
+                })
+                .collect()
+        };
+        let before = shared(&interp);
+        assert!(before.iter().all(|&(_, count)| count == 1));
+
+        interp
+            .eval_str(
+                "v.push(3); v.extend(range(2)); v.sort(); s.push_str(\"b\"); \
+                 m.get_or_insert_with(1, || 2);",
+            )
+            .unwrap();
+        assert_eq!(shared(&interp), before);
+    }
+
+    #[test]
+    fn test_vec_growth_respects_memory_limit() {
+        let ctx = EvalContext::with_memory_limit(1024);
+        let mut interp = crate::Interpreter::with_context(ctx);
+        interp.env_mut().define("v".to_string(), ints(&[]));
+
+        let err = interp
+            .eval_str("let mut v = v; loop { v.push(0); }")
+            .unwrap_err();
+        assert!(matches!(err.inner(), EvalError::MemoryLimitExceeded { .. }));
+
+        let err = interp
+            .eval_str("let mut w = range(4); loop { w.extend(range(4)); }")
+            .unwrap_err();
+        assert!(matches!(err.inner(), EvalError::MemoryLimitExceeded { .. }));
+    }
+
+    #[test]
+    fn test_vec_push_through_field_place() {
+        let mut interp = crate::Interpreter::new();
+        interp.env_mut().define("empty".to_string(), ints(&[]));
+        interp
+            .eval_str("struct Stack { items: Vec<i64> } let mut s = Stack { items: empty };")
+            .unwrap();
+        interp.eval_str("s.items.push(4)").unwrap();
+        assert_eq!(interp.eval_str("s.items").unwrap(), ints(&[4]));
+        assert_eq!(interp.env().get("empty"), Some(&ints(&[])));
+    }
+
+    #[test]
+    fn test_string_push_through_place() {
+        let mut interp = crate::Interpreter::new();
+        let result = interp
+            .eval_str("let mut s = String::from(\"a\"); s.push('b'); s.push_str(\"cd\"); s")
+            .unwrap();
+        assert_eq!(result, Value::string("abcd"));
+
+        let result = interp.eval_str("s.push(1)");
+        assert!(matches!(result, Err(EvalError::TypeError { .. })));
+    }
+
+    #[test]
+    fn test_to_string() {
+        let result = try_builtin_method("to_string", &[Value::I64(42)]).unwrap();
+        assert_eq!(result, Some(Value::string("42")));
+
+        let result = try_builtin_method("to_string", &[Value::string("hi")]).unwrap();
+        assert_eq!(result, Some(Value::string("hi")));
+    }
+
+    #[test]
+    fn test_option_map() {
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(
+            interp.eval_str("Some(5).map(|x| x + 1)").unwrap(),
+            Value::some(Value::I64(6))
+        );
+    }
+
+    #[test]
+    fn test_option_map_none_skips_closure() {
+        let mut interp = crate::Interpreter::new();
+        // Calling the closure would fail on the undefined name
+        assert_eq!(
+            interp.eval_str("None.map(|x| undefined(x))").unwrap(),
+            Value::none()
+        );
+    }
+
+    #[test]
+    fn test_option_and_then() {
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(
+            interp
+                .eval_str("Some(4).and_then(|x| if x > 3 { Some(x * 2) } else { None })")
+                .unwrap(),
+            Value::some(Value::I64(8))
+        );
+        assert!(interp.eval_str("Some(4).and_then(|x| x)").is_err());
+    }
+
+    #[test]
+    fn test_option_unwrap_or_else() {
+        let mut interp = crate::Interpreter::new();
+        interp.env_mut().define("n".to_string(), Value::none());
+        assert_eq!(
+            interp.eval_str("n.unwrap_or_else(|| 9)").unwrap(),
+            Value::I64(9)
+        );
+        assert_eq!(
+            interp.eval_str("Some(1).unwrap_or_else(|| 9)").unwrap(),
+            Value::I64(1)
+        );
+    }
+
+    #[test]
+    fn test_option_ok_or() {
+        let some_val = Value::some(Value::I64(1));
+        let result = try_builtin_method("ok_or", &[some_val, Value::string("e")]).unwrap();
+        assert_eq!(result, Some(Value::ok(Value::I64(1))));
+
+        let result = try_builtin_method("ok_or", &[Value::none(), Value::string("e")]).unwrap();
+        assert_eq!(result, Some(Value::err(Value::string("e"))));
+    }
+
+    #[test]
+    fn test_result_map() {
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(
+            interp.eval_str("Ok(2).map(|x| x * 10)").unwrap(),
+            Value::ok(Value::I64(20))
+        );
+        assert_eq!(
+            interp
+                .eval_str(r#"Err("e").map(|x| undefined(x))"#)
+                .unwrap(),
+            Value::err(Value::string("e"))
+        );
+    }
+
+    #[test]
+    fn test_result_map_err() {
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(
+            interp
+                .eval_str(r#"Err("e").map_err(|e| "wrapped")"#)
+                .unwrap(),
+            Value::err(Value::string("wrapped"))
+        );
+        assert_eq!(
+            interp.eval_str("Ok(1).map_err(|e| undefined(e))").unwrap(),
+            Value::ok(Value::I64(1))
+        );
+    }
+
+    #[test]
+    fn test_result_and_then() {
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(
+            interp
+                .eval_str(r#"Ok(3).and_then(|x| if x > 5 { Ok(x) } else { Err("small") })"#)
+                .unwrap(),
+            Value::err(Value::string("small"))
+        );
+        assert!(interp.eval_str("Ok(3).and_then(|x| x)").is_err());
+    }
+
+    #[test]
+    fn test_result_ok_and_unwrap_or() {
+        let ok_val = Value::ok(Value::I64(1));
+        let err_val = Value::err(Value::string("e"));
+
+        let result = try_builtin_method("ok", &[ok_val.clone()]).unwrap();
+        assert_eq!(result, Some(Value::some(Value::I64(1))));
+        let result = try_builtin_method("ok", &[err_val.clone()]).unwrap();
+        assert_eq!(result, Some(Value::none()));
+
+        let result = try_builtin_method("unwrap_or", &[err_val, Value::I64(0)]).unwrap();
+        assert_eq!(result, Some(Value::I64(0)));
+        let result = try_builtin_method("unwrap_or", &[ok_val, Value::I64(0)]).unwrap();
+        assert_eq!(result, Some(Value::I64(1)));
+    }
+
+    #[test]
+    fn test_method_completions() {
+        assert_eq!(
+            method_completions(&Value::string("hi"), "to_up"),
+            vec!["to_uppercase"]
+        );
+        assert_eq!(
+            method_completions(&Value::some(Value::I64(1)), "unwrap_or"),
+            vec!["unwrap_or", "unwrap_or_else"]
+        );
+        assert!(method_completions(&Value::I64(1), "to_up").is_empty());
+    }
+
+    #[test]
+    fn test_arity_error_has_call_site_span() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str("fn add(a: i64, b: i64) -> i64 { a + b }")
+            .unwrap();
+
+        let err = interp.eval_str("add(1)").unwrap_err();
+        assert!(matches!(err.inner(), EvalError::ArityMismatch { .. }));
+        assert!(err.span().is_some());
+    }
+
+    #[test]
+    fn test_builtin_method_error_has_span() {
+        let mut interp = crate::Interpreter::new();
+
+        let err = interp.eval_str("'a'.to_digit(1)").unwrap_err();
+        assert!(matches!(err.inner(), EvalError::BuiltinError { .. }));
+        assert!(err.span().is_some());
+
+        let err = interp.eval_str("None.unwrap()").unwrap_err();
+        assert!(err.is_panic());
+        assert!(err.span().is_some());
+    }
+
+    #[test]
+    fn test_variadic_function() {
+        let body: syn::Block =
+            syn::parse_str("{ let mut total = 0; for arg in args { total += arg; } total }")
+                .unwrap();
+        let sum = Value::Function(Arc::new(
+            FunctionValue::new("sum".to_string(), vec!["args".to_string()], body).with_variadic(),
+        ));
+        let mut env = Environment::new();
+        let ctx = EvalContext::default();
+
+        let mut call = |args: Vec<Value>| call_value(sum.clone(), args, &mut env, &ctx, None);
+        assert_eq!(call(vec![]).unwrap(), Value::I64(0));
+        assert_eq!(call(vec![Value::I64(4)]).unwrap(), Value::I64(4));
+        assert_eq!(
+            call(vec![Value::I64(1), Value::I64(2), Value::I64(3)]).unwrap(),
+            Value::I64(6)
+        );
+    }
+
+    #[test]
+    fn test_variadic_function_requires_leading_params() {
+        let body: syn::Block = syn::parse_str("{ rest.len() }").unwrap();
+        let f = Value::Function(Arc::new(
+            FunctionValue::new(
+                "f".to_string(),
+                vec!["first".to_string(), "rest".to_string()],
+                body,
+            )
+            .with_variadic(),
+        ));
+        let mut env = Environment::new();
+        let ctx = EvalContext::default();
+
+        let result = call_value(f.clone(), vec![], &mut env, &ctx, None);
+        assert!(matches!(
+            result,
+            Err(EvalError::ArityMismatch {
+                expected: 1,
+                got: 0,
+                ..
+            })
+        ));
+        let result = call_value(f, vec![Value::I64(1); 3], &mut env, &ctx, None);
+        assert_eq!(result.unwrap(), Value::Usize(2));
+    }
+
+    #[test]
+    fn test_try_in_method_argument_returns_from_function() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str("fn f(o: Option<char>) -> Option<bool> { Some(\"ab\".contains(o?)) }")
+            .unwrap();
+
+        assert_eq!(interp.eval_str("f(None)").unwrap(), Value::none());
+        assert_eq!(
+            interp.eval_str("f(Some('b'))").unwrap(),
+            Value::some(Value::Bool(true))
+        );
+    }
+
+    #[test]
+    fn test_break_in_method_argument_outside_loop() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str("fn f() -> bool { \"a\".contains(break) }")
+            .unwrap();
+        interp
+            .eval_str("fn g() -> bool { \"a\".contains(continue) }")
+            .unwrap();
+
+        let err = interp.eval_str("f()").unwrap_err();
+        assert!(matches!(err.inner(), EvalError::BreakOutsideLoop { .. }));
+        let err = interp.eval_str("g()").unwrap_err();
+        assert!(matches!(err.inner(), EvalError::ContinueOutsideLoop { .. }));
+
+        // A caller's loop does not catch a `break` from inside the call
+        let err = interp.eval_str("loop { f() }").unwrap_err();
+        assert!(matches!(err.inner(), EvalError::BreakOutsideLoop { .. }));
+    }
+
+    #[test]
+    fn test_nested_function_is_scoped_to_parent() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str(
+                "fn sum_to(n: i64) -> i64 {
+                     fn go(k: i64) -> i64 { if k == 0 { 0 } else { k + go(k - 1) } }
+                     go(n)
+                 }",
+            )
+            .unwrap();
+
+        assert_eq!(interp.eval_str("sum_to(4)").unwrap(), Value::I64(10));
+        assert!(!interp.env().contains("go"));
+        assert!(matches!(
+            interp.eval_str("go(1)"),
+            Err(EvalError::UndefinedVariable { .. })
+        ));
+    }
 }
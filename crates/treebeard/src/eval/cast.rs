@@ -0,0 +1,130 @@
+//! Cast expression evaluation
+
+use quote::ToTokens;
+
+use crate::error::type_name;
+use crate::{Environment, EvalContext, EvalError, Value};
+
+use super::Evaluate;
+
+/// Evaluate a cast expression (`x as T`).
+///
+/// Casts that cannot change the value are passed through: casts to the
+/// value's own type, casts to `_`, and reference-to-reference casts that
+/// do not add mutability.
+///
+/// # Errors
+///
+/// Returns `UnsupportedExpr` for other casts between primitives, such as
+/// `5 as u8`, which Rust allows but the interpreter does not yet perform,
+/// and `TypeError` for casts Rust rejects.
+pub fn eval_cast(
+    cast: &syn::ExprCast,
+    env: &mut Environment,
+    ctx: &EvalContext,
+) -> Result<Value, EvalError> {
+    let value = cast.expr.eval(env, ctx)?;
+
+    if is_identity_cast(&value, &cast.ty) {
+        return Ok(value);
+    }
+
+    let target = type_string(&cast.ty);
+    let span = Some(cast.as_token.span);
+    if is_primitive_cast(&value, &target) {
+        return Err(EvalError::UnsupportedExpr {
+            kind: format!("cast from `{}` to `{}`", type_name(&value), target),
+            span,
+        });
+    }
+
+    Err(EvalError::TypeError {
+        message: format!("cannot cast `{}` as `{}`", type_name(&value), target),
+        span,
+    })
+}
+
+/// Whether Rust allows casting `value` to the type named `target`: from a
+/// number, `bool` or `char` to a number, or from `u8` to `char`.
+fn is_primitive_cast(value: &Value, target: &str) -> bool {
+    let numeric_target =
+        Value::default_for_type_name(target).is_some_and(|default| default.is_numeric());
+    let from_primitive = value.is_numeric() || matches!(value, Value::Bool(_) | Value::Char(_));
+    (from_primitive && numeric_target) || (matches!(value, Value::U8(_)) && target == "char")
+}
+
+/// Whether casting `value` to `ty` leaves it unchanged.
+fn is_identity_cast(value: &Value, ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Paren(paren) => is_identity_cast(value, &paren.elem),
+        syn::Type::Group(group) => is_identity_cast(value, &group.elem),
+        syn::Type::Infer(_) => true,
+        syn::Type::Reference(reference) => match value {
+            Value::Ref(_) => reference.mutability.is_none(),
+            Value::RefMut(_) => true,
+            _ => false,
+        },
+        ty => type_string(ty) == type_name(value),
+    }
+}
+
+/// Render a type without token spacing, e.g. `Vec<i64>`.
+pub(crate) fn type_string(ty: &syn::Type) -> String {
+    ty.to_token_stream().to_string().replace(' ', "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(src: &str, env: &mut Environment) -> Result<Value, EvalError> {
+        let expr: syn::Expr = syn::parse_str(src).unwrap();
+        expr.eval(env, &EvalContext::default())
+    }
+
+    #[test]
+    fn test_same_type_cast_is_identity() {
+        let mut env = Environment::new();
+        assert_eq!(eval("5i64 as i64", &mut env).unwrap(), Value::I64(5));
+        assert_eq!(eval("'a' as char", &mut env).unwrap(), Value::Char('a'));
+        assert_eq!(eval("true as _", &mut env).unwrap(), Value::Bool(true));
+    }
+
+    #[test]
+    fn test_reference_cast_passes_through() {
+        let mut env = Environment::new();
+        env.define("x", Value::I64(5));
+
+        let Value::Ref(r) = eval("(&x) as &i64", &mut env).unwrap() else {
+            panic!("expected a reference");
+        };
+        assert_eq!(*r.value, Value::I64(5));
+
+        assert!(matches!(
+            eval("&mut x as &i64", &mut env).unwrap(),
+            Value::RefMut(_)
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_cast() {
+        let mut env = Environment::new();
+        env.define("x", Value::I64(5));
+        assert!(matches!(
+            eval("x as u8", &mut env),
+            Err(EvalError::UnsupportedExpr { .. })
+        ));
+        assert!(matches!(
+            eval("'a' as u32", &mut env),
+            Err(EvalError::UnsupportedExpr { .. })
+        ));
+        assert!(matches!(
+            eval("x as bool", &mut env),
+            Err(EvalError::TypeError { .. })
+        ));
+        assert!(matches!(
+            eval("&x as &mut i64", &mut env),
+            Err(EvalError::TypeError { .. })
+        ));
+    }
+}
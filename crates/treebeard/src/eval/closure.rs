@@ -0,0 +1,103 @@
+//! Closure expression evaluation
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use syn::visit::Visit;
+
+use crate::{ClosureValue, Environment, EvalError, Value};
+
+use super::function::extract_pat_name;
+
+/// Evaluate a closure expression to a closure value.
+///
+/// Free variables of the body that are bound at the point of creation are
+/// captured by value, so later changes to them are not observed by the
+/// closure (`move` semantics).
+///
+/// # Errors
+///
+/// Returns `UnsupportedExpr` for destructuring parameter patterns.
+pub fn eval_closure(closure: &syn::ExprClosure, env: &Environment) -> Result<Value, EvalError> {
+    let params = closure
+        .inputs
+        .iter()
+        .map(extract_pat_name)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut free = FreeNames::default();
+    free.visit_expr(&closure.body);
+
+    let captures = free
+        .names
+        .into_iter()
+        .filter(|name| !params.contains(name))
+        .filter_map(|name| env.get(&name).map(|value| (name, value.clone())))
+        .collect();
+
+    let value = ClosureValue::new(params, closure.body.as_ref().clone(), captures);
+
+    // ALLOW: ClosureValue is Send + Sync (syn::Expr is Send + Sync),
+    // but clippy can't verify this automatically
+    #[allow(clippy::arc_with_non_send_sync)]
+    Ok(Value::Closure(Arc::new(value)))
+}
+
+/// Collects single-segment path names referenced by an expression.
+#[derive(Default)]
+struct FreeNames {
+    names: Vec<String>,
+    seen: HashSet<String>,
+}
+
+impl<'ast> Visit<'ast> for FreeNames {
+    fn visit_expr_path(&mut self, path: &'ast syn::ExprPath) {
+        if path.qself.is_none() && path.path.segments.len() == 1 {
+            let name = path.path.segments[0].ident.to_string();
+            if self.seen.insert(name.clone()) {
+                self.names.push(name);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EvalContext, Evaluate};
+
+    fn eval(src: &str, env: &mut Environment) -> Result<Value, EvalError> {
+        let expr: syn::Expr = syn::parse_str(src).unwrap();
+        expr.eval(env, &EvalContext::default())
+    }
+
+    #[test]
+    fn test_closure_call() {
+        let mut env = Environment::new();
+        let f = eval("|x, y: i64| x + y", &mut env).unwrap();
+        env.define("f".to_string(), f);
+        assert_eq!(eval("f(2, 3)", &mut env).unwrap(), Value::I64(5));
+    }
+
+    #[test]
+    fn test_closure_captures_by_value() {
+        let mut env = Environment::new();
+        env.define_with_mode("n".to_string(), Value::I64(10), crate::BindingMode::Mutable);
+        let f = eval("|x| x + n", &mut env).unwrap();
+        env.define("f".to_string(), f);
+
+        eval("n = 100", &mut env).unwrap();
+        assert_eq!(eval("f(1)", &mut env).unwrap(), Value::I64(11));
+    }
+
+    #[test]
+    fn test_closure_captures_only_free_names() {
+        let mut env = Environment::new();
+        env.define("x".to_string(), Value::I64(1));
+        env.define("y".to_string(), Value::I64(2));
+        let Value::Closure(c) = eval("|x| x + y", &mut env).unwrap() else {
+            panic!("expected closure");
+        };
+        assert_eq!(c.captures.as_slice(), &[("y".to_string(), Value::I64(2))]);
+    }
+}
@@ -6,7 +6,8 @@ use super::Evaluate;
 
 /// Evaluate a field access expression.
 ///
-/// Supports field access on structs, tuples, and enum variants.
+/// Supports field access on structs, tuples, and enum variants. As in
+/// Rust, references are dereferenced automatically.
 ///
 /// # Errors
 ///
@@ -19,7 +20,7 @@ pub fn eval_field(
     ctx: &EvalContext,
 ) -> Result<Value, EvalError> {
     // Evaluate the base expression
-    let base = field.base.eval(env, ctx)?;
+    let base = auto_deref(field.base.eval(env, ctx)?);
 
     match &field.member {
         // Named field access (struct)
@@ -118,6 +119,24 @@ pub fn eval_field(
     }
 }
 
+/// Follow references down to the value they point at.
+fn auto_deref(mut value: Value) -> Value {
+    loop {
+        value = match value {
+            Value::Ref(r) => (*r.value).clone(),
+            Value::RefMut(r) => {
+                let inner = r.value.read().ok().map(|guard| guard.clone());
+                match inner {
+                    Some(inner) => inner,
+                    // A poisoned lock is reported by the field lookup
+                    None => return Value::RefMut(r),
+                }
+            }
+            other => return other,
+        };
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -288,4 +307,24 @@ mod tests {
             panic!("Expected Field");
         }
     }
+
+    #[test]
+    fn test_field_through_references() {
+        let mut interp = crate::Interpreter::new();
+        interp.eval_str("struct P { x: i64 }").unwrap();
+        assert_eq!(
+            interp.eval_str("let p = P { x: 3 }; (&p).x").unwrap(),
+            Value::I64(3)
+        );
+        assert_eq!(
+            interp.eval_str("let t = (1, 2); let r = &&t; r.1").unwrap(),
+            Value::I64(2)
+        );
+        assert_eq!(
+            interp
+                .eval_str("let mut q = P { x: 4 }; (&mut q).x")
+                .unwrap(),
+            Value::I64(4)
+        );
+    }
 }
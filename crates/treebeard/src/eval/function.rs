@@ -2,7 +2,9 @@
 
 use std::sync::Arc;
 
-use crate::{Environment, EvalError, FunctionValue, Value};
+use crate::{BindingMode, Environment, EvalError, FunctionValue, Value};
+
+use super::cast::type_string;
 
 /// Extract a FunctionValue from a syn::ItemFn.
 ///
@@ -15,13 +17,58 @@ use crate::{Environment, EvalError, FunctionValue, Value};
 pub fn function_from_item(item_fn: &syn::ItemFn) -> Result<FunctionValue, EvalError> {
     let name = item_fn.sig.ident.to_string();
 
-    // Extract parameter names
-    let params = extract_params(&item_fn.sig)?;
+    // Extract parameter names, with any rest parameter last
+    let mut params = extract_params(&item_fn.sig)?;
+    let rest = rest_param(&item_fn.sig)?;
+    let variadic = rest.is_some();
+    params.extend(rest);
 
     // Store the body
     let body = item_fn.block.as_ref().clone();
 
-    Ok(FunctionValue::new(name, params, body))
+    let func = FunctionValue::new(name, params, body)
+        .with_param_types(param_types(&item_fn.sig))
+        .with_return_type(return_type(&item_fn.sig));
+    Ok(if variadic { func.with_variadic() } else { func })
+}
+
+/// Extract the name of a rest parameter, written `rest: ...` as the last
+/// parameter, which collects any extra arguments into a `Vec`.
+///
+/// # Errors
+///
+/// Returns `UnsupportedExpr` for a bare `...` or a complex pattern.
+pub(crate) fn rest_param(sig: &syn::Signature) -> Result<Option<String>, EvalError> {
+    let Some(variadic) = &sig.variadic else {
+        return Ok(None);
+    };
+    match &variadic.pat {
+        Some((pat, _)) => extract_pat_name(pat).map(Some),
+        None => Err(EvalError::UnsupportedExpr {
+            kind: "unnamed rest parameter (write `rest: ...`)".to_string(),
+            span: None,
+        }),
+    }
+}
+
+/// Extract the declared return type from a function signature.
+pub(crate) fn return_type(sig: &syn::Signature) -> Option<String> {
+    match &sig.output {
+        syn::ReturnType::Default => None,
+        syn::ReturnType::Type(_, ty) => Some(type_string(ty)),
+    }
+}
+
+/// Extract the declared parameter types from a function signature, with
+/// `Self` standing in for the receiver's type.
+pub(crate) fn param_types(sig: &syn::Signature) -> Vec<String> {
+    sig.inputs
+        .iter()
+        .map(|input| match input {
+            syn::FnArg::Typed(pat_type) => type_string(&pat_type.ty),
+            syn::FnArg::Receiver(_) => "Self".to_string(),
+        })
+        .collect()
 }
 
 /// Extract parameter names from a function signature.
@@ -56,7 +103,7 @@ fn extract_params(sig: &syn::Signature) -> Result<Vec<String>, EvalError> {
 /// # Errors
 ///
 /// Returns `UnsupportedExpr` for complex patterns like tuples or structs.
-fn extract_pat_name(pat: &syn::Pat) -> Result<String, EvalError> {
+pub(crate) fn extract_pat_name(pat: &syn::Pat) -> Result<String, EvalError> {
     match pat {
         syn::Pat::Ident(pat_ident) => Ok(pat_ident.ident.to_string()),
         syn::Pat::Wild(_) => Ok("_".to_string()),
@@ -78,13 +125,13 @@ fn extract_pat_name(pat: &syn::Pat) -> Result<String, EvalError> {
 ///
 /// Returns errors from `function_from_item` if the function cannot be extracted.
 pub fn define_function(item_fn: &syn::ItemFn, env: &mut Environment) -> Result<(), EvalError> {
-    let func = function_from_item(item_fn)?;
+    let func = function_from_item(item_fn)?.with_item_scope(env.item_scope());
     let name = func.name.clone();
     // ALLOW: syn::Block is Send + Sync (it's just AST data),
     // but clippy can't verify this automatically
     #[allow(clippy::arc_with_non_send_sync)]
     let func_value = Value::Function(Arc::new(func));
-    env.define(name, func_value);
+    env.define_item(name, func_value, BindingMode::Immutable);
     Ok(())
 }
 
@@ -112,6 +159,35 @@ mod tests {
         assert_eq!(func.params.len(), 0);
     }
 
+    #[test]
+    fn test_rest_param_makes_function_variadic() {
+        let item_fn: syn::ItemFn =
+            syn::parse_str("fn sum(first: i64, rest: ...) -> i64 { first }").unwrap();
+        let func = function_from_item(&item_fn).unwrap();
+        assert_eq!(func.params, vec!["first", "rest"]);
+        assert!(func.variadic);
+
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str(
+                "fn sum(first: i64, rest: ...) -> i64 {
+                     let mut total = first;
+                     for n in rest { total += n; }
+                     total
+                 }",
+            )
+            .unwrap();
+        assert_eq!(interp.eval_str("sum(1)").unwrap(), Value::I64(1));
+        assert_eq!(interp.eval_str("sum(1, 2, 3)").unwrap(), Value::I64(6));
+        assert!(interp.eval_str("sum()").is_err());
+
+        let item_fn: syn::ItemFn = syn::parse_str("fn f(a: i64, ...) {}").unwrap();
+        assert!(matches!(
+            function_from_item(&item_fn),
+            Err(EvalError::UnsupportedExpr { .. })
+        ));
+    }
+
     #[test]
     fn test_extract_params_with_references() {
         let source = "fn process(a: &str, b: &mut i64) -> () {}";
@@ -141,4 +217,90 @@ mod tests {
         let func_val = env.get("test").unwrap();
         assert!(matches!(func_val, Value::Function(_)));
     }
+
+    #[test]
+    fn test_generic_function_ignores_type_params() {
+        let mut interp = crate::Interpreter::new();
+        interp.eval_str("fn id<T>(x: T) -> T { x }").unwrap();
+        interp
+            .eval_str("fn first<T: Clone, U>(a: T, _b: U) -> T where T: Copy { a }")
+            .unwrap();
+
+        assert_eq!(interp.eval_str("id(42)").unwrap(), Value::I64(42));
+        assert_eq!(interp.eval_str("id(\"s\")").unwrap(), Value::string("s"));
+        assert_eq!(interp.eval_str("first('a', 1)").unwrap(), Value::Char('a'));
+    }
+
+    #[test]
+    fn test_const_fn_in_const_contexts() {
+        let mut interp = crate::Interpreter::new();
+        interp.eval_str("const fn two() -> usize { 2 }").unwrap();
+
+        assert_eq!(interp.eval_str("two()").unwrap(), Value::Usize(2));
+        assert_eq!(
+            interp.eval_str("[0; two()]").unwrap(),
+            Value::array(vec![Value::I64(0); 2])
+        );
+
+        interp.eval_str("const N: usize = two() + 1;").unwrap();
+        assert_eq!(interp.eval_str("N").unwrap(), Value::Usize(3));
+    }
+
+    #[test]
+    fn test_arguments_coerced_to_param_types() {
+        let source = "fn f(x: u8, s: &str) -> u8 { x }";
+        let item_fn: syn::ItemFn = syn::parse_str(source).unwrap();
+        let func = function_from_item(&item_fn).unwrap();
+        assert_eq!(func.param_types, vec!["u8", "&str"]);
+
+        let mut interp = crate::Interpreter::new();
+        interp.eval_str("fn f(x: u8) -> u8 { x }").unwrap();
+        assert_eq!(interp.eval_str("f(255)").unwrap(), Value::U8(255));
+
+        let err = interp.eval_str("f(256)").unwrap_err();
+        assert!(matches!(
+            err.inner(),
+            EvalError::IntegerOverflow { span: Some(_) }
+        ));
+        assert!(interp.eval_str("f(\"x\")").is_err());
+    }
+
+    #[test]
+    fn test_typed_params_mix_with_untyped_literals() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str("fn fact(n: u64) -> u64 { if n == 0 { 1 } else { n * fact(n - 1) } }")
+            .unwrap();
+        assert_eq!(interp.eval_str("fact(5)").unwrap(), Value::U64(120));
+
+        interp.eval_str("fn dbl(n: u32) -> u32 { n * 2 }").unwrap();
+        assert_eq!(interp.eval_str("dbl(21)").unwrap(), Value::U32(42));
+
+        interp.eval_str("fn f(x: u8) -> u8 { x }").unwrap();
+        assert_eq!(interp.eval_str("f(3) == 3").unwrap(), Value::Bool(true));
+        assert_eq!(interp.eval_str("3 < f(4)").unwrap(), Value::Bool(true));
+
+        interp
+            .eval_str("fn g(n: u8) -> u8 { match n { 0 => 10, 1..=9 => n + 1, _ => 0 } }")
+            .unwrap();
+        assert_eq!(interp.eval_str("g(0)").unwrap(), Value::U8(10));
+        assert_eq!(interp.eval_str("g(5)").unwrap(), Value::U8(6));
+    }
+
+    #[test]
+    fn test_result_coerced_to_return_type() {
+        let mut interp = crate::Interpreter::new();
+        interp.eval_str("fn f() -> u8 { 200 + 55 }").unwrap();
+        assert_eq!(interp.eval_str("f()").unwrap(), Value::U8(255));
+
+        interp
+            .eval_str("fn g(x: i64) -> u8 { if x > 0 { return 1; } 300 }")
+            .unwrap();
+        assert_eq!(interp.eval_str("g(1)").unwrap(), Value::U8(1));
+        let err = interp.eval_str("g(0)").unwrap_err();
+        assert!(matches!(
+            err.inner(),
+            EvalError::IntegerOverflow { span: Some(_) }
+        ));
+    }
 }
@@ -1,30 +1,36 @@
 //! If expression evaluation
 
+use super::pattern::{apply_bindings, match_pattern};
+use super::stmt::eval_block;
 use super::Evaluate;
 use crate::{Environment, EvalContext, EvalError, Value};
 
 impl Evaluate for syn::ExprIf {
     fn eval(&self, env: &mut Environment, ctx: &EvalContext) -> Result<Value, EvalError> {
-        // Evaluate condition
-        let cond = self.cond.eval(env, ctx)?;
+        // Bindings made by `let` in the condition are scoped to the then
+        // branch, so the condition gets a frame of its own
+        let then_value = {
+            let mut scope = env.scope_guard();
+            let cond = self.cond.eval(&mut scope, ctx)?;
 
-        // Condition must be boolean
-        let cond_bool = match cond {
-            Value::Bool(b) => b,
-            other => {
-                return Err(EvalError::TypeError {
-                    message: format!(
-                        "expected `bool` in if condition, found `{}`",
-                        crate::error::type_name(&other)
-                    ),
-                    span: expr_span(&self.cond),
-                });
+            // Condition must be boolean, unless truthy conditions are on
+            match ctx.condition(&cond) {
+                Some(true) => Some(eval_block(&self.then_branch, &mut scope, ctx)?),
+                Some(false) => None,
+                None => {
+                    return Err(EvalError::TypeError {
+                        message: format!(
+                            "expected `bool` in if condition, found `{}`",
+                            crate::error::type_name(&cond)
+                        ),
+                        span: expr_span(&self.cond),
+                    });
+                }
             }
         };
 
-        if cond_bool {
-            // Evaluate then branch
-            eval_block(&self.then_branch, env, ctx)
+        if let Some(value) = then_value {
+            Ok(value)
         } else if let Some((_, else_branch)) = &self.else_branch {
             // Evaluate else branch
             match else_branch.as_ref() {
@@ -39,71 +45,22 @@ impl Evaluate for syn::ExprIf {
     }
 }
 
-/// Evaluate a block, returning the value of the last expression.
-pub fn eval_block(
-    block: &syn::Block,
-    env: &mut Environment,
-    ctx: &EvalContext,
-) -> Result<Value, EvalError> {
-    // Push a new scope for the block
-    env.push_frame();
-
-    let result = eval_block_inner(block, env, ctx);
-
-    // Pop the scope (even on error)
-    env.pop_frame();
-
-    result
-}
-
-fn eval_block_inner(
-    block: &syn::Block,
-    env: &mut Environment,
-    ctx: &EvalContext,
-) -> Result<Value, EvalError> {
-    let mut last_value = Value::Unit;
-
-    for stmt in &block.stmts {
-        last_value = eval_stmt(stmt, env, ctx)?;
-    }
-
-    Ok(last_value)
-}
+/// A `let` expression in a condition (`if let`, `while let`, let-chains).
+///
+/// Evaluates to whether the pattern matched. On a match, the pattern's
+/// bindings are added to the current scope, which the enclosing `if` or
+/// `while` limits to its body.
+impl Evaluate for syn::ExprLet {
+    fn eval(&self, env: &mut Environment, ctx: &EvalContext) -> Result<Value, EvalError> {
+        let value = self.expr.eval(env, ctx)?;
 
-/// Evaluate a statement (placeholder - full impl in Stage 1.6).
-fn eval_stmt(
-    stmt: &syn::Stmt,
-    env: &mut Environment,
-    ctx: &EvalContext,
-) -> Result<Value, EvalError> {
-    match stmt {
-        syn::Stmt::Expr(expr, None) => {
-            // Expression without semicolon - its value is the block's value
-            expr.eval(env, ctx)
-        }
-        syn::Stmt::Expr(expr, Some(_)) => {
-            // Expression with semicolon - evaluate for side effects, return unit
-            expr.eval(env, ctx)?;
-            Ok(Value::Unit)
-        }
-        syn::Stmt::Local(_) => {
-            // Let binding - implemented in Stage 1.6
-            Err(EvalError::UnsupportedExpr {
-                kind: "let binding (not yet implemented)".to_string(),
-                span: None,
-            })
-        }
-        syn::Stmt::Item(_) => {
-            // Item in block - implemented in Stage 1.5/1.6
-            Err(EvalError::UnsupportedExpr {
-                kind: "item in block (not yet implemented)".to_string(),
-                span: None,
-            })
+        match match_pattern(&self.pat, &value, None)? {
+            Some(bindings) => {
+                apply_bindings(env, bindings);
+                Ok(Value::Bool(true))
+            }
+            None => Ok(Value::Bool(false)),
         }
-        syn::Stmt::Macro(_) => Err(EvalError::UnsupportedExpr {
-            kind: "macro statement".to_string(),
-            span: None,
-        }),
     }
 }
 
@@ -125,6 +82,37 @@ mod tests {
         assert_eq!(result, Value::I64(42));
     }
 
+    #[test]
+    fn test_if_let() {
+        let mut interp = crate::Interpreter::new();
+        let result = interp.eval_str("if let Some(x) = Some(1) { x } else { 0 }");
+        assert_eq!(result.unwrap(), Value::I64(1));
+
+        let result = interp.eval_str("if let Some(x) = None { x } else { 0 }");
+        assert_eq!(result.unwrap(), Value::I64(0));
+
+        // Bindings do not escape the then branch
+        assert!(!interp.env().contains("x"));
+    }
+
+    #[test]
+    fn test_let_expression_is_bool() {
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(
+            interp.eval_str("true && let Some(_) = None").unwrap(),
+            Value::Bool(false)
+        );
+    }
+
+    #[test]
+    fn test_let_chain() {
+        let mut interp = crate::Interpreter::new();
+        let result = interp
+            .eval_str("let o = Some(3); if true && let Some(x) = o && x > 2 { x } else { 0 }")
+            .unwrap();
+        assert_eq!(result, Value::I64(3));
+    }
+
     #[test]
     fn test_if_false_branch() {
         let expr: syn::ExprIf = syn::parse_quote!(if false { 42 } else { 0 });
@@ -171,6 +159,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_if_truthy_conditions() {
+        let expr: syn::ExprIf = syn::parse_quote!(if 0 { 1 } else { 2 });
+        let mut env = Environment::new();
+        let mut ctx = EvalContext::default();
+        ctx.truthy_conditions = true;
+        assert_eq!(expr.eval(&mut env, &ctx).unwrap(), Value::I64(2));
+
+        let expr: syn::ExprIf = syn::parse_quote!(if "s" { 1 } else { 2 });
+        assert_eq!(expr.eval(&mut env, &ctx).unwrap(), Value::I64(1));
+
+        let expr: syn::ExprIf = syn::parse_quote!(if 0 { 1 } else { 2 });
+        assert!(expr.eval(&mut env, &EvalContext::default()).is_err());
+    }
+
     #[test]
     fn test_if_else_if() {
         let expr: syn::ExprIf = syn::parse_quote! {
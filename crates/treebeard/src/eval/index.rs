@@ -1,16 +1,20 @@
 //! Index expression evaluation
 
+use syn::spanned::Spanned;
+
 use crate::{EvalContext, EvalError, Value};
 
 use super::Evaluate;
 
 /// Evaluate an index expression.
 ///
-/// Supports indexing into Vec, Array, String, and HashMap.
+/// Supports indexing into Vec, Array, String, and HashMap. A range index
+/// slices a Vec, Array, or String instead; see [`eval_slice`].
 ///
 /// # Errors
 ///
 /// Returns `IndexOutOfBounds` if the index is out of range.
+/// Returns `NegativeIndex` if an integer index is negative.
 /// Returns `KeyNotFound` if the key doesn't exist in a HashMap.
 /// Returns `TypeError` if the base value doesn't support indexing.
 pub fn eval_index(
@@ -21,19 +25,18 @@ pub fn eval_index(
     // Evaluate the base expression
     let base = index.expr.eval(env, ctx)?;
 
+    // Range indices slice the base rather than expanding to a Vec
+    if let syn::Expr::Range(range) = index.index.as_ref() {
+        return eval_slice(&base, range, env, ctx);
+    }
+
     // Evaluate the index expression
     let index_val = index.index.eval(env, ctx)?;
 
     match base {
         // Vec indexing
         Value::Vec(vec) => {
-            let idx = index_val.as_usize().ok_or_else(|| EvalError::TypeError {
-                message: format!(
-                    "vec index must be integer, got {}",
-                    crate::error::type_name(&index_val)
-                ),
-                span: None,
-            })?;
+            let idx = index_to_usize(&index_val, "vec", None)?;
 
             vec.get(idx)
                 .cloned()
@@ -46,13 +49,7 @@ pub fn eval_index(
 
         // Array indexing
         Value::Array(arr) => {
-            let idx = index_val.as_usize().ok_or_else(|| EvalError::TypeError {
-                message: format!(
-                    "array index must be integer, got {}",
-                    crate::error::type_name(&index_val)
-                ),
-                span: None,
-            })?;
+            let idx = index_to_usize(&index_val, "array", None)?;
 
             arr.get(idx)
                 .cloned()
@@ -65,13 +62,7 @@ pub fn eval_index(
 
         // String indexing (returns char)
         Value::String(s) => {
-            let idx = index_val.as_usize().ok_or_else(|| EvalError::TypeError {
-                message: format!(
-                    "string index must be integer, got {}",
-                    crate::error::type_name(&index_val)
-                ),
-                span: None,
-            })?;
+            let idx = index_to_usize(&index_val, "string", None)?;
 
             s.chars()
                 .nth(idx)
@@ -115,6 +106,124 @@ pub fn eval_index(
     }
 }
 
+/// Evaluate `base[range]`, slicing a Vec, Array, or String.
+///
+/// Either bound may be omitted. String ranges are in bytes, as in Rust.
+///
+/// # Errors
+///
+/// Returns `IndexOutOfBounds` if the range ends past the end of `base` or
+/// starts after it ends.
+/// Returns `TypeError` if a bound is not an integer, a string bound is not
+/// on a char boundary, or `base` cannot be sliced.
+fn eval_slice(
+    base: &Value,
+    range: &syn::ExprRange,
+    env: &mut crate::Environment,
+    ctx: &EvalContext,
+) -> Result<Value, EvalError> {
+    let len = match base {
+        Value::Vec(items) | Value::Array(items) => items.len(),
+        Value::String(s) => s.len(),
+        _ => {
+            return Err(EvalError::TypeError {
+                message: format!("cannot slice {}", crate::error::type_name(base)),
+                span: Some(range.span()),
+            })
+        }
+    };
+
+    let start = match &range.start {
+        Some(expr) => eval_slice_bound(expr, env, ctx)?,
+        None => 0,
+    };
+    let end = match (&range.end, &range.limits) {
+        (Some(expr), syn::RangeLimits::Closed(_)) => {
+            eval_slice_bound(expr, env, ctx)?.saturating_add(1)
+        }
+        (Some(expr), syn::RangeLimits::HalfOpen(_)) => eval_slice_bound(expr, env, ctx)?,
+        (None, _) => len,
+    };
+
+    if end > len {
+        return Err(EvalError::IndexOutOfBounds {
+            index: end,
+            len,
+            span: Some(range.span()),
+        });
+    }
+    if start > end {
+        return Err(EvalError::IndexOutOfBounds {
+            index: start,
+            len: end,
+            span: Some(range.span()),
+        });
+    }
+
+    match base {
+        Value::Vec(items) => Ok(Value::vec(items[start..end].to_vec())),
+        Value::Array(items) => Ok(Value::array(items[start..end].to_vec())),
+        Value::String(s) => {
+            s.get(start..end)
+                .map(Value::string)
+                .ok_or_else(|| EvalError::TypeError {
+                    message: format!(
+                        "byte range {}..{} is not on a char boundary of {:?}",
+                        start,
+                        end,
+                        s.as_str()
+                    ),
+                    span: Some(range.span()),
+                })
+        }
+        _ => unreachable!("checked above"),
+    }
+}
+
+/// Evaluate one bound of a slice range as a `usize`.
+fn eval_slice_bound(
+    expr: &syn::Expr,
+    env: &mut crate::Environment,
+    ctx: &EvalContext,
+) -> Result<usize, EvalError> {
+    let value = expr.eval(env, ctx)?;
+    index_to_usize(&value, "slice", Some(expr.span()))
+}
+
+/// Convert an index value to a `usize`.
+///
+/// `kind` names the collection for the error message.
+///
+/// # Errors
+///
+/// Returns `NegativeIndex` for a negative integer, or `TypeError` for a
+/// value that is not an integer.
+pub(crate) fn index_to_usize(
+    value: &Value,
+    kind: &str,
+    span: Option<proc_macro2::Span>,
+) -> Result<usize, EvalError> {
+    if let Some(idx) = value.as_usize() {
+        return Ok(idx);
+    }
+
+    let negative = match value {
+        Value::I128(n) => Some(*n),
+        other => other.as_i64().map(i128::from),
+    };
+    match negative {
+        Some(index) if index < 0 => Err(EvalError::NegativeIndex { index, span }),
+        _ => Err(EvalError::TypeError {
+            message: format!(
+                "{} index must be integer, got {}",
+                kind,
+                crate::error::type_name(value)
+            ),
+            span,
+        }),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -261,4 +370,101 @@ mod tests {
             panic!("Expected Index");
         }
     }
+
+    fn eval_str(src: &str, env: &mut Environment) -> Result<Value, EvalError> {
+        let expr: syn::Expr = syn::parse_str(src).unwrap();
+        expr.eval(env, &EvalContext::default())
+    }
+
+    #[test]
+    fn test_hashmap_index_string_literal() {
+        use std::collections::HashMap;
+
+        let mut env = Environment::new();
+        let mut map = HashMap::new();
+        map.insert(
+            crate::value::HashableValue(Value::string("a")),
+            Value::I64(1),
+        );
+        env.define("map".to_string(), Value::HashMap(std::sync::Arc::new(map)));
+
+        assert_eq!(eval_str("map[\"a\"]", &mut env).unwrap(), Value::I64(1));
+        assert!(matches!(
+            eval_str("map[\"b\"]", &mut env),
+            Err(EvalError::KeyNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_string_slice() {
+        let mut env = Environment::new();
+        env.define("s".to_string(), Value::string("héllo"));
+
+        assert_eq!(eval_str("s[0..1]", &mut env).unwrap(), Value::string("h"));
+        assert_eq!(eval_str("s[3..]", &mut env).unwrap(), Value::string("llo"));
+        assert_eq!(eval_str("s[..=2]", &mut env).unwrap(), Value::string("hé"));
+        assert!(matches!(
+            eval_str("s[0..2]", &mut env),
+            Err(EvalError::TypeError { .. })
+        ));
+        assert!(matches!(
+            eval_str("s[4..9]", &mut env),
+            Err(EvalError::IndexOutOfBounds {
+                index: 9,
+                len: 6,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_vec_slice() {
+        let mut env = Environment::new();
+        env.define(
+            "v".to_string(),
+            Value::vec(vec![Value::I64(1), Value::I64(2), Value::I64(3)]),
+        );
+
+        assert_eq!(
+            eval_str("v[1..]", &mut env).unwrap(),
+            Value::vec(vec![Value::I64(2), Value::I64(3)])
+        );
+        assert!(matches!(
+            eval_str("v[2..1]", &mut env),
+            Err(EvalError::IndexOutOfBounds { .. })
+        ));
+        assert!(matches!(
+            eval_str("v[true]", &mut env),
+            Err(EvalError::TypeError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_negative_index() {
+        let mut env = Environment::new();
+        env.define(
+            "v".to_string(),
+            Value::vec(vec![Value::I64(1), Value::I64(2), Value::I64(3)]),
+        );
+
+        let err = eval_str("v[-1]", &mut env).unwrap_err();
+        assert!(matches!(err, EvalError::NegativeIndex { index: -1, .. }));
+        assert_eq!(err.to_string(), "cannot index with negative value -1");
+
+        let err = eval_str("[1, 2, 3][9]", &mut env).unwrap_err();
+        assert!(matches!(
+            err,
+            EvalError::IndexOutOfBounds {
+                index: 9,
+                len: 3,
+                ..
+            }
+        ));
+        assert_eq!(err.to_string(), "index out of bounds: index 9 >= len 3");
+
+        assert!(matches!(
+            eval_str("v[1.5]", &mut env),
+            Err(EvalError::TypeError { .. })
+        ));
+    }
 }
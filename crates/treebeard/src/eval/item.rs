@@ -2,47 +2,72 @@
 
 use std::sync::Arc;
 
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+
 use crate::{BindingMode, Environment, EvalContext, EvalError, FunctionValue, Value};
 
-use super::function::function_from_item;
+use super::function::{function_from_item, param_types, rest_param, return_type};
 use super::Evaluate;
 
 /// Evaluate a top-level item.
 ///
+/// Items whose `#[cfg(...)]` predicate is false under the context's
+/// `cfg_flags` are skipped. Other attributes are ignored.
+///
 /// # Errors
 ///
-/// Returns `UnsupportedExpr` for items that aren't yet implemented.
+/// Returns `UnsupportedExpr` for items that aren't yet implemented and
+/// for `cfg` predicates that can't be evaluated.
 pub fn eval_item(
     item: &syn::Item,
     env: &mut Environment,
     ctx: &EvalContext,
 ) -> Result<Value, EvalError> {
+    if !cfg_enabled(item_attrs(item), ctx)? {
+        return Ok(Value::Unit);
+    }
+
     match item {
         syn::Item::Fn(item_fn) => {
-            let func = function_from_item(item_fn)?;
+            let func = function_from_item(item_fn)?.with_item_scope(env.item_scope());
             let name = func.name.clone();
             // ALLOW: syn::Block is Send + Sync (it's just AST data),
             // but clippy can't verify this automatically
             #[allow(clippy::arc_with_non_send_sync)]
             let func_value = Value::Function(Arc::new(func));
-            env.define(name, func_value);
+            env.define_item(name, func_value, BindingMode::Immutable);
             Ok(Value::Unit)
         }
 
         syn::Item::Const(item_const) => {
-            // Evaluate the const expression
-            let value = item_const.expr.eval(env, ctx)?;
+            // Fold the const expression once, at definition time
+            let value = item_const
+                .expr
+                .eval(env, ctx)?
+                .coerce_to_type_name(&super::cast::type_string(&item_const.ty))
+                .map_err(|e| e.or_span(Some(item_const.expr.span())))?;
             let name = item_const.ident.to_string();
-            env.define(name, value);
+            // A const in a function body or block belongs to that scope, so
+            // evaluating the body again defines it afresh
+            if env.is_global_scope() {
+                env.define_constant(name, value)?;
+            } else {
+                env.define_item(name, value, BindingMode::Immutable);
+            }
             Ok(Value::Unit)
         }
 
         syn::Item::Static(item_static) => {
             // Evaluate the static expression
-            let value = item_static.expr.eval(env, ctx)?;
+            let value = item_static
+                .expr
+                .eval(env, ctx)?
+                .coerce_to_type_name(&super::cast::type_string(&item_static.ty))
+                .map_err(|e| e.or_span(Some(item_static.expr.span())))?;
             let name = item_static.ident.to_string();
             // Statics are mutable by default in the interpreter
-            env.define_with_mode(
+            env.define_item(
                 name,
                 value,
                 match item_static.mutability {
@@ -54,33 +79,51 @@ pub fn eval_item(
             Ok(Value::Unit)
         }
 
-        // Struct/Enum definitions - just register the type name for now
+        // Struct/Enum definitions - register the type's shape
         syn::Item::Struct(item_struct) => {
             let name = item_struct.ident.to_string();
-            // Store struct definition for constructor calls
-            // For now, we don't need to do anything special
-            // Struct literals will be handled in expressions
-            let _ = name;
+            let fields = item_struct
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| match &field.ident {
+                    Some(ident) => ident.to_string(),
+                    None => i.to_string(),
+                })
+                .collect();
+            env.register_struct(name, fields);
             Ok(Value::Unit)
         }
 
         syn::Item::Enum(item_enum) => {
             let name = item_enum.ident.to_string();
-            let _ = name;
+            let variants = item_enum
+                .variants
+                .iter()
+                .map(|v| v.ident.to_string())
+                .collect();
+            env.register_enum(name, variants);
             Ok(Value::Unit)
         }
 
-        // Impl blocks - register methods
+        // Impl blocks - register methods under their bare name, for method
+        // call syntax, and as `Type::name`, for associated function calls
         syn::Item::Impl(item_impl) => {
-            // For now, just evaluate any associated functions
+            let type_name = impl_type_name(&item_impl.self_ty);
             for impl_item in &item_impl.items {
                 if let syn::ImplItem::Fn(method) = impl_item {
+                    if !cfg_enabled(&method.attrs, ctx)? {
+                        continue;
+                    }
                     let func = function_from_impl_method(method, &item_impl.self_ty)?;
                     let name = func.name.clone();
                     // ALLOW: syn::Block is Send + Sync (it's just AST data),
                     // but clippy can't verify this automatically
                     #[allow(clippy::arc_with_non_send_sync)]
                     let func_value = Value::Function(Arc::new(func));
+                    if let Some(type_name) = &type_name {
+                        env.define(format!("{}::{}", type_name, name), func_value.clone());
+                    }
                     env.define(name, func_value);
                 }
             }
@@ -113,16 +156,131 @@ pub fn eval_item(
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════
+// Conditional Compilation
+// ═══════════════════════════════════════════════════════════════════
+
+/// The attributes on an item.
+fn item_attrs(item: &syn::Item) -> &[syn::Attribute] {
+    match item {
+        syn::Item::Const(item) => &item.attrs,
+        syn::Item::Enum(item) => &item.attrs,
+        syn::Item::ExternCrate(item) => &item.attrs,
+        syn::Item::Fn(item) => &item.attrs,
+        syn::Item::ForeignMod(item) => &item.attrs,
+        syn::Item::Impl(item) => &item.attrs,
+        syn::Item::Macro(item) => &item.attrs,
+        syn::Item::Mod(item) => &item.attrs,
+        syn::Item::Static(item) => &item.attrs,
+        syn::Item::Struct(item) => &item.attrs,
+        syn::Item::Trait(item) => &item.attrs,
+        syn::Item::TraitAlias(item) => &item.attrs,
+        syn::Item::Type(item) => &item.attrs,
+        syn::Item::Union(item) => &item.attrs,
+        syn::Item::Use(item) => &item.attrs,
+        _ => &[],
+    }
+}
+
+/// Whether every `#[cfg(...)]` among `attrs` holds.
+fn cfg_enabled(attrs: &[syn::Attribute], ctx: &EvalContext) -> Result<bool, EvalError> {
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("cfg")) {
+        let predicate: syn::Meta = attr.parse_args().map_err(|e| EvalError::UnsupportedExpr {
+            kind: format!("cfg predicate: {}", e),
+            span: Some(attr.span()),
+        })?;
+        if !eval_cfg(&predicate, ctx)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Evaluate a `cfg` predicate: a flag name, `feature = "name"`, or
+/// `all(..)`, `any(..)` and `not(..)` over other predicates.
+/// `debug_assertions` follows [`EvalContext::debug_assertions`]. Other
+/// `key = "value"` options, such as `target_os`, are never set.
+fn eval_cfg(predicate: &syn::Meta, ctx: &EvalContext) -> Result<bool, EvalError> {
+    let unsupported = || EvalError::UnsupportedExpr {
+        kind: format!(
+            "cfg predicate `{}`",
+            quote::ToTokens::to_token_stream(predicate)
+        ),
+        span: Some(predicate.span()),
+    };
+
+    match predicate {
+        syn::Meta::Path(path) => {
+            let name = path.get_ident().ok_or_else(unsupported)?;
+            if name == "debug_assertions" {
+                return Ok(ctx.debug_assertions);
+            }
+            Ok(ctx.cfg_flags.contains(&name.to_string()))
+        }
+        syn::Meta::NameValue(name_value) => match &name_value.value {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(value),
+                ..
+            }) => Ok(name_value.path.is_ident("feature") && ctx.cfg_flags.contains(&value.value())),
+            _ => Err(unsupported()),
+        },
+        syn::Meta::List(list) => {
+            let operands = list
+                .parse_args_with(Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated)
+                .map_err(|_| unsupported())?;
+            let results = operands
+                .iter()
+                .map(|operand| eval_cfg(operand, ctx))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if list.path.is_ident("all") {
+                Ok(results.iter().all(|&held| held))
+            } else if list.path.is_ident("any") {
+                Ok(results.iter().any(|&held| held))
+            } else if list.path.is_ident("not") && results.len() == 1 {
+                Ok(!results[0])
+            } else {
+                Err(unsupported())
+            }
+        }
+    }
+}
+
+/// The name an impl block's associated functions are qualified with, e.g.
+/// `Point` for `impl Point` and `impl<T> Point<T>`.
+fn impl_type_name(self_ty: &syn::Type) -> Option<String> {
+    match self_ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+}
+
 /// Extract a FunctionValue from an impl method.
 fn function_from_impl_method(
     method: &syn::ImplItemFn,
     _self_ty: &syn::Type,
 ) -> Result<FunctionValue, EvalError> {
     let name = method.sig.ident.to_string();
-    let params = extract_method_params(&method.sig)?;
+    let mut params = extract_method_params(&method.sig)?;
+    let rest = rest_param(&method.sig)?;
+    let variadic = rest.is_some();
+    params.extend(rest);
     let body = method.block.clone();
 
-    Ok(FunctionValue::new(name, params, body))
+    let func = FunctionValue::new(name, params, body)
+        .with_param_types(param_types(&method.sig))
+        .with_return_type(return_type(&method.sig));
+    let func = if variadic { func.with_variadic() } else { func };
+    match method.sig.receiver() {
+        Some(receiver) if receiver.reference.is_some() && receiver.mutability.is_some() => {
+            Ok(func.with_mut_self())
+        }
+        _ => Ok(func),
+    }
 }
 
 /// Extract parameter names from a method signature.
@@ -196,6 +354,67 @@ mod tests {
         assert!(matches!(func, Value::Function(_)));
     }
 
+    #[test]
+    fn test_nested_fn_does_not_capture_locals() {
+        let mut interp = crate::Interpreter::new();
+        let err = interp
+            .eval_str("fn outer() -> i64 { let x = 1; fn inner() -> i64 { x } inner() } outer()")
+            .unwrap_err();
+        assert!(matches!(
+            err.inner(),
+            EvalError::UndefinedVariable { name, .. } if name == "x"
+        ));
+    }
+
+    #[test]
+    fn test_nested_fn_items_are_not_dynamically_scoped() {
+        let mut interp = crate::Interpreter::new();
+        let err = interp
+            .eval_str(
+                "fn b() -> i64 { fn helper() -> i64 { 7 } a() }
+                 fn a() -> i64 { helper() }
+                 b()",
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err.inner(),
+            EvalError::UndefinedVariable { name, .. } if name == "helper"
+        ));
+
+        let err = interp
+            .eval_str(
+                "fn outer() -> i64 {
+                     fn one() -> i64 { 1 }
+                     let h = one;
+                     fn inner() -> i64 { h() }
+                     inner()
+                 }
+                 outer()",
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err.inner(),
+            EvalError::UndefinedVariable { name, .. } if name == "h"
+        ));
+    }
+
+    #[test]
+    fn test_nested_fn_sees_consts_and_items() {
+        let mut interp = crate::Interpreter::new();
+        let result = interp
+            .eval_str(
+                "const BASE: i64 = 10;
+                 fn outer() -> i64 {
+                     fn fact(n: i64) -> i64 { if n <= 1 { 1 } else { n * fact(n - 1) } }
+                     fn scaled(n: i64) -> i64 { BASE * fact(n) }
+                     scaled(3)
+                 }
+                 outer()",
+            )
+            .unwrap();
+        assert_eq!(result, Value::I64(60));
+    }
+
     #[test]
     fn test_eval_const_item() {
         let source = "const X: i64 = 42;";
@@ -211,6 +430,65 @@ mod tests {
         assert_eq!(value, &Value::I64(42));
     }
 
+    #[test]
+    fn test_const_redefinition_errors() {
+        let mut interp = crate::Interpreter::new();
+        interp.eval_str("const MAX: i64 = 10;").unwrap();
+
+        let result = interp.eval_str("const MAX: i64 = 20;");
+        assert!(matches!(
+            result,
+            Err(EvalError::Environment(
+                crate::EnvironmentError::ConstantRedefinition { .. }
+            ))
+        ));
+        assert!(interp.eval_str("MAX = 20").is_err());
+        assert_eq!(interp.eval_str("MAX").unwrap(), Value::I64(10));
+    }
+
+    #[test]
+    fn test_const_and_static_coerced_to_annotation() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str("const N: u8 = 200; static M: u64 = 7;")
+            .unwrap();
+        assert_eq!(interp.eval_str("N").unwrap(), Value::U8(200));
+        assert_eq!(interp.eval_str("M").unwrap(), Value::U64(7));
+
+        let err = interp.eval_str("const BIG: u8 = 300;").unwrap_err();
+        assert!(matches!(err, EvalError::IntegerOverflow { span: Some(_) }));
+        assert!(interp.eval_str("static S: String = 5;").is_err());
+    }
+
+    #[test]
+    fn test_const_in_fn_body_reevaluated_per_call() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str(
+                "fn f() -> i64 {
+                     const K: i64 = 2;
+                     fn g() -> i64 { K * 10 }
+                     K + g()
+                 }",
+            )
+            .unwrap();
+        assert_eq!(interp.eval_str("f(); f()").unwrap(), Value::I64(22));
+        assert_eq!(
+            interp
+                .eval_str("let mut n = 0; for _ in 0..3 { const STEP: i64 = 5; n += STEP; } n")
+                .unwrap(),
+            Value::I64(15)
+        );
+        assert!(interp.eval_str("K").is_err());
+    }
+
+    #[test]
+    fn test_const_in_array_repeat() {
+        let mut interp = crate::Interpreter::new();
+        let result = interp.eval_str("const N: usize = 3; [0; N]").unwrap();
+        assert_eq!(result, Value::array(vec![Value::I64(0); 3]));
+    }
+
     #[test]
     fn test_eval_static_item() {
         let source = "static Y: i64 = 100;";
@@ -320,8 +598,35 @@ mod tests {
         let result = eval_item(&item, &mut env, &ctx).unwrap();
         assert_eq!(result, Value::Unit);
 
-        // Method should be registered
+        // Method should be registered, bare and qualified
         assert!(env.get("new").is_some());
+        assert!(env.get("Point::new").is_some());
+    }
+
+    #[test]
+    fn test_associated_function_call() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str(
+                r#"
+                struct Point { x: i64, y: i64 }
+                impl Point {
+                    fn new(x: i64, y: i64) -> Self { Point { x, y } }
+                    fn sum(&self) -> i64 { self.x + self.y }
+                }
+                "#,
+            )
+            .unwrap();
+
+        assert_eq!(
+            interp.eval_str("Point::new(1, 2).x").unwrap(),
+            Value::I64(1)
+        );
+        assert_eq!(
+            interp.eval_str("Point::new(1, 2).sum()").unwrap(),
+            Value::I64(3)
+        );
+        assert!(interp.eval_str("Line::new(1, 2)").is_err());
     }
 
     #[test]
@@ -588,4 +893,92 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_cfg_gated_items() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str(
+                r#"
+                #[cfg(feature = "off")]
+                fn gated() -> i64 { 1 }
+                #[cfg(not(feature = "off"))]
+                fn ungated() -> i64 { 2 }
+                "#,
+            )
+            .unwrap();
+
+        assert!(interp.env().get("gated").is_none());
+        assert_eq!(interp.eval_str("ungated()").unwrap(), Value::I64(2));
+    }
+
+    #[test]
+    fn test_cfg_debug_assertions_follows_context() {
+        let src = r#"
+            #[cfg(debug_assertions)]
+            const MODE: &str = "debug";
+            #[cfg(not(debug_assertions))]
+            const MODE: &str = "release";
+        "#;
+
+        let mut interp = crate::Interpreter::new();
+        interp.eval_str(src).unwrap();
+        assert_eq!(interp.eval_str("MODE").unwrap(), Value::string("release"));
+
+        let mut interp = crate::Interpreter::new();
+        interp.context_mut().debug_assertions = true;
+        interp.eval_str(src).unwrap();
+        assert_eq!(interp.eval_str("MODE").unwrap(), Value::string("debug"));
+    }
+
+    #[test]
+    fn test_cfg_flags_enable_items() {
+        let ctx = EvalContext::with_cfg_flags(["on", "extra"]);
+        let mut interp = crate::Interpreter::with_context(ctx);
+        interp
+            .eval_str(
+                r#"
+                #[cfg(all(feature = "on", any(extra, missing)))]
+                const X: i64 = 1;
+                #[cfg(target_os = "none")]
+                const X: i64 = 2;
+                struct S;
+                impl S {
+                    #[cfg(feature = "on")]
+                    fn on() -> i64 { 3 }
+                    #[cfg(missing)]
+                    fn off() -> i64 { 4 }
+                }
+                "#,
+            )
+            .unwrap();
+
+        assert_eq!(interp.eval_str("X").unwrap(), Value::I64(1));
+        assert_eq!(interp.eval_str("S::on()").unwrap(), Value::I64(3));
+        assert!(interp.env().get("S::off").is_none());
+    }
+
+    #[test]
+    fn test_other_attributes_are_ignored() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str(
+                r#"
+                #[derive(Debug, Clone)]
+                struct P { x: i64 }
+                #[inline]
+                #[allow(dead_code)]
+                fn get(p: P) -> i64 { p.x }
+                "#,
+            )
+            .unwrap();
+        assert_eq!(interp.eval_str("get(P { x: 5 })").unwrap(), Value::I64(5));
+    }
+
+    #[test]
+    fn test_malformed_cfg_errors() {
+        let mut interp = crate::Interpreter::new();
+        let result = interp.eval_str("#[cfg(not(a, b))] fn f() {}");
+        assert!(matches!(result, Err(EvalError::UnsupportedExpr { .. })));
+    }
 }
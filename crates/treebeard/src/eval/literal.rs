@@ -112,6 +112,41 @@ fn eval_int_literal(lit: &syn::LitInt) -> Result<Value, EvalError> {
     }
 }
 
+/// Evaluate the negation of an integer literal, as in `-128i8`.
+///
+/// The literal is parsed in the negative domain, so the most negative
+/// value of each signed type is accepted even though its magnitude does
+/// not fit. Returns `Ok(None)` for unsigned or unknown suffixes, which
+/// are left to ordinary negation to reject.
+///
+/// # Errors
+///
+/// Returns `IntegerOverflow` if the negated value does not fit the type.
+pub(crate) fn eval_negated_int_literal(lit: &syn::LitInt) -> Result<Option<Value>, EvalError> {
+    let span = Some(lit.span());
+    let overflow = |_| overflow_error(span);
+
+    let magnitude = lit
+        .base10_parse::<u128>()
+        .map_err(|_| overflow_error(span))?;
+    let negated = if magnitude == i128::MIN.unsigned_abs() {
+        i128::MIN
+    } else {
+        -i128::try_from(magnitude).map_err(overflow)?
+    };
+
+    let value = match lit.suffix() {
+        "i8" => Value::I8(negated.try_into().map_err(overflow)?),
+        "i16" => Value::I16(negated.try_into().map_err(overflow)?),
+        "i32" => Value::I32(negated.try_into().map_err(overflow)?),
+        "i64" | "" => Value::I64(negated.try_into().map_err(overflow)?),
+        "i128" => Value::I128(negated),
+        "isize" => Value::Isize(negated.try_into().map_err(overflow)?),
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
+
 /// Evaluate a float literal, respecting suffixes.
 fn eval_float_literal(lit: &syn::LitFloat) -> Result<Value, EvalError> {
     let suffix = lit.suffix();
@@ -141,6 +176,48 @@ fn eval_float_literal(lit: &syn::LitFloat) -> Result<Value, EvalError> {
     }
 }
 
+/// Whether `expr` is a numeric literal without a type suffix, possibly
+/// negated or parenthesized.
+///
+/// Such a literal has no type of its own in Rust: `n - 1` with `n: u64`
+/// subtracts a `u64`. See [`adapt_untyped`].
+pub(crate) fn is_untyped_literal(expr: &syn::Expr) -> bool {
+    match expr {
+        syn::Expr::Lit(lit) => match &lit.lit {
+            syn::Lit::Int(i) => i.suffix().is_empty(),
+            syn::Lit::Float(f) => f.suffix().is_empty(),
+            _ => false,
+        },
+        syn::Expr::Paren(paren) => is_untyped_literal(&paren.expr),
+        syn::Expr::Group(group) => is_untyped_literal(&group.expr),
+        syn::Expr::Unary(unary) if matches!(unary.op, syn::UnOp::Neg(_)) => {
+            is_untyped_literal(&unary.expr)
+        }
+        _ => false,
+    }
+}
+
+/// Give the value of an untyped literal the numeric type of `like`.
+///
+/// Integer literals take any integer type and float literals take `f32`;
+/// everything else is returned unchanged.
+///
+/// # Errors
+///
+/// Returns `IntegerOverflow` if the literal does not fit the type.
+pub(crate) fn adapt_untyped(value: Value, like: &Value) -> Result<Value, EvalError> {
+    let adapts = match value {
+        Value::I64(_) => like.is_integer(),
+        Value::F64(_) => matches!(like, Value::F32(_)),
+        _ => false,
+    };
+    if adapts {
+        value.coerce_to_type_name(crate::error::type_name(like))
+    } else {
+        Ok(value)
+    }
+}
+
 fn overflow_error(span: Option<proc_macro2::Span>) -> EvalError {
     EvalError::IntegerOverflow { span }
 }
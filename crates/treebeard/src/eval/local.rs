@@ -1,18 +1,25 @@
 //! Local binding (let statement) evaluation
 
+use syn::spanned::Spanned;
+
 use crate::eval::pattern::match_pattern;
 use crate::{BindingMode, Environment, EvalContext, EvalError, Value};
 
+use super::cast::type_string;
 use super::Evaluate;
 
 /// Evaluate a local (let) binding.
 ///
-/// Supports let-else patterns where the else block must diverge.
+/// Supports let-else patterns where the else block must diverge. With a
+/// type annotation, the initializer is coerced to the annotated type, so
+/// `let x: u8 = 5;` binds a `u8`.
 ///
 /// # Errors
 ///
 /// Returns `RefutablePattern` if the pattern doesn't match and no else block.
 /// Returns `NonDivergingLetElse` if the else block doesn't diverge.
+/// Returns `IntegerOverflow` or `TypeError` if the initializer doesn't fit
+/// the annotated type.
 pub fn eval_local(
     local: &syn::Local,
     env: &mut Environment,
@@ -20,7 +27,12 @@ pub fn eval_local(
 ) -> Result<(), EvalError> {
     // Get the initializer value and diverge block
     let (value, diverge_block) = if let Some(init) = &local.init {
-        let val = init.expr.eval(env, ctx)?;
+        let mut val = init.expr.eval(env, ctx)?;
+        if let syn::Pat::Type(pat_type) = &local.pat {
+            val = val
+                .coerce_to_type_name(&type_string(&pat_type.ty))
+                .map_err(|e| e.or_span(Some(init.expr.span())))?;
+        }
         let diverge = init.diverge.as_ref().map(|(_, expr)| expr.as_ref());
         (val, diverge)
     } else {
@@ -135,8 +147,83 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_let_shadowing_changes_type_and_mutability() {
+        use crate::EnvironmentError;
+
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(
+            interp.eval_str("let x = 5; let x = \"five\"; x").unwrap(),
+            Value::string("five")
+        );
+
+        // A mutable binding shadowed by an immutable one is immutable
+        interp.eval_str("let mut y = 1; let y = 2;").unwrap();
+        assert!(matches!(
+            interp.eval_str("y = 3"),
+            Err(EvalError::Environment(
+                EnvironmentError::ImmutableBinding { .. }
+            ))
+        ));
+        assert_eq!(interp.eval_str("y").unwrap(), Value::I64(2));
+
+        // And the other way round
+        interp.eval_str("let z = 1; let mut z = z;").unwrap();
+        interp.eval_str("z = 3").unwrap();
+        assert_eq!(interp.eval_str("z").unwrap(), Value::I64(3));
+    }
+
+    #[test]
+    fn test_let_destructures_returned_tuple() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str("fn divmod(a: i64, b: i64) -> (i64, i64) { (a / b, a % b) }")
+            .unwrap();
+
+        assert_eq!(
+            interp
+                .eval_str("let (q, r) = divmod(17, 5); q * 10 + r")
+                .unwrap(),
+            Value::I64(32)
+        );
+        assert_eq!(
+            interp
+                .eval_str(
+                    "fn nested() -> (i64, (bool, char)) { let t = (true, 'c'); (1, t) } \
+                     let (n, (b, _)) = nested(); if b { n } else { 0 }"
+                )
+                .unwrap(),
+            Value::I64(1)
+        );
+        assert!(interp.eval_str("let (a, b, c) = divmod(1, 1);").is_err());
+    }
+
     // Note: Let-else tests with Option patterns are removed because
     // they require proper Option enum evaluation support which is part of Stage 1.4+.
     // The let-else syntax parsing and divergence checking is implemented,
     // but comprehensive testing requires more evaluator features to be complete.
+
+    #[test]
+    fn test_let_annotation_coerces_initializer() {
+        let mut interp = crate::Interpreter::new();
+        let result = interp
+            .eval_str("let x: u8 = 5; let y: u8 = 3; x + y")
+            .unwrap();
+        assert_eq!(result, Value::U8(8));
+
+        let err = interp.eval_str("let x: u8 = 300;").unwrap_err();
+        assert!(matches!(err, EvalError::IntegerOverflow { span: Some(_) }));
+
+        let err = interp.eval_str("let x: i32 = true;").unwrap_err();
+        assert!(matches!(err, EvalError::TypeError { .. }));
+
+        assert_eq!(
+            interp.eval_str("let x: u8 = 5; x + 1").unwrap(),
+            Value::U8(6)
+        );
+        for source in ["let x: char = 5;", "let x: String = 5;", "let x: bool = 1;"] {
+            let err = interp.eval_str(source).unwrap_err();
+            assert!(matches!(err, EvalError::TypeError { .. }), "{}", source);
+        }
+    }
 }
@@ -1,9 +1,10 @@
 //! Loop expression evaluation
 
-use super::if_expr::eval_block;
+use super::assign::{self, Place};
+use super::stmt::eval_block;
 use super::Evaluate;
 use crate::eval::control::ControlFlow;
-use crate::{Environment, EvalContext, EvalError, Value};
+use crate::{Environment, EvalContext, EvalError, IterValue, Value};
 
 // ═══════════════════════════════════════════════════════════════════════
 // loop expression
@@ -14,10 +15,8 @@ impl Evaluate for syn::ExprLoop {
         let label = self.label.as_ref().map(|l| l.name.ident.to_string());
 
         loop {
-            // Check for interruption
-            if ctx.is_interrupted() {
-                return Err(EvalError::Interrupted);
-            }
+            // Check for interruption or timeout
+            ctx.check_limits()?;
 
             // Evaluate body
             match eval_block(&self.body, env, ctx) {
@@ -53,20 +52,20 @@ impl Evaluate for syn::ExprWhile {
         let label = self.label.as_ref().map(|l| l.name.ident.to_string());
 
         loop {
-            // Check for interruption
-            if ctx.is_interrupted() {
-                return Err(EvalError::Interrupted);
-            }
-
-            // Evaluate condition
-            let cond = self.cond.eval(env, ctx)?;
-            let cond_bool = match cond {
-                Value::Bool(b) => b,
-                other => {
+            // Check for interruption or timeout
+            ctx.check_limits()?;
+
+            // Evaluate condition in a per-iteration scope, so `while let`
+            // bindings are visible to the body only
+            let mut scope = env.scope_guard();
+            let cond = self.cond.eval(&mut scope, ctx)?;
+            let cond_bool = match ctx.condition(&cond) {
+                Some(b) => b,
+                None => {
                     return Err(EvalError::TypeError {
                         message: format!(
                             "expected `bool` in while condition, found `{}`",
-                            crate::error::type_name(&other)
+                            crate::error::type_name(&cond)
                         ),
                         span: None,
                     });
@@ -79,15 +78,20 @@ impl Evaluate for syn::ExprWhile {
             }
 
             // Evaluate body
-            match eval_block(&self.body, env, ctx) {
+            match eval_block(&self.body, &mut scope, ctx) {
                 Ok(_) => {
                     // Continue looping
                 }
                 Err(EvalError::ControlFlow(cf)) => {
                     match &cf {
-                        ControlFlow::Break { .. } if cf.matches_label(label.as_deref()) => {
-                            // while loops always return unit (break value ignored)
-                            return Ok(Value::Unit);
+                        ControlFlow::Break { value, .. } if cf.matches_label(label.as_deref()) => {
+                            // The break value is ignored unless the context
+                            // opts into expression-valued `while`
+                            return Ok(if ctx.while_break_value {
+                                value.clone()
+                            } else {
+                                Value::Unit
+                            });
                         }
                         ControlFlow::Continue { .. } if cf.matches_label(label.as_deref()) => {
                             // Continue to next iteration
@@ -112,17 +116,16 @@ impl Evaluate for syn::ExprForLoop {
     fn eval(&self, env: &mut Environment, ctx: &EvalContext) -> Result<Value, EvalError> {
         let label = self.label.as_ref().map(|l| l.name.ident.to_string());
 
-        // Evaluate the iterator expression
-        let iter_value = self.expr.eval(env, ctx)?;
-
-        // Convert to an iterator
-        let iterator = value_to_iterator(iter_value)?;
+        // Loop over an iterator variable through `&mut`, or else over the
+        // value of the expression
+        let mut source = match borrowed_iterator(&self.expr, env, ctx)? {
+            Some(place) => LoopSource::Borrowed(place),
+            None => LoopSource::Owned(value_to_iterator(self.expr.eval(env, ctx)?)?),
+        };
 
-        for item in iterator {
-            // Check for interruption
-            if ctx.is_interrupted() {
-                return Err(EvalError::Interrupted);
-            }
+        while let Some(item) = source.next(env)? {
+            // Check for interruption or timeout
+            ctx.check_limits()?;
 
             // Push frame for loop body
             env.push_frame();
@@ -168,26 +171,57 @@ impl Evaluate for syn::ExprForLoop {
     }
 }
 
-/// Convert a Value to an iterator of Values.
-fn value_to_iterator(value: Value) -> Result<Box<dyn Iterator<Item = Value>>, EvalError> {
-    match value {
-        Value::Vec(elements) => Ok(Box::new(
-            elements.iter().cloned().collect::<Vec<_>>().into_iter(),
-        )),
-        Value::Array(elements) => Ok(Box::new(
-            elements.iter().cloned().collect::<Vec<_>>().into_iter(),
-        )),
-        Value::String(s) => Ok(Box::new(
-            s.chars().map(Value::Char).collect::<Vec<_>>().into_iter(),
-        )),
-        // Range values would go here if we had them
-        other => Err(EvalError::TypeError {
-            message: format!("`{}` is not an iterator", crate::error::type_name(&other)),
-            span: None,
-        }),
+/// Where a `for` loop takes its items from.
+enum LoopSource {
+    /// A copy of the looped-over value, as an iterator
+    Owned(IterValue),
+    /// An iterator variable borrowed with `&mut`, advanced in place
+    Borrowed(Place),
+}
+
+impl LoopSource {
+    fn next(&mut self, env: &mut Environment) -> Result<Option<Value>, EvalError> {
+        match self {
+            LoopSource::Owned(iter) => Ok(iter.next()),
+            LoopSource::Borrowed(place) => {
+                match assign::modify_place(place, env, super::call::advance_iter)? {
+                    Value::Option(item) => Ok(item.as_ref().clone()),
+                    _ => Ok(None),
+                }
+            }
+        }
     }
 }
 
+/// The place of an iterator variable borrowed as `&mut it`, so the loop
+/// leaves it advanced, or `None` for any other expression.
+fn borrowed_iterator(
+    expr: &syn::Expr,
+    env: &mut Environment,
+    ctx: &EvalContext,
+) -> Result<Option<Place>, EvalError> {
+    let syn::Expr::Reference(reference) = expr else {
+        return Ok(None);
+    };
+    if reference.mutability.is_none() || !matches!(&*reference.expr, syn::Expr::Path(_)) {
+        return Ok(None);
+    }
+    let place = assign::resolve_place(&reference.expr, env, ctx)?;
+    let is_iter = matches!(assign::read_place(&place, env), Ok(Value::Iter(_)));
+    Ok(is_iter.then_some(place))
+}
+
+/// Convert a Value to an iterator of Values.
+///
+/// This is the same conversion `.iter()` uses, so looping over an
+/// iterator value starts from its current position.
+fn value_to_iterator(value: Value) -> Result<IterValue, EvalError> {
+    IterValue::from_value(&value).ok_or_else(|| EvalError::TypeError {
+        message: format!("`{}` is not an iterator", crate::error::type_name(&value)),
+        span: None,
+    })
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 // break expression
 // ═══════════════════════════════════════════════════════════════════════
@@ -221,7 +255,7 @@ impl Evaluate for syn::ExprContinue {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::eval::if_expr::eval_block;
+    use crate::eval::stmt::eval_block;
 
     #[test]
     fn test_loop_with_break() {
@@ -233,6 +267,47 @@ mod tests {
         assert_eq!(result, Value::I64(42));
     }
 
+    #[test]
+    fn test_infinite_loop_times_out() {
+        let expr: syn::Expr = syn::parse_str("loop {}").unwrap();
+        let mut env = Environment::new();
+        let ctx = EvalContext::with_timeout(std::time::Duration::from_millis(50));
+
+        let result = expr.eval(&mut env, &ctx);
+        assert!(matches!(result, Err(EvalError::Timeout)));
+    }
+
+    #[test]
+    fn test_infinite_loop_hits_step_limit() {
+        let expr: syn::Expr = syn::parse_str("loop {}").unwrap();
+        let mut env = Environment::new();
+        let ctx = EvalContext::with_step_limit(100);
+
+        let result = expr.eval(&mut env, &ctx);
+        assert!(matches!(
+            result,
+            Err(EvalError::StepLimitExceeded { limit: 100 })
+        ));
+        assert_eq!(ctx.steps_remaining(), Some(0));
+    }
+
+    #[test]
+    fn test_finite_program_under_step_limit() {
+        let expr: syn::Expr = syn::parse_str("1 + 2 * 3").unwrap();
+        let mut env = Environment::new();
+
+        // Five expressions: exactly five steps
+        let ctx = EvalContext::with_step_limit(5);
+        assert_eq!(expr.eval(&mut env, &ctx).unwrap(), Value::I64(7));
+        assert_eq!(ctx.steps_remaining(), Some(0));
+
+        let ctx = EvalContext::with_step_limit(4);
+        assert!(matches!(
+            expr.eval(&mut env, &ctx),
+            Err(EvalError::StepLimitExceeded { .. })
+        ));
+    }
+
     #[test]
     fn test_while_false_never_executes() {
         let expr: syn::Expr = syn::parse_str("while false { 42 }").unwrap();
@@ -282,6 +357,16 @@ mod tests {
         assert!(matches!(result.unwrap_err(), EvalError::TypeError { .. }));
     }
 
+    #[test]
+    fn test_while_truthy_condition() {
+        let mut interp = crate::Interpreter::new();
+        interp.context_mut().truthy_conditions = true;
+        let result = interp
+            .eval_str("let mut n = 3; let mut steps = 0; while n { n -= 1; steps += 1; } steps")
+            .unwrap();
+        assert_eq!(result, Value::I64(3));
+    }
+
     #[test]
     fn test_loop_with_continue() {
         // This is tricky to test without let bindings, but we can test
@@ -438,4 +523,57 @@ mod tests {
         assert_eq!(iter.next(), Some(Value::Char('b')));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn test_while_let() {
+        let mut interp = crate::Interpreter::new();
+        let result = interp
+            .eval_str(
+                "let mut n = Some(3); let mut total = 0; \
+                 while let Some(k) = n { total += k; n = if k > 1 { Some(k - 1) } else { None }; } \
+                 total",
+            )
+            .unwrap();
+        assert_eq!(result, Value::I64(6));
+    }
+
+    #[test]
+    fn test_while_break_value() {
+        let expr: syn::Expr = syn::parse_str("while true { break 7; }").unwrap();
+        let mut env = Environment::new();
+
+        let ctx = EvalContext::default();
+        assert_eq!(expr.eval(&mut env, &ctx).unwrap(), Value::Unit);
+
+        let mut ctx = EvalContext::default();
+        ctx.while_break_value = true;
+        assert_eq!(expr.eval(&mut env, &ctx).unwrap(), Value::I64(7));
+
+        // A loop that ends by its condition is still `()`
+        let expr: syn::Expr = syn::parse_str("while false { break 7; }").unwrap();
+        assert_eq!(expr.eval(&mut env, &ctx).unwrap(), Value::Unit);
+    }
+
+    #[test]
+    fn test_loop_bodies_do_not_leak_bindings() {
+        let mut interp = crate::Interpreter::new();
+        let before = interp.env().len();
+
+        interp.eval_str("for i in 0..3 { let t = i * 2; }").unwrap();
+        interp
+            .eval_str("let mut n = 0; while n < 3 { let t = n; n += 1; }")
+            .unwrap();
+        interp
+            .eval_str("loop { let t = n; n += 1; if t > 5 { break; } }")
+            .unwrap();
+        assert!(!interp.env().contains("t"));
+        assert!(!interp.env().contains("i"));
+        assert_eq!(interp.env().len(), before + 1);
+
+        // Bindings do not pile up across iterations
+        interp
+            .eval_str("for i in 0..100000 { let t = i; let u = t; }")
+            .unwrap();
+        assert_eq!(interp.env().len(), before + 1);
+    }
 }
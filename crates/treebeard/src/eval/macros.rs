@@ -0,0 +1,777 @@
+//! Built-in macro evaluation
+//!
+//! Macros from the standard library that interpreted code commonly uses
+//! are evaluated directly rather than expanded. User-defined macros are
+//! expanded before evaluation (see [`crate::expansion`]), so any macro
+//! that reaches this point and is not built in is unsupported.
+
+use quote::ToTokens;
+use syn::parse::{ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+
+use std::sync::Arc;
+
+use crate::{Environment, EvalContext, EvalError, Value};
+
+use super::assign;
+use super::pattern::{apply_bindings, match_pattern};
+use super::Evaluate;
+
+/// Evaluate an invocation of a built-in macro.
+///
+/// # Errors
+///
+/// Returns `Panic` for `panic!`, `unreachable!` and failed assertions,
+/// `NotImplementedRuntime` for `todo!` and `unimplemented!`, `TypeError`
+/// for a `write!` destination that is not a `String` place, `ParseError`
+/// for malformed arguments, and `UnsupportedExpr` for unknown macros.
+pub fn eval_macro(
+    mac: &syn::Macro,
+    env: &mut Environment,
+    ctx: &EvalContext,
+) -> Result<Value, EvalError> {
+    let name = mac
+        .path
+        .get_ident()
+        .map(|ident| ident.to_string())
+        .unwrap_or_default();
+
+    match name.as_str() {
+        "assert" => eval_assert(mac, env, ctx),
+        "assert_eq" => eval_assert_cmp(mac, true, env, ctx),
+        "assert_ne" => eval_assert_cmp(mac, false, env, ctx),
+        // Debug assertions are skipped entirely unless enabled
+        "debug_assert" | "debug_assert_eq" | "debug_assert_ne" if !ctx.debug_assertions => {
+            Ok(Value::Unit)
+        }
+        "debug_assert" => eval_assert(mac, env, ctx),
+        "debug_assert_eq" => eval_assert_cmp(mac, true, env, ctx),
+        "debug_assert_ne" => eval_assert_cmp(mac, false, env, ctx),
+        "matches" => eval_matches(mac, env, ctx),
+        "dbg" => eval_dbg(mac, env, ctx),
+        "format" => Ok(Value::string(format_args(&parse_args(mac)?, env, ctx)?)),
+        "write" => eval_write(mac, &name, false, env, ctx),
+        "writeln" => eval_write(mac, &name, true, env, ctx),
+        "panic" => {
+            let message = match parse_args(mac)?.as_slice() {
+                [] => "explicit panic".to_string(),
+                args => format_args(args, env, ctx)?,
+            };
+            Err(panic_error(mac, message))
+        }
+        "unreachable" => {
+            let message = match parse_args(mac)?.as_slice() {
+                [] => "internal error: entered unreachable code".to_string(),
+                args => format!(
+                    "internal error: entered unreachable code: {}",
+                    format_args(args, env, ctx)?
+                ),
+            };
+            Err(panic_error(mac, message))
+        }
+        "todo" | "unimplemented" => {
+            let prefix = if name == "todo" {
+                "not yet implemented"
+            } else {
+                "not implemented"
+            };
+            let message = match parse_args(mac)?.as_slice() {
+                [] => prefix.to_string(),
+                args => format!("{}: {}", prefix, format_args(args, env, ctx)?),
+            };
+            Err(EvalError::NotImplementedRuntime {
+                message,
+                span: Some(mac.path.span()),
+            })
+        }
+        _ => Err(EvalError::UnsupportedExpr {
+            kind: format!("macro invocation `{}!`", mac.path.to_token_stream()),
+            span: Some(mac.path.span()),
+        }),
+    }
+}
+
+/// `assert!(cond)` or `assert!(cond, "format", args...)`.
+fn eval_assert(
+    mac: &syn::Macro,
+    env: &mut Environment,
+    ctx: &EvalContext,
+) -> Result<Value, EvalError> {
+    let args = parse_args(mac)?;
+    let Some(cond) = args.first() else {
+        return Err(arity_error(mac, "assert", 1, 0));
+    };
+
+    match cond.eval(env, ctx)? {
+        Value::Bool(true) => Ok(Value::Unit),
+        Value::Bool(false) => {
+            let message = match &args[1..] {
+                [] => format!("assertion failed: {}", cond.to_token_stream()),
+                rest => format_args(rest, env, ctx)?,
+            };
+            Err(panic_error(mac, message))
+        }
+        other => Err(EvalError::TypeError {
+            message: format!(
+                "expected `bool` in assert!, found `{}`",
+                crate::error::type_name(&other)
+            ),
+            span: Some(cond.span()),
+        }),
+    }
+}
+
+/// `assert_eq!(a, b, ...)` and `assert_ne!(a, b, ...)`.
+fn eval_assert_cmp(
+    mac: &syn::Macro,
+    expect_equal: bool,
+    env: &mut Environment,
+    ctx: &EvalContext,
+) -> Result<Value, EvalError> {
+    let name = if expect_equal {
+        "assert_eq"
+    } else {
+        "assert_ne"
+    };
+    let args = parse_args(mac)?;
+    if args.len() < 2 {
+        return Err(arity_error(mac, name, 2, args.len()));
+    }
+
+    let left = args[0].eval(env, ctx)?;
+    let right = args[1].eval(env, ctx)?;
+    if (left == right) == expect_equal {
+        return Ok(Value::Unit);
+    }
+
+    let op = if expect_equal { "==" } else { "!=" };
+    let detail = match &args[2..] {
+        [] => String::new(),
+        rest => format!(": {}", format_args(rest, env, ctx)?),
+    };
+    let message = format!(
+        "assertion `left {} right` failed{}\n  left: {:?}\n right: {:?}",
+        op, detail, left, right
+    );
+    Err(panic_error(mac, message))
+}
+
+/// `matches!(value, pattern)` or `matches!(value, pattern if guard)`.
+fn eval_matches(
+    mac: &syn::Macro,
+    env: &mut Environment,
+    ctx: &EvalContext,
+) -> Result<Value, EvalError> {
+    let (scrutinee, pat, guard) = mac.parse_body_with(|input: ParseStream| {
+        let scrutinee: syn::Expr = input.parse()?;
+        input.parse::<syn::Token![,]>()?;
+        let pat = syn::Pat::parse_multi_with_leading_vert(input)?;
+        let guard = if input.parse::<Option<syn::Token![if]>>()?.is_some() {
+            Some(input.parse::<syn::Expr>()?)
+        } else {
+            None
+        };
+        input.parse::<Option<syn::Token![,]>>()?;
+        Ok((scrutinee, pat, guard))
+    })?;
+
+    let value = scrutinee.eval(env, ctx)?;
+    let Some(bindings) = match_pattern(&pat, &value, None)? else {
+        return Ok(Value::Bool(false));
+    };
+    let Some(guard) = guard else {
+        return Ok(Value::Bool(true));
+    };
+
+    let mut scope = env.scope_guard();
+    apply_bindings(&mut scope, bindings);
+    match guard.eval(&mut scope, ctx)? {
+        Value::Bool(b) => Ok(Value::Bool(b)),
+        other => Err(EvalError::TypeError {
+            message: format!(
+                "expected `bool` in matches! guard, found `{}`",
+                crate::error::type_name(&other)
+            ),
+            span: Some(guard.span()),
+        }),
+    }
+}
+
+/// `dbg!(expr, ...)`: print each expression and its value to stderr.
+///
+/// Like std's `dbg!`, returns the value of a single argument unchanged,
+/// a tuple of the values of several, and `()` for none.
+fn eval_dbg(
+    mac: &syn::Macro,
+    env: &mut Environment,
+    ctx: &EvalContext,
+) -> Result<Value, EvalError> {
+    let args = parse_args(mac)?;
+    let location = source_location(mac.path.span());
+
+    if args.is_empty() {
+        eprintln!("[{}]", location);
+        return Ok(Value::Unit);
+    }
+
+    let mut values = Vec::with_capacity(args.len());
+    for arg in &args {
+        let value = arg.eval(env, ctx)?;
+        eprintln!("{}", dbg_line(&location, arg, &value));
+        values.push(value);
+    }
+
+    Ok(match values.len() {
+        1 => values.remove(0),
+        _ => Value::tuple(values),
+    })
+}
+
+/// `write!(dest, "format", args...)` and `writeln!`: append formatted text
+/// to a `String` place, as std does through `fmt::Write`.
+///
+/// The destination may be written `&mut s` or `s`. Returns `Ok(())`.
+fn eval_write(
+    mac: &syn::Macro,
+    name: &str,
+    newline: bool,
+    env: &mut Environment,
+    ctx: &EvalContext,
+) -> Result<Value, EvalError> {
+    let args = parse_args(mac)?;
+    let Some((dest, rest)) = args.split_first() else {
+        return Err(arity_error(mac, name, 1, 0));
+    };
+
+    let mut text = match rest {
+        [] => String::new(),
+        rest => format_args(rest, env, ctx)?,
+    };
+    if newline {
+        text.push('\n');
+    }
+
+    let target = match dest {
+        syn::Expr::Reference(reference) if reference.mutability.is_some() => &reference.expr,
+        dest => dest,
+    };
+    if !assign::is_place(target) {
+        return Err(EvalError::TypeError {
+            message: format!(
+                "`{}!` destination must be a `String` variable or field",
+                name
+            ),
+            span: Some(dest.span()),
+        });
+    }
+
+    let place = assign::resolve_place(target, env, ctx)?;
+    assign::modify_place(&place, env, |slot| match slot {
+        Value::String(s) => {
+            Arc::make_mut(s).push_str(&text);
+            Ok(())
+        }
+        other => Err(EvalError::TypeError {
+            message: format!(
+                "`{}!` expects a `String` destination, got {}",
+                name,
+                crate::error::type_name(other)
+            ),
+            span: None,
+        }),
+    })
+    .map_err(|e| e.or_span(Some(dest.span())))?;
+
+    Ok(Value::ok(Value::Unit))
+}
+
+/// Format one `dbg!` line: `[location] expr = value`.
+fn dbg_line(location: &str, expr: &syn::Expr, value: &Value) -> String {
+    format!("[{}] {} = {:?}", location, expr.to_token_stream(), value)
+}
+
+/// Describe where `span` starts, as `<eval>:line:column`.
+fn source_location(span: proc_macro2::Span) -> String {
+    let start = span.start();
+    format!("<eval>:{}:{}", start.line, start.column + 1)
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Formatting
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Render `format!`-style arguments: a string literal followed by values.
+///
+/// Supports `{}`, `{:?}`, positional `{0}`, inline names such as `{x}`,
+/// and `{{`/`}}` escapes. Specs may set fill, alignment, sign, zero
+/// padding, width and precision, as in `{:>8}`, `{:+.2}` or `{:05}`.
+///
+/// # Errors
+///
+/// Returns `TypeError` if the first argument is not a string literal,
+/// placeholders and arguments do not line up, or a spec is malformed, and
+/// errors from evaluating the arguments.
+pub(crate) fn format_args(
+    args: &[syn::Expr],
+    env: &mut Environment,
+    ctx: &EvalContext,
+) -> Result<String, EvalError> {
+    let Some(syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Str(template),
+        ..
+    })) = args.first()
+    else {
+        return Err(EvalError::TypeError {
+            message: "format argument must be a string literal".to_string(),
+            span: args.first().map(|arg| arg.span()),
+        });
+    };
+    let span = Some(template.span());
+
+    let values = args[1..]
+        .iter()
+        .map(|arg| arg.eval(env, ctx))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let template = template.value();
+    let mut out = String::new();
+    let mut next = 0;
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                out.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                out.push('}');
+            }
+            '{' => {
+                let spec: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                let (arg, format) = spec.split_once(':').unwrap_or((spec.as_str(), ""));
+                let Some(format) = FormatSpec::parse(format) else {
+                    return Err(EvalError::TypeError {
+                        message: format!("invalid format spec `{{{}}}`", spec),
+                        span,
+                    });
+                };
+
+                let value = if arg.is_empty() {
+                    next += 1;
+                    values.get(next - 1).cloned()
+                } else if let Ok(index) = arg.parse::<usize>() {
+                    values.get(index).cloned()
+                } else {
+                    env.get(arg).cloned()
+                };
+                let Some(value) = value else {
+                    return Err(EvalError::TypeError {
+                        message: format!("no argument for `{{{}}}` in format string", spec),
+                        span,
+                    });
+                };
+
+                out.push_str(&format.render(&value));
+            }
+            c => out.push(c),
+        }
+    }
+
+    Ok(out)
+}
+
+/// A parsed format spec, the part of a placeholder after the `:`.
+///
+/// Follows std's `[[fill]align][sign]['#']['0'][width]['.' precision]['?']`.
+/// Width and precision must be literal numbers.
+#[derive(Debug, Default, PartialEq)]
+struct FormatSpec {
+    fill: Option<char>,
+    align: Option<char>,
+    plus: bool,
+    alternate: bool,
+    zero: bool,
+    width: usize,
+    precision: Option<usize>,
+    debug: bool,
+}
+
+impl FormatSpec {
+    /// Parse a spec, or return `None` if it is malformed.
+    fn parse(spec: &str) -> Option<Self> {
+        let mut format = FormatSpec::default();
+        let mut rest = spec;
+
+        let mut chars = rest.chars();
+        match (chars.next(), chars.next()) {
+            (Some(fill), Some(align @ ('<' | '^' | '>'))) => {
+                format.fill = Some(fill);
+                format.align = Some(align);
+                rest = chars.as_str();
+            }
+            (Some(align @ ('<' | '^' | '>')), _) => {
+                format.align = Some(align);
+                rest = &rest[1..];
+            }
+            _ => {}
+        }
+
+        if let Some(r) = rest.strip_prefix('+') {
+            format.plus = true;
+            rest = r;
+        }
+        if let Some(r) = rest.strip_prefix('#') {
+            format.alternate = true;
+            rest = r;
+        }
+        if let Some(r) = rest.strip_prefix('0') {
+            format.zero = true;
+            rest = r;
+        }
+
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        if digits > 0 {
+            format.width = rest[..digits].parse().ok()?;
+            rest = &rest[digits..];
+        }
+
+        if let Some(r) = rest.strip_prefix('.') {
+            let digits = r.len() - r.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            format.precision = Some(r[..digits].parse().ok()?);
+            rest = &r[digits..];
+        }
+
+        match rest {
+            "" => {}
+            "?" => format.debug = true,
+            _ => return None,
+        }
+        Some(format)
+    }
+
+    /// Render `value` according to this spec.
+    fn render(&self, value: &Value) -> String {
+        let numeric = value.is_numeric();
+
+        let mut body = match (value, self.precision) {
+            (Value::F32(x), Some(p)) => format!("{:.*}", p, x),
+            (Value::F64(x), Some(p)) => format!("{:.*}", p, x),
+            _ if self.debug && self.alternate => format!("{:#?}", value),
+            _ if self.debug => format!("{:?}", value),
+            (Value::String(s), Some(p)) => s.chars().take(p).collect(),
+            _ => value.to_string(),
+        };
+        if self.plus && numeric && !body.starts_with('-') {
+            body.insert(0, '+');
+        }
+
+        let len = body.chars().count();
+        if len >= self.width {
+            return body;
+        }
+        let padding = self.width - len;
+
+        // Zero padding goes between the sign and the digits
+        if self.zero && numeric {
+            let sign_len = usize::from(body.starts_with(['+', '-']));
+            body.insert_str(sign_len, &"0".repeat(padding));
+            return body;
+        }
+
+        let fill = self.fill.unwrap_or(' ');
+        let pad = |n: usize| fill.to_string().repeat(n);
+        match self.align.unwrap_or(if numeric { '>' } else { '<' }) {
+            '>' => pad(padding) + &body,
+            '^' => pad(padding / 2) + &body + &pad(padding - padding / 2),
+            _ => body + &pad(padding),
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Helpers
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Parse a macro body as comma-separated expressions.
+fn parse_args(mac: &syn::Macro) -> Result<Vec<syn::Expr>, EvalError> {
+    let args =
+        Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated.parse2(mac.tokens.clone())?;
+    Ok(args.into_iter().collect())
+}
+
+fn panic_error(mac: &syn::Macro, message: String) -> EvalError {
+    EvalError::Panic {
+        message,
+        span: Some(mac.path.span()),
+    }
+}
+
+fn arity_error(mac: &syn::Macro, name: &str, expected: usize, got: usize) -> EvalError {
+    EvalError::ArityMismatch {
+        expected,
+        got,
+        name: format!("{}!", name),
+        span: Some(mac.path.span()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Interpreter;
+
+    #[test]
+    fn test_matches() {
+        let mut interp = Interpreter::new();
+        assert_eq!(
+            interp.eval_str("matches!(Some(3), Some(_))").unwrap(),
+            Value::Bool(true)
+        );
+        assert_eq!(
+            interp.eval_str("matches!(None, Some(_))").unwrap(),
+            Value::Bool(false)
+        );
+        assert_eq!(
+            interp.eval_str("matches!(5, 1..=3 | 5)").unwrap(),
+            Value::Bool(true)
+        );
+        assert_eq!(
+            interp
+                .eval_str("matches!(Some(3), Some(n) if n > 4)")
+                .unwrap(),
+            Value::Bool(false)
+        );
+    }
+
+    #[test]
+    fn test_assert_eq() {
+        let mut interp = Interpreter::new();
+        assert_eq!(
+            interp.eval_str("assert_eq!(1 + 1, 2);").unwrap(),
+            Value::Unit
+        );
+
+        let Err(EvalError::Panic { message, .. }) = interp.eval_str("assert_eq!(1, 2)") else {
+            panic!("expected assertion failure");
+        };
+        assert!(message.contains("left: 1"));
+        assert!(message.contains("right: 2"));
+
+        assert!(interp.eval_str("assert_ne!(1, 2)").is_ok());
+    }
+
+    #[test]
+    fn test_debug_assertions() {
+        let mut interp = Interpreter::new();
+        assert_eq!(
+            interp.eval_str("debug_assert!(false)").unwrap(),
+            Value::Unit
+        );
+        assert_eq!(
+            interp.eval_str("debug_assert_eq!(1, 2)").unwrap(),
+            Value::Unit
+        );
+        // Arguments aren't evaluated when disabled
+        assert!(interp.eval_str("debug_assert!(undefined)").is_ok());
+
+        interp.context_mut().debug_assertions = true;
+        assert!(interp.eval_str("debug_assert!(true)").is_ok());
+        let err = interp.eval_str("debug_assert!(false)").unwrap_err();
+        assert!(err.is_panic());
+        let err = interp.eval_str("debug_assert_ne!(1, 1)").unwrap_err();
+        assert!(err.is_panic());
+    }
+
+    #[test]
+    fn test_assert_ne_fails_on_equal() {
+        let mut interp = Interpreter::new();
+        let Err(EvalError::Panic { message, .. }) = interp.eval_str("assert_ne!(1, 1)") else {
+            panic!("expected assertion failure");
+        };
+        assert!(message.contains("left != right"));
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let mut interp = Interpreter::new();
+        let Err(EvalError::Panic { message, .. }) = interp.eval_str("unreachable!()") else {
+            panic!("expected a panic");
+        };
+        assert_eq!(message, "internal error: entered unreachable code");
+
+        let Err(EvalError::Panic { message, .. }) =
+            interp.eval_str("unreachable!(\"state {}\", 3)")
+        else {
+            panic!("expected a panic");
+        };
+        assert_eq!(message, "internal error: entered unreachable code: state 3");
+    }
+
+    #[test]
+    fn test_todo_and_unimplemented() {
+        let mut interp = Interpreter::new();
+        let Err(EvalError::NotImplementedRuntime { message, span }) =
+            interp.eval_str("todo!(\"later\")")
+        else {
+            panic!("expected a stub error");
+        };
+        assert_eq!(message, "not yet implemented: later");
+        assert!(span.is_some());
+
+        let err = interp
+            .eval_str("fn f(n: i64) -> i64 { unimplemented!(\"f({})\", n) } f(2)")
+            .unwrap_err();
+        assert!(!err.is_panic());
+        assert!(matches!(
+            err.inner(),
+            EvalError::NotImplementedRuntime { message, .. } if message == "not implemented: f(2)"
+        ));
+
+        let err = interp.eval_str("todo!()").unwrap_err();
+        assert_eq!(err.to_string(), "not yet implemented");
+    }
+
+    #[test]
+    fn test_assert_with_message() {
+        let mut interp = Interpreter::new();
+        assert!(interp.eval_str("assert!(true)").is_ok());
+
+        let Err(EvalError::Panic { message, .. }) =
+            interp.eval_str("let x = 7; assert!(x < 5, \"x was {} ({x:?})\", x);")
+        else {
+            panic!("expected assertion failure");
+        };
+        assert_eq!(message, "x was 7 (7)");
+
+        let Err(EvalError::Panic { message, .. }) = interp.eval_str("assert!(1 > 2)") else {
+            panic!("expected assertion failure");
+        };
+        assert_eq!(message, "assertion failed: 1 > 2");
+    }
+
+    #[test]
+    fn test_dbg_returns_value() {
+        let mut interp = Interpreter::new();
+        assert_eq!(interp.eval_str("dbg!(2 + 3)").unwrap(), Value::I64(5));
+        assert_eq!(interp.eval_str("dbg!()").unwrap(), Value::Unit);
+        assert_eq!(
+            interp.eval_str("dbg!(1, true)").unwrap(),
+            Value::tuple(vec![Value::I64(1), Value::Bool(true)])
+        );
+    }
+
+    #[test]
+    fn test_dbg_line() {
+        let mac: syn::Macro = syn::parse_str("dbg!(2 + 3)").unwrap();
+        let expr: syn::Expr = syn::parse_str("2 + 3").unwrap();
+        let line = dbg_line(&source_location(mac.path.span()), &expr, &Value::I64(5));
+        assert_eq!(line, "[<eval>:1:1] 2 + 3 = 5");
+    }
+
+    #[test]
+    fn test_format_width_and_precision() {
+        let mut interp = Interpreter::new();
+        let cases = [
+            (r#"format!("{:.2}", 3.14159)"#, "3.14"),
+            (r#"format!("{:>5}", 42)"#, "   42"),
+            (r#"format!("{:5}", 42)"#, "   42"),
+            (r#"format!("{:5}|", "ab")"#, "ab   |"),
+            (r#"format!("{:<5}|", 42)"#, "42   |"),
+            (r#"format!("{:^6}", "ab")"#, "  ab  "),
+            (r#"format!("{:*>4}", 7)"#, "***7"),
+            (r#"format!("{:05}", 42)"#, "00042"),
+            (r#"format!("{:05}", -42)"#, "-0042"),
+            (r#"format!("{:+}", 3)"#, "+3"),
+            (r#"format!("{:+08.3}", 2.5)"#, "+002.500"),
+            (r#"format!("{:.1}", "hello")"#, "h"),
+            (r#"format!("{:>6?}", "a")"#, "   \"a\""),
+            (r#"format!("{0:>3}{0:<3}", 1)"#, "  11  "),
+        ];
+        for (src, expected) in cases {
+            assert_eq!(
+                interp.eval_str(src).unwrap(),
+                Value::string(expected),
+                "{}",
+                src
+            );
+        }
+
+        assert!(interp.eval_str(r#"format!("{:x}", 1)"#).is_err());
+    }
+
+    #[test]
+    fn test_format_debug_and_display() {
+        let mut interp = Interpreter::new();
+        assert_eq!(
+            interp.eval_str(r#"format!("{:?}", "hi")"#).unwrap(),
+            Value::string("\"hi\"")
+        );
+        assert_eq!(
+            interp.eval_str(r#"format!("{}", "hi")"#).unwrap(),
+            Value::string("hi")
+        );
+        assert_eq!(
+            interp.eval_str(r#"format!("{:?}", Some(1))"#).unwrap(),
+            Value::string("Some(1)")
+        );
+        assert_eq!(
+            interp.eval_str(r#"format!("{:?} {}", 'c', 'c')"#).unwrap(),
+            Value::string("'c' c")
+        );
+        assert_eq!(
+            interp
+                .eval_str(r#"struct P { x: i64 } format!("{:?}", P { x: 1 })"#)
+                .unwrap(),
+            Value::string("P { x: 1 }")
+        );
+    }
+
+    #[test]
+    fn test_write_appends_to_string() {
+        let mut interp = Interpreter::new();
+        let result = interp
+            .eval_str(
+                r#"let mut s = String::new();
+                   writeln!(&mut s, "a = {}", 1);
+                   let r = writeln!(s, "b = {}", 2);
+                   write!(&mut s, "end").unwrap();
+                   (s, r)"#,
+            )
+            .unwrap();
+        assert_eq!(
+            result,
+            Value::tuple(vec![
+                Value::string("a = 1\nb = 2\nend"),
+                Value::ok(Value::Unit)
+            ])
+        );
+
+        interp.eval_str("let n = 1;").unwrap();
+        assert!(interp.eval_str(r#"write!(&mut n, "x")"#).is_err());
+        assert!(interp.eval_str(r#"write!(&mut 1, "x")"#).is_err());
+    }
+
+    #[test]
+    fn test_panic() {
+        let mut interp = Interpreter::new();
+        let err = interp.eval_str(r#"panic!("bad {}", 42)"#).unwrap_err();
+        let EvalError::Panic { message, .. } = &err else {
+            panic!("expected a panic, got {:?}", err);
+        };
+        assert_eq!(message, "bad 42");
+
+        let err = interp.eval_str("fn f() { panic!() } f()").unwrap_err();
+        assert!(err.is_panic());
+        assert_eq!(err.to_string(), "panicked: explicit panic");
+    }
+
+    #[test]
+    fn test_unknown_macro() {
+        let mut interp = Interpreter::new();
+        let result = interp.eval_str("vec![1]");
+        assert!(matches!(result, Err(EvalError::UnsupportedExpr { .. })));
+    }
+}
@@ -1,5 +1,7 @@
 //! Match expression evaluation
 
+use syn::spanned::Spanned;
+
 use super::pattern::{apply_bindings, match_pattern};
 use super::Evaluate;
 use crate::{Environment, EvalContext, EvalError, Value};
@@ -9,6 +11,18 @@ impl Evaluate for syn::ExprMatch {
         // Evaluate the scrutinee
         let scrutinee = self.expr.eval(env, ctx)?;
 
+        // If enabled, registered enums are checked up front, before any arm
+        // runs, including when matched through a shared reference
+        let inspected = match &scrutinee {
+            Value::Ref(r) => &*r.value,
+            value => value,
+        };
+        if let (true, Value::Enum(e)) = (ctx.exhaustive_enum_matches, inspected) {
+            if let Some(variants) = env.enum_variants(&e.type_name) {
+                check_enum_exhaustive(&e.type_name, variants, &self.arms, self.span())?;
+            }
+        }
+
         // Try each arm
         for arm in &self.arms {
             // Check if pattern matches
@@ -29,7 +43,7 @@ impl Evaluate for syn::ExprMatch {
                                     "expected `bool` in match guard, found `{}`",
                                     crate::error::type_name(&other)
                                 ),
-                                span: None,
+                                span: Some(guard.span()),
                             });
                         }
                     }
@@ -56,6 +70,83 @@ impl Evaluate for syn::ExprMatch {
     }
 }
 
+/// Verify that `arms` cover every variant of a registered enum.
+///
+/// Guarded arms never count toward coverage. Sub-patterns are not
+/// inspected: an arm naming a variant covers it.
+fn check_enum_exhaustive(
+    type_name: &str,
+    variants: &[String],
+    arms: &[syn::Arm],
+    span: proc_macro2::Span,
+) -> Result<(), EvalError> {
+    let mut covered = Vec::new();
+    for arm in arms.iter().filter(|arm| arm.guard.is_none()) {
+        if collect_covered_variants(&arm.pat, type_name, &mut covered) {
+            return Ok(());
+        }
+    }
+
+    let missing: Vec<String> = variants
+        .iter()
+        .map(|v| format!("{}::{}", type_name, v))
+        .filter(|v| !covered.contains(v))
+        .collect();
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(EvalError::NonExhaustiveMatch {
+            value: missing.join("`, `"),
+            span: Some(span),
+        })
+    }
+}
+
+/// Record the variants named by `pat` as `Type::Variant` paths, reading
+/// a bare variant name as one of `type_name`. Returns `true` if it matches
+/// anything.
+fn collect_covered_variants(pat: &syn::Pat, type_name: &str, covered: &mut Vec<String>) -> bool {
+    let variant_path = |path: &syn::Path| {
+        let mut idents = path.segments.iter().rev().map(|s| s.ident.to_string());
+        let variant = idents.next()?;
+        let ty = idents.next().unwrap_or_else(|| type_name.to_string());
+        Some(format!("{}::{}", ty, variant))
+    };
+
+    match pat {
+        syn::Pat::Wild(_) => true,
+        syn::Pat::Ident(pat_ident) => match &pat_ident.subpat {
+            Some((_, sub)) => collect_covered_variants(sub, type_name, covered),
+            None => true,
+        },
+        syn::Pat::Path(pat_path) => {
+            covered.extend(variant_path(&pat_path.path));
+            false
+        }
+        syn::Pat::TupleStruct(pat_ts) => {
+            covered.extend(variant_path(&pat_ts.path));
+            false
+        }
+        syn::Pat::Struct(pat_struct) => {
+            covered.extend(variant_path(&pat_struct.path));
+            false
+        }
+        syn::Pat::Or(pat_or) => {
+            // Visit every case so all named variants are recorded
+            let mut catch_all = false;
+            for case in &pat_or.cases {
+                catch_all |= collect_covered_variants(case, type_name, covered);
+            }
+            catch_all
+        }
+        syn::Pat::Paren(pat) => collect_covered_variants(&pat.pat, type_name, covered),
+        syn::Pat::Reference(pat_ref) => collect_covered_variants(&pat_ref.pat, type_name, covered),
+        syn::Pat::Type(pat_type) => collect_covered_variants(&pat_type.pat, type_name, covered),
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -143,6 +234,40 @@ mod tests {
         assert_eq!(result, Value::I64(0));
     }
 
+    #[test]
+    fn test_match_guard_even_odd() {
+        let expr: syn::ExprMatch = syn::parse_quote! {
+            match 4 {
+                n if n % 2 == 0 => "even",
+                _ => "odd",
+            }
+        };
+
+        let mut env = Environment::new();
+        let ctx = EvalContext::default();
+        let result = expr.eval(&mut env, &ctx).unwrap();
+
+        assert_eq!(result, Value::string("even"));
+    }
+
+    #[test]
+    fn test_match_guard_falls_through_to_next_arm() {
+        let expr: syn::ExprMatch = syn::parse_quote! {
+            match 3 {
+                n if n % 2 == 0 => "even",
+                _ => "odd",
+            }
+        };
+
+        let mut env = Environment::new();
+        let ctx = EvalContext::default();
+        let result = expr.eval(&mut env, &ctx).unwrap();
+
+        assert_eq!(result, Value::string("odd"));
+        // Guard bindings must not leak out of the match
+        assert!(env.get("n").is_none());
+    }
+
     #[test]
     fn test_match_guard_non_bool() {
         let expr: syn::ExprMatch = syn::parse_quote! {
@@ -158,8 +283,9 @@ mod tests {
 
         assert!(result.is_err());
         match result.unwrap_err() {
-            EvalError::TypeError { message, .. } => {
+            EvalError::TypeError { message, span } => {
                 assert!(message.contains("expected `bool` in match guard"));
+                assert!(span.is_some());
             }
             _ => panic!("Expected TypeError"),
         }
@@ -199,4 +325,169 @@ mod tests {
 
         assert_eq!(result, Value::I64(43));
     }
+
+    #[test]
+    fn test_match_reference_pattern() {
+        let expr: syn::ExprMatch = syn::parse_quote! {
+            match &5 {
+                &n => n,
+            }
+        };
+
+        let mut env = Environment::new();
+        let ctx = EvalContext::default();
+        let result = expr.eval(&mut env, &ctx).unwrap();
+
+        assert_eq!(result, Value::I64(5));
+    }
+
+    fn color_env(variant: &str) -> Environment {
+        let item: syn::Item = syn::parse_quote! {
+            enum Color { Red, Green, Blue }
+        };
+        let mut env = Environment::new();
+        super::super::item::eval_item(&item, &mut env, &EvalContext::default()).unwrap();
+        env.define(
+            "c",
+            Value::enumeration(crate::value::EnumValue::unit("Color", variant)),
+        );
+        env
+    }
+
+    #[test]
+    fn test_match_registered_enum_missing_variant() {
+        let expr: syn::ExprMatch = syn::parse_quote! {
+            match c {
+                Color::Red => 1,
+                Color::Green => 2,
+            }
+        };
+
+        // Red is covered, but the check runs before any arm is tried
+        let mut env = color_env("Red");
+        let mut ctx = EvalContext::default();
+        ctx.exhaustive_enum_matches = true;
+
+        match expr.eval(&mut env, &ctx).unwrap_err() {
+            EvalError::NonExhaustiveMatch { value, span } => {
+                assert_eq!(value, "Color::Blue");
+                assert!(span.is_some());
+            }
+            other => panic!("Expected NonExhaustiveMatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_match_registered_enum_guard_does_not_cover() {
+        let expr: syn::ExprMatch = syn::parse_quote! {
+            match c {
+                Color::Red => 1,
+                Color::Green if true => 2,
+                Color::Blue => 3,
+            }
+        };
+
+        let mut env = color_env("Red");
+        let mut ctx = EvalContext::default();
+        ctx.exhaustive_enum_matches = true;
+        let err = expr.eval(&mut env, &ctx).unwrap_err();
+
+        assert!(err.to_string().contains("Color::Green"));
+    }
+
+    #[test]
+    fn test_match_registered_enum_unchecked_by_default() {
+        let mut interp = crate::Interpreter::new();
+        let result = interp
+            .eval_str("enum E { A, B } match E::A { E::A => 1 }")
+            .unwrap();
+        assert_eq!(result, Value::I64(1));
+    }
+
+    #[test]
+    fn test_match_registered_enum_compares_full_paths() {
+        let expr: syn::ExprMatch = syn::parse_quote! {
+            match c {
+                Shade::Red => 0,
+                Color::Red => 1,
+                Color::Green => 2,
+                Other::Blue => 3,
+            }
+        };
+
+        let mut env = color_env("Red");
+        let mut ctx = EvalContext::default();
+        ctx.exhaustive_enum_matches = true;
+        let err = expr.eval(&mut env, &ctx).unwrap_err();
+
+        assert!(matches!(
+            err,
+            EvalError::NonExhaustiveMatch { value, .. } if value == "Color::Blue"
+        ));
+    }
+
+    #[test]
+    fn test_match_registered_enum_wildcard() {
+        let expr: syn::ExprMatch = syn::parse_quote! {
+            match c {
+                Color::Red => 1,
+                _ => 0,
+            }
+        };
+
+        let mut env = color_env("Blue");
+        let ctx = EvalContext::default();
+        let result = expr.eval(&mut env, &ctx).unwrap();
+
+        assert_eq!(result, Value::I64(0));
+    }
+
+    #[test]
+    fn test_match_tuple_scrutinee() {
+        let mut interp = crate::Interpreter::new();
+        let result = interp
+            .eval_str("match (1, 2) { (1, y) => y, _ => 0 }")
+            .unwrap();
+        assert_eq!(result, Value::I64(2));
+
+        let result = interp
+            .eval_str("match (3, 2) { (1, _) => 1, (_, 2) => 2, _ => 0 }")
+            .unwrap();
+        assert_eq!(result, Value::I64(2));
+    }
+
+    #[test]
+    fn test_match_through_reference() {
+        let mut interp = crate::Interpreter::new();
+        let result = interp
+            .eval_str("let p = (4, 5); match &p { (4, b) => b, _ => 0 }")
+            .unwrap();
+        assert_eq!(result, Value::I64(5));
+
+        let result = interp
+            .eval_str("let o = Some(3); match &o { Some(n) => n, None => 0 }")
+            .unwrap();
+        assert_eq!(result, Value::I64(3));
+
+        let result = interp
+            .eval_str("match &7 { 7 => true, _ => false }")
+            .unwrap();
+        assert_eq!(result, Value::Bool(true));
+    }
+
+    #[test]
+    fn test_match_registered_enum_through_reference() {
+        let expr: syn::ExprMatch = syn::parse_quote! {
+            match &c {
+                Color::Red => 1,
+            }
+        };
+
+        let mut env = color_env("Red");
+        let mut ctx = EvalContext::default();
+        ctx.exhaustive_enum_matches = true;
+        let err = expr.eval(&mut env, &ctx).unwrap_err();
+
+        assert!(matches!(err, EvalError::NonExhaustiveMatch { .. }));
+    }
 }
@@ -4,6 +4,8 @@ pub mod array;
 pub mod assign;
 pub mod binary;
 pub mod call;
+pub mod cast;
+pub mod closure;
 pub mod control;
 pub mod field;
 pub mod function;
@@ -13,10 +15,12 @@ pub mod item;
 pub mod literal;
 pub mod local;
 pub mod loops;
+pub mod macros;
 pub mod match_expr;
 pub mod path;
 pub mod pattern;
 pub mod range;
+pub mod reference;
 pub mod return_expr;
 pub mod stmt;
 pub mod struct_lit;
@@ -40,59 +44,99 @@ pub trait Evaluate {
 
 impl Evaluate for syn::Expr {
     fn eval(&self, env: &mut Environment, ctx: &EvalContext) -> Result<Value, EvalError> {
-        // Check for interruption before each expression
-        if ctx.is_interrupted() {
-            return Err(EvalError::Interrupted);
-        }
+        // Check for interruption or timeout before each expression
+        ctx.check_limits()?;
+
+        let result = dispatch_expr(self, env, ctx);
 
-        match self {
-            // Stage 1.3: Basic expressions
-            syn::Expr::Lit(expr) => expr.eval(env, ctx),
-            syn::Expr::Path(expr) => expr.eval(env, ctx),
-            syn::Expr::Unary(expr) => expr.eval(env, ctx),
-            syn::Expr::Binary(expr) => expr.eval(env, ctx),
-
-            // Stage 1.4: Control flow
-            syn::Expr::If(expr) => expr.eval(env, ctx),
-            syn::Expr::Match(expr) => expr.eval(env, ctx),
-            syn::Expr::Loop(expr) => expr.eval(env, ctx),
-            syn::Expr::While(expr) => expr.eval(env, ctx),
-            syn::Expr::ForLoop(expr) => expr.eval(env, ctx),
-            syn::Expr::Break(expr) => expr.eval(env, ctx),
-            syn::Expr::Continue(expr) => expr.eval(env, ctx),
-
-            // Stage 1.5: Functions
-            syn::Expr::Call(expr) => expr.eval(env, ctx),
-            syn::Expr::MethodCall(expr) => expr.eval(env, ctx),
-            syn::Expr::Return(expr) => expr.eval(env, ctx),
-            syn::Expr::Closure(_) => Err(not_yet_implemented("closure", self)),
-
-            // Stage 1.6: Statements & Blocks
-            syn::Expr::Block(expr) => stmt::eval_block(&expr.block, env, ctx),
-            syn::Expr::Assign(expr) => assign::eval_assign(expr, env, ctx),
-            syn::Expr::Index(expr) => index::eval_index(expr, env, ctx),
-            syn::Expr::Field(expr) => field::eval_field(expr, env, ctx),
-            syn::Expr::Tuple(expr) => tuple::eval_tuple(expr, env, ctx),
-            syn::Expr::Array(expr) => array::eval_array(expr, env, ctx),
-            syn::Expr::Repeat(expr) => array::eval_array_repeat(expr, env, ctx),
-            syn::Expr::Struct(expr) => struct_lit::eval_struct(expr, env, ctx),
-            syn::Expr::Range(expr) => range::eval_range(expr, env, ctx),
-
-            // Parenthesized expressions - just unwrap
-            syn::Expr::Paren(expr) => expr.expr.eval(env, ctx),
-
-            // Group expressions (for precedence) - just unwrap
-            syn::Expr::Group(expr) => expr.expr.eval(env, ctx),
-
-            // Everything else
-            _ => Err(EvalError::UnsupportedExpr {
-                kind: expr_kind_name(self).to_string(),
-                span: Some(expr_span(self)),
-            }),
+        if ctx.trace {
+            trace_expr(self, &result, env, ctx);
         }
+
+        result
+    }
+}
+
+/// Evaluate an expression by delegating to its kind-specific implementation.
+fn dispatch_expr(
+    outer: &syn::Expr,
+    env: &mut Environment,
+    ctx: &EvalContext,
+) -> Result<Value, EvalError> {
+    match outer {
+        // Stage 1.3: Basic expressions
+        syn::Expr::Lit(expr) => expr.eval(env, ctx),
+        syn::Expr::Path(expr) => expr.eval(env, ctx),
+        syn::Expr::Unary(expr) => expr.eval(env, ctx),
+        syn::Expr::Binary(expr) => expr.eval(env, ctx),
+
+        // Stage 1.4: Control flow
+        syn::Expr::If(expr) => expr.eval(env, ctx),
+        syn::Expr::Match(expr) => expr.eval(env, ctx),
+        syn::Expr::Loop(expr) => expr.eval(env, ctx),
+        syn::Expr::While(expr) => expr.eval(env, ctx),
+        syn::Expr::ForLoop(expr) => expr.eval(env, ctx),
+        syn::Expr::Break(expr) => expr.eval(env, ctx),
+        syn::Expr::Continue(expr) => expr.eval(env, ctx),
+        syn::Expr::Let(expr) => expr.eval(env, ctx),
+
+        // Stage 1.5: Functions
+        syn::Expr::Call(expr) => expr.eval(env, ctx),
+        syn::Expr::MethodCall(expr) => expr.eval(env, ctx),
+        syn::Expr::Return(expr) => expr.eval(env, ctx),
+        syn::Expr::Try(expr) => expr.eval(env, ctx),
+        syn::Expr::Closure(expr) => closure::eval_closure(expr, env),
+        syn::Expr::Macro(expr) => macros::eval_macro(&expr.mac, env, ctx),
+
+        // Stage 1.6: Statements & Blocks
+        syn::Expr::Block(expr) => stmt::eval_block(&expr.block, env, ctx),
+        syn::Expr::Assign(expr) => assign::eval_assign(expr, env, ctx),
+        syn::Expr::Index(expr) => index::eval_index(expr, env, ctx),
+        syn::Expr::Field(expr) => field::eval_field(expr, env, ctx),
+        syn::Expr::Tuple(expr) => tuple::eval_tuple(expr, env, ctx),
+        syn::Expr::Array(expr) => array::eval_array(expr, env, ctx),
+        syn::Expr::Repeat(expr) => array::eval_array_repeat(expr, env, ctx),
+        syn::Expr::Struct(expr) => struct_lit::eval_struct(expr, env, ctx),
+        syn::Expr::Range(expr) => range::eval_range(expr, env, ctx),
+        syn::Expr::Cast(expr) => cast::eval_cast(expr, env, ctx),
+        syn::Expr::Reference(expr) => reference::eval_reference(expr, env, ctx),
+
+        // Parenthesized expressions - just unwrap
+        syn::Expr::Paren(expr) => expr.expr.eval(env, ctx),
+
+        // Group expressions (for precedence) - just unwrap
+        syn::Expr::Group(expr) => expr.expr.eval(env, ctx),
+
+        // `_` in expression position reads the last REPL result
+        syn::Expr::Infer(_) => env.get("_").cloned().ok_or(EvalError::UndefinedVariable {
+            name: "_".to_string(),
+            span: Some(expr_span(outer)),
+        }),
+
+        // Everything else
+        _ => Err(EvalError::UnsupportedExpr {
+            kind: expr_kind_name(outer).to_string(),
+            span: Some(expr_span(outer)),
+        }),
     }
 }
 
+/// Emit a trace line for an evaluated expression, indented by call depth.
+fn trace_expr(
+    expr: &syn::Expr,
+    result: &Result<Value, EvalError>,
+    env: &Environment,
+    ctx: &EvalContext,
+) {
+    let kind = expr_kind_name(expr);
+    let line = match result {
+        Ok(value) => format!("{} => {}", kind, value),
+        Err(e) if e.is_control_flow() => format!("{} => <control flow>", kind),
+        Err(e) => format!("{} => error: {}", kind, e),
+    };
+    ctx.emit_trace(env.call_depth(), &line);
+}
+
 /// Get a human-readable name for an expression kind.
 fn expr_kind_name(expr: &syn::Expr) -> &'static str {
     match expr {
@@ -114,7 +158,7 @@ fn expr_kind_name(expr: &syn::Expr) -> &'static str {
         syn::Expr::If(_) => "if",
         syn::Expr::Index(_) => "index",
         syn::Expr::Infer(_) => "infer",
-        syn::Expr::Let(_) => "let guard",
+        syn::Expr::Let(_) => "let expression",
         syn::Expr::Lit(_) => "literal",
         syn::Expr::Loop(_) => "loop",
         syn::Expr::Macro(_) => "macro invocation",
@@ -149,14 +193,6 @@ fn expr_span(expr: &syn::Expr) -> proc_macro2::Span {
         .unwrap_or_else(proc_macro2::Span::call_site)
 }
 
-/// Create a "not yet implemented" error.
-fn not_yet_implemented(what: &str, expr: &syn::Expr) -> EvalError {
-    EvalError::UnsupportedExpr {
-        kind: format!("{} (not yet implemented)", what),
-        span: Some(expr_span(expr)),
-    }
-}
-
 // ═══════════════════════════════════════════════════════════════════════
 // Convenience Functions
 // ═══════════════════════════════════════════════════════════════════════
@@ -171,6 +207,7 @@ pub fn eval_expr(
 }
 
 // Re-export for use by other modules
+pub use call::method_completions;
 pub use control::ControlFlow;
 pub use pattern::{apply_bindings, match_pattern};
 pub use stmt::{eval_block, eval_block_stmts, eval_stmt};
@@ -228,14 +265,45 @@ mod tests {
     }
 
     #[test]
-    fn test_not_yet_implemented() {
-        let expr: syn::Expr = syn::parse_quote!(async {});
-        let err = not_yet_implemented("async block", &expr);
-        match err {
-            EvalError::UnsupportedExpr { kind, .. } => {
-                assert!(kind.contains("not yet implemented"));
-            }
-            _ => panic!("Expected UnsupportedExpr"),
-        }
+    fn test_trace_emits_sub_expressions() {
+        use crate::context::tests::SharedBuf;
+        use std::sync::{Arc, Mutex};
+
+        let buf = Arc::new(Mutex::new(Vec::new()));
+        let mut ctx = EvalContext::default();
+        ctx.trace = true;
+        ctx.set_trace_sink(Box::new(SharedBuf(buf.clone())));
+
+        let expr: syn::Expr = syn::parse_quote!(1 + 2 * 3);
+        let mut env = Environment::new();
+        assert_eq!(eval_expr(&expr, &mut env, &ctx).unwrap(), Value::I64(7));
+
+        let out = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "literal => 1",
+                "literal => 2",
+                "literal => 3",
+                "binary operation => 6",
+                "binary operation => 7",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trace_off_by_default() {
+        use crate::context::tests::SharedBuf;
+        use std::sync::{Arc, Mutex};
+
+        let buf = Arc::new(Mutex::new(Vec::new()));
+        let mut ctx = EvalContext::default();
+        ctx.set_trace_sink(Box::new(SharedBuf(buf.clone())));
+
+        let expr: syn::Expr = syn::parse_quote!(1 + 2);
+        eval_expr(&expr, &mut Environment::new(), &ctx).unwrap();
+
+        assert!(buf.lock().unwrap().is_empty());
     }
 }
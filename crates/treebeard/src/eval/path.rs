@@ -1,15 +1,23 @@
 //! Path evaluation (variable lookup)
 
+use crate::value::EnumValue;
 use crate::{Environment, EvalContext, EvalError, Value};
 
 use super::Evaluate;
 
 impl Evaluate for syn::ExprPath {
     fn eval(&self, env: &mut Environment, _ctx: &EvalContext) -> Result<Value, EvalError> {
-        // For now, we only support simple paths (single identifier)
-        // Complex paths like `std::collections::HashMap` are not supported yet
+        // For now, we only support simple paths (single identifier) and
+        // qualified paths bound under their full name
 
         if self.path.segments.len() != 1 {
+            // Qualified names such as `String::from` may be bound directly
+            if let Some(value) = env.get(&path_to_string(&self.path)) {
+                return Ok(value.clone());
+            }
+            if let Some(variant) = unit_variant(&self.path, env) {
+                return Ok(variant);
+            }
             return Err(EvalError::UnsupportedExpr {
                 kind: format!("qualified path `{}`", path_to_string(&self.path)),
                 span: Some(self.path.segments.first().unwrap().ident.span()),
@@ -37,6 +45,17 @@ impl Evaluate for syn::ExprPath {
     }
 }
 
+/// Evaluate `Enum::Variant` as a unit variant of a registered enum.
+fn unit_variant(path: &syn::Path, env: &Environment) -> Option<Value> {
+    let mut segments = path.segments.iter().rev();
+    let variant = segments.next()?.ident.to_string();
+    let type_name = segments.next()?.ident.to_string();
+
+    env.enum_variants(&type_name)?
+        .contains(&variant)
+        .then(|| Value::enumeration(EnumValue::unit(type_name, variant)))
+}
+
 /// Convert a syn::Path to a string for error messages.
 pub fn path_to_string(path: &syn::Path) -> String {
     path.segments
@@ -199,4 +218,16 @@ mod tests {
         let path: syn::Path = syn::parse_quote!(std::collections::hash_map::HashMap);
         assert_eq!(path_to_string(&path), "std::collections::hash_map::HashMap");
     }
+
+    #[test]
+    fn test_eval_path_unit_enum_variant() {
+        let mut interp = crate::Interpreter::new();
+        interp.eval_str("enum Color { Red, Green }").unwrap();
+
+        assert_eq!(
+            interp.eval_str("Color::Green").unwrap(),
+            Value::enumeration(EnumValue::unit("Color", "Green"))
+        );
+        assert!(interp.eval_str("Color::Blue").is_err());
+    }
 }
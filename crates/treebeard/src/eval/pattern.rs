@@ -1,5 +1,7 @@
 //! Pattern matching logic
 
+use std::cmp::Ordering;
+
 use crate::{Environment, EvalError, Value};
 use proc_macro2::Span;
 
@@ -16,32 +18,71 @@ pub fn match_pattern(
     value: &Value,
     _span: Option<Span>,
 ) -> Result<Option<MatchBindings>, EvalError> {
+    // Default binding modes: a destructuring pattern matches through a
+    // reference as if it were written `&pat`.
+    if is_destructuring(pattern) {
+        match value {
+            Value::Ref(r) => return match_pattern(pattern, &r.value, None),
+            Value::RefMut(r) => {
+                let inner = r.value.read().map_err(|_| EvalError::TypeError {
+                    message: "failed to acquire read lock on RefMut".to_string(),
+                    span: None,
+                })?;
+                return match_pattern(pattern, &inner, None);
+            }
+            _ => {}
+        }
+    }
+
     match pattern {
         // Wildcard: matches anything, no bindings
         syn::Pat::Wild(_) => Ok(Some(vec![])),
 
         // Identifier: matches anything, binds the value
+        //
+        // `ref x` borrows instead of moving, binding a reference to the
+        // matched value. `ref mut x` is rejected: patterns match a copy of
+        // the scrutinee, so writes through it would be lost.
         syn::Pat::Ident(pat_ident) => {
             let name = pat_ident.ident.to_string();
-            let mutable = pat_ident.mutability.is_some();
+            let (bound, mutable) = match (&pat_ident.by_ref, &pat_ident.mutability) {
+                (Some(_), Some(_)) => {
+                    return Err(EvalError::UnsupportedExpr {
+                        kind: format!("`ref mut {}` binding (not yet supported)", name),
+                        span: Some(pat_ident.ident.span()),
+                    })
+                }
+                (Some(_), None) => (Value::reference(value.clone()), false),
+                (None, mutability) => (value.clone(), mutability.is_some()),
+            };
 
             // Check for @ pattern (e.g., `x @ 1..=5`)
             if let Some((_, subpat)) = &pat_ident.subpat {
                 // Must also match the subpattern
                 if let Some(mut bindings) = match_pattern(subpat, value, None)? {
-                    bindings.push((name, value.clone(), mutable));
+                    bindings.push((name, bound, mutable));
                     Ok(Some(bindings))
                 } else {
                     Ok(None)
                 }
             } else {
-                Ok(Some(vec![(name, value.clone(), mutable)]))
+                Ok(Some(vec![(name, bound, mutable)]))
             }
         }
 
         // Literal pattern: matches exact value
         syn::Pat::Lit(pat_lit) => {
             let lit_value = crate::eval::literal::eval_lit(&pat_lit.lit)?;
+            let untyped = match &pat_lit.lit {
+                syn::Lit::Int(i) => i.suffix().is_empty(),
+                syn::Lit::Float(f) => f.suffix().is_empty(),
+                _ => false,
+            };
+            let lit_value = if untyped {
+                crate::eval::literal::adapt_untyped(lit_value, value)?
+            } else {
+                lit_value
+            };
             if value == &lit_value {
                 Ok(Some(vec![]))
             } else {
@@ -213,12 +254,12 @@ pub fn match_pattern(
             let start = pat_range
                 .start
                 .as_ref()
-                .map(|e| eval_const_expr(e))
+                .map(|e| eval_bound(e, value))
                 .transpose()?;
             let end = pat_range
                 .end
                 .as_ref()
-                .map(|e| eval_const_expr(e))
+                .map(|e| eval_bound(e, value))
                 .transpose()?;
 
             let in_range = match (start, end, &pat_range.limits) {
@@ -243,12 +284,20 @@ pub fn match_pattern(
             }
         }
 
-        // Reference pattern
-        syn::Pat::Reference(pat_ref) => {
-            // For now, just match the inner pattern against the value
-            // (We're not tracking references strictly yet)
-            match_pattern(&pat_ref.pat, value, None)
-        }
+        // Reference pattern: match the inner pattern through the reference.
+        // Plain values are matched directly, since references are not
+        // tracked strictly yet.
+        syn::Pat::Reference(pat_ref) => match value {
+            Value::Ref(r) => match_pattern(&pat_ref.pat, &r.value, None),
+            Value::RefMut(r) => {
+                let inner = r.value.read().map_err(|_| EvalError::TypeError {
+                    message: "failed to acquire read lock on RefMut".to_string(),
+                    span: None,
+                })?;
+                match_pattern(&pat_ref.pat, &inner, None)
+            }
+            _ => match_pattern(&pat_ref.pat, value, None),
+        },
 
         // Rest pattern (..) - handled by parent patterns
         syn::Pat::Rest(_) => Ok(Some(vec![])),
@@ -318,6 +367,23 @@ pub fn match_pattern(
     }
 }
 
+/// Whether `pattern` looks inside the value it matches.
+///
+/// Such patterns see through references; bindings, wildcards and `&`
+/// patterns take the reference itself.
+fn is_destructuring(pattern: &syn::Pat) -> bool {
+    matches!(
+        pattern,
+        syn::Pat::Lit(_)
+            | syn::Pat::Range(_)
+            | syn::Pat::Tuple(_)
+            | syn::Pat::Struct(_)
+            | syn::Pat::TupleStruct(_)
+            | syn::Pat::Path(_)
+            | syn::Pat::Slice(_)
+    )
+}
+
 /// Evaluate a constant expression (for range patterns).
 fn eval_const_expr(expr: &syn::Expr) -> Result<Value, EvalError> {
     // Only handle literals and negated literals for now
@@ -334,28 +400,27 @@ fn eval_const_expr(expr: &syn::Expr) -> Result<Value, EvalError> {
     }
 }
 
+/// Evaluate a range pattern bound, giving an untyped literal the type of
+/// the scrutinee.
+fn eval_bound(expr: &syn::Expr, value: &Value) -> Result<Value, EvalError> {
+    let bound = eval_const_expr(expr)?;
+    if crate::eval::literal::is_untyped_literal(expr) {
+        crate::eval::literal::adapt_untyped(bound, value)
+    } else {
+        Ok(bound)
+    }
+}
+
 /// Check if value is in inclusive range [start, end].
 fn value_in_range_inclusive(value: &Value, start: &Value, end: &Value) -> bool {
-    match (value, start, end) {
-        (Value::I64(v), Value::I64(s), Value::I64(e)) => *v >= *s && *v <= *e,
-        (Value::I32(v), Value::I32(s), Value::I32(e)) => *v >= *s && *v <= *e,
-        (Value::U64(v), Value::U64(s), Value::U64(e)) => *v >= *s && *v <= *e,
-        (Value::U32(v), Value::U32(s), Value::U32(e)) => *v >= *s && *v <= *e,
-        (Value::Char(v), Value::Char(s), Value::Char(e)) => *v >= *s && *v <= *e,
-        _ => false,
-    }
+    start.compare(value).is_some_and(Ordering::is_le)
+        && value.compare(end).is_some_and(Ordering::is_le)
 }
 
 /// Check if value is in exclusive range [start, end).
 fn value_in_range_exclusive(value: &Value, start: &Value, end: &Value) -> bool {
-    match (value, start, end) {
-        (Value::I64(v), Value::I64(s), Value::I64(e)) => *v >= *s && *v < *e,
-        (Value::I32(v), Value::I32(s), Value::I32(e)) => *v >= *s && *v < *e,
-        (Value::U64(v), Value::U64(s), Value::U64(e)) => *v >= *s && *v < *e,
-        (Value::U32(v), Value::U32(s), Value::U32(e)) => *v >= *s && *v < *e,
-        (Value::Char(v), Value::Char(s), Value::Char(e)) => *v >= *s && *v < *e,
-        _ => false,
-    }
+    start.compare(value).is_some_and(Ordering::is_le)
+        && value.compare(end).is_some_and(Ordering::is_lt)
 }
 
 /// Match a slice pattern with rest (..).
@@ -603,6 +668,22 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_value_in_range_floats_match_comparison_operators() {
+        let (zero, one) = (Value::F64(0.0), Value::F64(1.0));
+        assert!(value_in_range_inclusive(&Value::F64(-0.0), &zero, &one));
+        assert!(!value_in_range_inclusive(
+            &Value::F64(f64::NAN),
+            &zero,
+            &one
+        ));
+        assert!(!value_in_range_exclusive(
+            &Value::F64(f64::NAN),
+            &zero,
+            &one
+        ));
+    }
+
     #[test]
     fn test_apply_bindings_immutable() {
         let mut env = Environment::new();
@@ -646,6 +727,17 @@ mod tests {
         assert!(result.is_some());
     }
 
+    #[test]
+    fn test_ref_mut_binding_rejected() {
+        let mut interp = crate::Interpreter::new();
+        let err = interp
+            .eval_str("let mut o = Some(1); if let Some(ref mut v) = o { *v += 1; } o")
+            .unwrap_err();
+        assert!(
+            matches!(err.inner(), EvalError::UnsupportedExpr { kind, .. } if kind.contains("ref mut v"))
+        );
+    }
+
     #[test]
     fn test_eval_const_expr_literal() {
         let expr: syn::Expr = syn::parse_quote!(42);
@@ -20,6 +20,7 @@ use super::Evaluate;
 ///
 /// Returns `TypeError` if range bounds are not integers.
 /// Returns `UnsupportedExpr` for unbounded ranges.
+/// Returns `MemoryLimitExceeded` if the range exceeds the memory budget.
 pub fn eval_range(
     range: &syn::ExprRange,
     env: &mut crate::Environment,
@@ -49,6 +50,17 @@ pub fn eval_range(
                 span: None,
             })?;
 
+            // Check the budget before allocating
+            let len = match limits {
+                RangeLimits::HalfOpen(_) => end_i64.saturating_sub(start_i64),
+                RangeLimits::Closed(_) => end_i64.saturating_sub(start_i64).saturating_add(1),
+            };
+            ctx.check_allocation(
+                usize::try_from(len.max(0))
+                    .unwrap_or(usize::MAX)
+                    .saturating_mul(std::mem::size_of::<Value>()),
+            )?;
+
             // Generate the range values
             let values = match limits {
                 RangeLimits::HalfOpen(_) => {
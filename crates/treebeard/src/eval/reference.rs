@@ -0,0 +1,45 @@
+//! Reference expression evaluation
+
+use crate::{Environment, EvalContext, EvalError, Value};
+
+use super::Evaluate;
+
+/// Evaluate a reference expression (`&x` or `&mut x`).
+///
+/// Ownership tracking is not implemented yet, so the reference holds a
+/// copy of the referent rather than aliasing the original binding.
+///
+/// # Errors
+///
+/// Returns errors from evaluating the referent.
+pub fn eval_reference(
+    reference: &syn::ExprReference,
+    env: &mut Environment,
+    ctx: &EvalContext,
+) -> Result<Value, EvalError> {
+    let value = reference.expr.eval(env, ctx)?;
+
+    if reference.mutability.is_some() {
+        Ok(Value::reference_mut(value))
+    } else {
+        Ok(Value::reference(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_and_deref() {
+        let mut env = Environment::new();
+        let ctx = EvalContext::default();
+        env.define("x", Value::I64(7));
+
+        let expr: syn::Expr = syn::parse_str("&x").unwrap();
+        assert!(matches!(expr.eval(&mut env, &ctx).unwrap(), Value::Ref(_)));
+
+        let expr: syn::Expr = syn::parse_str("*&mut x").unwrap();
+        assert_eq!(expr.eval(&mut env, &ctx).unwrap(), Value::I64(7));
+    }
+}
@@ -1,4 +1,6 @@
-//! Return expression evaluation
+//! Return and `?` expression evaluation
+
+use syn::spanned::Spanned;
 
 use crate::eval::control::ControlFlow;
 use crate::{Environment, EvalContext, EvalError, Value};
@@ -18,6 +20,36 @@ impl Evaluate for syn::ExprReturn {
     }
 }
 
+impl Evaluate for syn::ExprTry {
+    /// Unwrap `Ok`/`Some`, or return `Err`/`None` from the enclosing
+    /// function or closure.
+    fn eval(&self, env: &mut Environment, ctx: &EvalContext) -> Result<Value, EvalError> {
+        let value = self.expr.eval(env, ctx)?;
+
+        let early = match &value {
+            Value::Result(res) => match res.as_ref() {
+                Ok(inner) => return Ok(inner.clone()),
+                Err(_) => value,
+            },
+            Value::Option(opt) => match opt.as_ref() {
+                Some(inner) => return Ok(inner.clone()),
+                None => value,
+            },
+            other => {
+                return Err(EvalError::TypeError {
+                    message: format!(
+                        "the `?` operator can only be applied to `Result` or `Option`, found `{}`",
+                        crate::error::type_name(other)
+                    ),
+                    span: Some(self.question_token.span()),
+                })
+            }
+        };
+
+        Err(EvalError::ControlFlow(ControlFlow::Return { value: early }))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,4 +87,56 @@ mod tests {
             assert_eq!(value, Value::Unit);
         }
     }
+
+    #[test]
+    fn test_closure_return_stops_at_closure() {
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(
+            interp
+                .eval_str("let f = |x| { if x > 0 { return 1; } 2 }; f(5)")
+                .unwrap(),
+            Value::I64(1)
+        );
+
+        // The closure's `return` must not end the function calling it
+        interp
+            .eval_str(
+                "fn outer() -> i64 {
+                     let f = |x| { if x > 0 { return 1; } 2 };
+                     let a = f(5);
+                     let g = |y| { let h = |z| { return z * 10; }; h(y) + 1 };
+                     a + f(0) + g(3) + 100
+                 }",
+            )
+            .unwrap();
+        assert_eq!(interp.eval_str("outer()").unwrap(), Value::I64(134));
+    }
+
+    #[test]
+    fn test_try_operator() {
+        let mut interp = crate::Interpreter::new();
+        interp
+            .eval_str(
+                "fn first(r: i64) -> i64 {
+                     let f = |x| { let v = x?; Ok(v + 1) };
+                     match f(Err(r)) { Ok(v) => v, Err(e) => e * 2 }
+                 }
+                 fn half(n: i64) -> Option<i64> {
+                     let v = if n % 2 == 0 { Some(n / 2) } else { None };
+                     Some(v? + 100)
+                 }",
+            )
+            .unwrap();
+
+        assert_eq!(interp.eval_str("first(4)").unwrap(), Value::I64(8));
+        assert_eq!(
+            interp.eval_str("half(4)").unwrap(),
+            Value::some(Value::I64(102))
+        );
+        assert_eq!(interp.eval_str("half(3)").unwrap(), Value::none());
+        assert!(matches!(
+            interp.eval_str("5?"),
+            Err(EvalError::TypeError { .. })
+        ));
+    }
 }
@@ -4,6 +4,7 @@ use crate::{Environment, EvalContext, EvalError, Value};
 
 use super::item::eval_item;
 use super::local::eval_local;
+use super::macros::eval_macro;
 use super::Evaluate;
 
 /// Evaluate a statement.
@@ -38,20 +39,16 @@ pub fn eval_stmt(
             Ok(Value::Unit)
         }
 
-        // Macro statement
-        syn::Stmt::Macro(stmt_macro) => Err(EvalError::UnsupportedExpr {
-            kind: format!(
-                "macro statement: {}",
-                stmt_macro
-                    .mac
-                    .path
-                    .segments
-                    .last()
-                    .map(|s| s.ident.to_string())
-                    .unwrap_or_else(|| "unknown".to_string())
-            ),
-            span: None,
-        }),
+        // Macro statement: built-in macros only, user macros are expanded
+        // before evaluation
+        syn::Stmt::Macro(stmt_macro) => {
+            let value = eval_macro(&stmt_macro.mac, env, ctx)?;
+            if stmt_macro.semi_token.is_some() {
+                Ok(Value::Unit)
+            } else {
+                Ok(value)
+            }
+        }
     }
 }
 
@@ -86,10 +83,8 @@ pub fn eval_block_stmts(
     let mut last_value = Value::Unit;
 
     for stmt in stmts {
-        // Check for interruption
-        if ctx.is_interrupted() {
-            return Err(EvalError::Interrupted);
-        }
+        // Check for interruption or timeout
+        ctx.check_limits()?;
 
         last_value = eval_stmt(stmt, env, ctx)?;
     }
@@ -155,4 +150,37 @@ mod tests {
         // x should not be in scope after block
         assert!(env.get("x").is_none());
     }
+
+    #[test]
+    fn test_block_expression_value() {
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(
+            interp.eval_str("{ let x = 1; let y = 2; x + y }").unwrap(),
+            Value::I64(3)
+        );
+        assert_eq!(interp.eval_str("{ 1; }").unwrap(), Value::Unit);
+        assert_eq!(interp.eval_str("{ 1; fn f() {} }").unwrap(), Value::Unit);
+        assert_eq!(interp.eval_str("{ let z = 5; }").unwrap(), Value::Unit);
+        assert!(!interp.env().contains("x"));
+        assert!(!interp.env().contains("z"));
+    }
+
+    #[test]
+    fn test_branch_and_loop_blocks_allow_bindings() {
+        let mut interp = crate::Interpreter::new();
+        assert_eq!(
+            interp
+                .eval_str("if true { let a = 2; a * 3 } else { let b = 1; b }")
+                .unwrap(),
+            Value::I64(6)
+        );
+        assert_eq!(
+            interp
+                .eval_str("let mut t = 0; for i in [1, 2] { let d = i * 10; t += d; } t")
+                .unwrap(),
+            Value::I64(30)
+        );
+        assert!(!interp.env().contains("a"));
+        assert!(!interp.env().contains("d"));
+    }
 }
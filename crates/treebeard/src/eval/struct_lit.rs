@@ -58,6 +58,18 @@ pub fn eval_struct(
         fields.insert(field_name, field_value);
     }
 
+    // Keep fields in declaration order when the struct is known, so that
+    // output does not depend on the order they were written in
+    if let Some(declared) = env.struct_fields(&type_name) {
+        let position = |name: &str| declared.iter().position(|d| d == name);
+        fields.sort_by(|a, _, b, _| match (position(a), position(b)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+    }
+
     Ok(Value::structure(StructValue {
         type_name,
         fields,
@@ -224,4 +236,21 @@ mod tests {
             panic!("Expected Struct");
         }
     }
+
+    #[test]
+    fn test_struct_literal_declaration_order() {
+        let mut interp = crate::Interpreter::new();
+        interp.eval_str("struct Point { x: i64, y: i64 }").unwrap();
+
+        for src in [
+            "format!(\"{:?}\", Point { x: 1, y: 2 })",
+            "format!(\"{:?}\", Point { y: 2, x: 1 })",
+            "format!(\"{:?}\", Point { y: 2, ..Point { x: 1, y: 0 } })",
+        ] {
+            assert_eq!(
+                interp.eval_str(src).unwrap(),
+                Value::string("Point { x: 1, y: 2 }")
+            );
+        }
+    }
 }
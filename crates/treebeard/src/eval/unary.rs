@@ -3,11 +3,26 @@
 use crate::error::type_name;
 use crate::{Environment, EvalContext, EvalError, Value};
 
+use super::literal::eval_negated_int_literal;
 use super::Evaluate;
 use syn::spanned::Spanned;
 
 impl Evaluate for syn::ExprUnary {
     fn eval(&self, env: &mut Environment, ctx: &EvalContext) -> Result<Value, EvalError> {
+        // `-128i8` is a single literal to rustc, so parse it as one
+        if let (
+            syn::UnOp::Neg(_),
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(lit),
+                ..
+            }),
+        ) = (&self.op, self.expr.as_ref())
+        {
+            if let Some(value) = eval_negated_int_literal(lit)? {
+                return Ok(value);
+            }
+        }
+
         let operand = self.expr.eval(env, ctx)?;
         let span = Some(self.op.span());
 
@@ -100,13 +115,7 @@ fn eval_not(operand: Value, span: Option<proc_macro2::Span>) -> Result<Value, Ev
 fn eval_deref(operand: Value, span: Option<proc_macro2::Span>) -> Result<Value, EvalError> {
     match operand {
         Value::Ref(r) => Ok((*r.value).clone()),
-        Value::RefMut(r) => {
-            let guard = r.value.read().map_err(|_| EvalError::TypeError {
-                message: "failed to acquire read lock on RefMut".to_string(),
-                span,
-            })?;
-            Ok(guard.clone())
-        }
+        Value::RefMut(_) => Ok(operand.deref_ref().map_err(|e| e.or_span(span))?.clone()),
         other => Err(EvalError::InvalidUnaryOperand {
             op: "*".to_string(),
             operand_type: type_name(&other).to_string(),
@@ -375,6 +384,30 @@ mod tests {
         assert_eq!(result, Value::I64(-42));
     }
 
+    #[test]
+    fn test_expr_unary_neg_min_literal() {
+        let mut env = Environment::new();
+        let ctx = EvalContext::default();
+
+        let expr: syn::ExprUnary = syn::parse_quote!(-128i8);
+        assert_eq!(expr.eval(&mut env, &ctx).unwrap(), Value::I8(-128));
+
+        let expr: syn::ExprUnary = syn::parse_quote!(-170141183460469231731687303715884105728i128);
+        assert_eq!(expr.eval(&mut env, &ctx).unwrap(), Value::I128(i128::MIN));
+
+        let expr: syn::ExprUnary = syn::parse_quote!(-129i8);
+        assert!(matches!(
+            expr.eval(&mut env, &ctx),
+            Err(EvalError::IntegerOverflow { .. })
+        ));
+
+        let expr: syn::ExprUnary = syn::parse_quote!(-1u8);
+        assert!(matches!(
+            expr.eval(&mut env, &ctx),
+            Err(EvalError::InvalidUnaryOperand { .. })
+        ));
+    }
+
     #[test]
     fn test_expr_unary_not_bool() {
         let expr: syn::ExprUnary = syn::parse_quote!(!true);
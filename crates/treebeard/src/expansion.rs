@@ -14,7 +14,12 @@
 //!
 //! This is part of Phase 3: Macro System (Stage 3.4)
 
-use crate::{MacroEnvironment, Value};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::visit_mut::VisitMut;
+
+use crate::template::{template_from_expr, value_to_syn_expr, TemplateBindings};
+use crate::{EvalError, MacroEnvironment, Value};
 
 /// Result type for expansion operations.
 pub type ExpansionResult<T> = std::result::Result<T, ExpansionError>;
@@ -270,6 +275,135 @@ pub fn is_macro(name: &str, env: &MacroEnvironment) -> bool {
     env.has_macro(name)
 }
 
+/// Expand every invocation of a defined macro in a parsed expression.
+///
+/// Each `name!(args, ...)` whose `name` is defined in `macros` is expanded:
+/// the arguments are converted to template Values, the macro is expanded
+/// (including any macros in its output), and the result is converted back
+/// to `syn`. Expansion repeats until no defined macros remain or the
+/// macro environment's maximum expansion depth is reached. Invocations of
+/// undefined macros are left in place.
+///
+/// # Errors
+///
+/// Returns `MacroExpansionError` if an argument cannot be parsed, the
+/// macro fails, or expansion does not reach a fixpoint within the depth
+/// limit.
+pub fn expand_all(expr: &syn::Expr, macros: &MacroEnvironment) -> Result<syn::Expr, EvalError> {
+    let mut expr = expr.clone();
+    let mut expander = AstExpander {
+        macros,
+        expanded: 0,
+        error: None,
+    };
+
+    for _ in 0..=macros.max_expansion_depth() {
+        expander.expanded = 0;
+        expander.visit_expr_mut(&mut expr);
+        if let Some(error) = expander.error {
+            return Err(error);
+        }
+        if expander.expanded == 0 {
+            return Ok(expr);
+        }
+    }
+
+    Err(EvalError::MacroExpansionError {
+        name: macro_name(&expr).unwrap_or_default(),
+        message: format!(
+            "macro overflow: expansion did not finish within depth {}",
+            macros.max_expansion_depth()
+        ),
+        span: Some(expr.span()),
+    })
+}
+
+/// Rewrites defined macro invocations in place, one layer per pass.
+struct AstExpander<'a> {
+    macros: &'a MacroEnvironment,
+    expanded: usize,
+    error: Option<EvalError>,
+}
+
+impl AstExpander<'_> {
+    fn expand_invocation(&self, mac: &syn::Macro) -> Result<syn::Expr, EvalError> {
+        let name = mac
+            .path
+            .get_ident()
+            .map(|ident| ident.to_string())
+            .unwrap_or_default();
+        let fail = |message: String| EvalError::MacroExpansionError {
+            name: name.clone(),
+            message,
+            span: Some(mac.span()),
+        };
+
+        let args = mac
+            .parse_body_with(Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated)
+            .map_err(|e| fail(e.to_string()))?;
+        let args = args
+            .iter()
+            .map(|arg| template_from_expr(arg, &[])?.expand(&TemplateBindings::new()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let expanded = self
+            .macros
+            .expand_macro(&name, &args)
+            .and_then(|value| self.macros.expand_all(&value))
+            .map_err(fail)?;
+        value_to_syn_expr(&expanded)
+    }
+
+    fn is_defined(&self, mac: &syn::Macro) -> bool {
+        mac.path
+            .get_ident()
+            .is_some_and(|ident| self.macros.has_macro(&ident.to_string()))
+    }
+}
+
+impl VisitMut for AstExpander<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+        if self.error.is_some() {
+            return;
+        }
+        if let syn::Expr::Macro(m) = expr {
+            if self.is_defined(&m.mac) {
+                match self.expand_invocation(&m.mac) {
+                    Ok(expanded) => {
+                        *expr = expanded;
+                        self.expanded += 1;
+                    }
+                    Err(e) => self.error = Some(e),
+                }
+                return;
+            }
+        }
+        syn::visit_mut::visit_expr_mut(self, expr);
+    }
+
+    fn visit_stmt_mut(&mut self, stmt: &mut syn::Stmt) {
+        // `name!(...);` in statement position parses as a statement macro
+        if let syn::Stmt::Macro(m) = stmt {
+            if self.is_defined(&m.mac) {
+                let expr = syn::Expr::Macro(syn::ExprMacro {
+                    attrs: m.attrs.clone(),
+                    mac: m.mac.clone(),
+                });
+                *stmt = syn::Stmt::Expr(expr, m.semi_token);
+            }
+        }
+        syn::visit_mut::visit_stmt_mut(self, stmt);
+    }
+}
+
+/// Name of the outermost macro invocation in `expr`, if any.
+fn macro_name(expr: &syn::Expr) -> Option<String> {
+    match expr {
+        syn::Expr::Macro(m) => m.mac.path.get_ident().map(|i| i.to_string()),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -476,4 +610,49 @@ mod tests {
         };
         assert!(err.to_string().contains("Invalid macro invocation"));
     }
+
+    fn double_macro() -> MacroEnvironment {
+        let file: syn::File = syn::parse_str("#[defmacro] fn double(x: _) { x + x }").unwrap();
+        let mut env = MacroEnvironment::new();
+        env.define_from_items(&file.items).unwrap();
+        env
+    }
+
+    #[test]
+    fn test_expand_all_ast() {
+        let env = double_macro();
+        let expr: syn::Expr = syn::parse_str("1 + double!(double!(3))").unwrap();
+        let expanded = expand_all(&expr, &env).unwrap();
+
+        let result = crate::eval_expr(
+            &expanded,
+            &mut crate::Environment::new(),
+            &crate::EvalContext::default(),
+        )
+        .unwrap();
+        assert_eq!(result, Value::I64(13));
+    }
+
+    #[test]
+    fn test_expand_all_leaves_unknown_macros() {
+        let env = double_macro();
+        let expr: syn::Expr = syn::parse_str("other!(1)").unwrap();
+        assert_eq!(expand_all(&expr, &env).unwrap(), expr);
+    }
+
+    #[test]
+    fn test_expand_all_ast_overflow() {
+        let file: syn::File = syn::parse_str("#[defmacro] fn forever() { forever!() }").unwrap();
+        let mut env = MacroEnvironment::with_max_expansion_depth(8);
+        env.define_from_items(&file.items).unwrap();
+
+        let expr: syn::Expr = syn::parse_str("forever!()").unwrap();
+        match expand_all(&expr, &env) {
+            Err(EvalError::MacroExpansionError { name, message, .. }) => {
+                assert_eq!(name, "forever");
+                assert!(message.contains("macro overflow"));
+            }
+            other => panic!("expected MacroExpansionError, got {:?}", other),
+        }
+    }
 }
@@ -81,6 +81,15 @@ impl fmt::Display for ParseError {
 
 impl std::error::Error for ParseError {}
 
+impl From<ParseError> for EvalError {
+    fn from(err: ParseError) -> Self {
+        EvalError::ParseError {
+            message: err.message,
+            span: None,
+        }
+    }
+}
+
 /// Source code location for error reporting.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SourceLocation {
@@ -105,6 +114,28 @@ impl SourceLocation {
     }
 }
 
+/// An expression together with the source it was parsed from.
+///
+/// Spans in the expression are byte offsets into `source`, so errors
+/// raised while evaluating it can be rendered with
+/// [`render_error`](Self::render_error) against the exact text.
+#[derive(Debug, Clone)]
+pub struct SourceExpr {
+    /// The parsed expression
+    pub expr: syn::Expr,
+
+    /// The source text the expression's spans point into
+    pub source: String,
+}
+
+impl SourceExpr {
+    /// Render an error raised by this expression, underlining its span in
+    /// the source.
+    pub fn render_error(&self, error: &EvalError) -> String {
+        error.render(&self.source)
+    }
+}
+
 /// Error that occurred during macro expansion.
 #[derive(Debug, Clone)]
 pub struct MacroError {
@@ -235,6 +266,10 @@ impl ReplCommand {
 ///             .map_err(|e| ParseError::new(format!("Syntax error: {}", e)))
 ///     }
 ///
+///     fn parse_expr(&self, source: &str) -> Result<syn::Expr, EvalError> {
+///         syn::parse_str(source).map_err(EvalError::from)
+///     }
+///
 ///     fn expand_macros(
 ///         &self,
 ///         items: Vec<syn::Item>,
@@ -272,6 +307,44 @@ pub trait LanguageFrontend: Send + Sync {
     /// Returns `ParseError` if the source cannot be parsed.
     fn parse(&self, source: &str) -> Result<Vec<syn::Item>, ParseError>;
 
+    /// Parse source code as a single expression.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvalError::ParseError` if the source is not a valid expression.
+    fn parse_expr(&self, source: &str) -> Result<syn::Expr, EvalError>;
+
+    /// Parse source code as a single expression, keeping the source so
+    /// errors can be rendered against it.
+    ///
+    /// The default relies on [`parse_expr`](Self::parse_expr) producing
+    /// spans that are byte offsets into `source`, as `syn` does. Frontends
+    /// that translate to Rust first should override this to keep the
+    /// generated source instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvalError::ParseError` if the source is not a valid expression.
+    fn parse_expr_with_source(&self, source: &str) -> Result<SourceExpr, EvalError> {
+        Ok(SourceExpr {
+            expr: self.parse_expr(source)?,
+            source: source.to_string(),
+        })
+    }
+
+    /// Parse source code as a sequence of top-level items.
+    ///
+    /// Defaults to [`parse`](Self::parse) with the error converted to
+    /// `EvalError::ParseError`. Frontends that can report spans should
+    /// override this.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvalError::ParseError` if the source cannot be parsed.
+    fn parse_items(&self, source: &str) -> Result<Vec<syn::Item>, EvalError> {
+        self.parse(source).map_err(EvalError::from)
+    }
+
     /// Expand macros in the AST.
     ///
     /// This method transforms the AST by expanding macro invocations. The macro
@@ -387,6 +460,18 @@ mod tests {
         assert_eq!(loc.column, 10);
     }
 
+    #[test]
+    fn test_parse_error_into_eval_error() {
+        let err: EvalError = ParseError::new("unexpected token").into();
+        match err {
+            EvalError::ParseError { message, span } => {
+                assert_eq!(message, "unexpected token");
+                assert!(span.is_none());
+            }
+            other => panic!("Expected ParseError, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_macro_error_creation() {
         let err = MacroError::new("expansion failed");
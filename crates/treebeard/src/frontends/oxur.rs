@@ -81,6 +81,14 @@ impl LanguageFrontend for OxurFrontend {
         self.parse_oxur_to_syn(source)
     }
 
+    fn parse_expr(&self, _source: &str) -> Result<syn::Expr, EvalError> {
+        // The oxur-ast bridge generates whole crates, not bare expressions
+        Err(EvalError::ParseError {
+            message: "Oxur frontend does not support parsing standalone expressions".to_string(),
+            span: None,
+        })
+    }
+
     fn expand_macros(
         &self,
         items: Vec<syn::Item>,
@@ -264,6 +272,7 @@ fn format_value_oxur(value: &Value, max_depth: usize, current_depth: usize) -> S
             ),
         },
         Value::HashMap(_) => "#<hash-map>".to_string(),
+        Value::Iter(_) => "#<iter>".to_string(),
         Value::Function(f) => format!("#<function:{}>", f.name),
         Value::BuiltinFn(f) => format!("#<builtin:{}>", f.name),
         Value::Closure(_) => "#<closure>".to_string(),
@@ -52,6 +52,16 @@ impl LanguageFrontend for RustFrontend {
         }
     }
 
+    fn parse_expr(&self, source: &str) -> Result<syn::Expr, EvalError> {
+        syn::parse_str(source).map_err(EvalError::from)
+    }
+
+    fn parse_items(&self, source: &str) -> Result<Vec<syn::Item>, EvalError> {
+        syn::parse_file(source)
+            .map(|file| file.items)
+            .map_err(EvalError::from)
+    }
+
     fn expand_macros(
         &self,
         items: Vec<syn::Item>,
@@ -244,6 +254,7 @@ fn format_value_rust(value: &Value, max_depth: usize, current_depth: usize) -> S
             ),
         },
         Value::HashMap(_) => "<HashMap>".to_string(),
+        Value::Iter(_) => "<Iter>".to_string(),
         Value::Function(f) => format!("fn {}", f.name),
         Value::BuiltinFn(f) => format!("<builtin: {}>", f.name),
         Value::Closure(_) => "<closure>".to_string(),
@@ -311,6 +322,52 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_expr_round_trip() {
+        let frontend = RustFrontend::new();
+        let expr = frontend.parse_expr("1 + 2").unwrap();
+        assert!(matches!(expr, syn::Expr::Binary(_)));
+        assert_eq!(quote::quote!(#expr).to_string(), "1 + 2");
+    }
+
+    #[test]
+    fn test_parse_expr_syntax_error() {
+        let frontend = RustFrontend::new();
+        match frontend.parse_expr("1 +") {
+            Err(EvalError::ParseError { span, .. }) => assert!(span.is_some()),
+            other => panic!("Expected ParseError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_expr_with_source_renders_exact_span() {
+        let frontend = RustFrontend::new();
+        let parsed = frontend
+            .parse_expr_with_source("{\n    let n = 1;\n    n / 0\n}")
+            .unwrap();
+        let err = crate::Evaluate::eval(
+            &parsed.expr,
+            &mut crate::Environment::new(),
+            &crate::EvalContext::default(),
+        )
+        .unwrap_err();
+
+        let rendered = parsed.render_error(&err);
+        assert!(rendered.contains("--> 3:7"), "{}", rendered);
+        assert!(
+            rendered.ends_with("3 |     n / 0\n  |       ^"),
+            "{}",
+            rendered
+        );
+    }
+
+    #[test]
+    fn test_parse_items_syntax_error() {
+        let frontend = RustFrontend::new();
+        let result = frontend.parse_items("fn main(");
+        assert!(matches!(result, Err(EvalError::ParseError { .. })));
+    }
+
     #[test]
     fn test_expand_macros_noop() {
         let frontend = RustFrontend::new();
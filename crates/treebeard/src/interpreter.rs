@@ -0,0 +1,252 @@
+//! High-level interpreter façade
+//!
+//! Bundles the runtime environment, evaluation context, and macro
+//! environment behind a single entry point for embedders.
+
+use syn::parse::Parser;
+
+use crate::eval::eval_block_stmts;
+use crate::expansion::expand_all;
+use crate::{BuiltinFn, Environment, EvalContext, EvalError, Evaluate, MacroEnvironment, Value};
+
+/// A ready-to-use interpreter session.
+///
+/// State persists across calls to [`eval_str`](Self::eval_str), so
+/// functions and bindings defined by one call are visible to the next.
+///
+/// # Example
+///
+/// ```
+/// use treebeard::{Interpreter, Value};
+///
+/// let mut interp = Interpreter::new();
+/// interp.eval_str("fn sq(x: i64) -> i64 { x * x }").unwrap();
+/// assert_eq!(interp.eval_str("sq(7)").unwrap(), Value::I64(49));
+/// ```
+#[derive(Debug)]
+pub struct Interpreter {
+    env: Environment,
+    ctx: EvalContext,
+    macros: MacroEnvironment,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Interpreter {
+    /// Create an interpreter with the prelude loaded.
+    pub fn new() -> Self {
+        Self {
+            env: Environment::with_prelude(),
+            ctx: EvalContext::default(),
+            macros: MacroEnvironment::new(),
+        }
+    }
+
+    /// Create an interpreter with the prelude loaded and a custom context.
+    pub fn with_context(ctx: EvalContext) -> Self {
+        Self { ctx, ..Self::new() }
+    }
+
+    /// Parse, macro-expand, and evaluate Rust source.
+    ///
+    /// The source may be a single expression, or a sequence of items and
+    /// statements optionally followed by a trailing expression. Invocations
+    /// of macros defined in [`macros`](Self::macros) are expanded first.
+    /// Everything is evaluated at global scope, and the value of the
+    /// trailing expression (or `()`) is returned. A context timeout applies
+    /// to each call separately.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the source cannot be parsed,
+    /// `MacroExpansionError` if a macro fails to expand, or any error
+    /// raised during evaluation.
+    pub fn eval_str(&mut self, src: &str) -> Result<Value, EvalError> {
+        self.ctx.restart_timeout();
+        // Statements first, falling back to an expression for anything that
+        // is only valid as one
+        let stmts = match syn::Block::parse_within.parse_str(src) {
+            Ok(stmts) => stmts,
+            Err(err) => match syn::parse_str::<syn::Expr>(src).map_err(|_| err)? {
+                // `let x = 5` without its semicolon is a binding, not a
+                // `let` condition
+                syn::Expr::Let(binding) => vec![let_statement(binding)],
+                expr => {
+                    let expr = expand_all(&expr, &self.macros)?;
+                    return expr.eval(&mut self.env, &self.ctx);
+                }
+            },
+        };
+        let block = syn::Expr::Block(syn::ExprBlock {
+            attrs: Vec::new(),
+            label: None,
+            block: syn::Block {
+                brace_token: Default::default(),
+                stmts,
+            },
+        });
+        let syn::Expr::Block(block) = expand_all(&block, &self.macros)? else {
+            unreachable!("expansion only rewrites macro invocations");
+        };
+        eval_block_stmts(&block.block.stmts, &mut self.env, &self.ctx)
+    }
+
+    /// Register a built-in function.
+    pub fn define_builtin(&mut self, builtin: BuiltinFn) {
+        self.env.define_builtin(builtin);
+    }
+
+    /// Get the runtime environment.
+    pub fn env(&self) -> &Environment {
+        &self.env
+    }
+
+    /// Get the runtime environment mutably.
+    pub fn env_mut(&mut self) -> &mut Environment {
+        &mut self.env
+    }
+
+    /// Get the evaluation context.
+    pub fn context(&self) -> &EvalContext {
+        &self.ctx
+    }
+
+    /// Get the evaluation context mutably.
+    pub fn context_mut(&mut self) -> &mut EvalContext {
+        &mut self.ctx
+    }
+
+    /// Get the macro environment.
+    pub fn macros(&self) -> &MacroEnvironment {
+        &self.macros
+    }
+
+    /// Get the macro environment mutably.
+    pub fn macros_mut(&mut self) -> &mut MacroEnvironment {
+        &mut self.macros
+    }
+}
+
+/// The `let` statement a `let` expression reads as on its own.
+fn let_statement(binding: syn::ExprLet) -> syn::Stmt {
+    syn::Stmt::Local(syn::Local {
+        attrs: binding.attrs,
+        let_token: binding.let_token,
+        pat: *binding.pat,
+        init: Some(syn::LocalInit {
+            eq_token: binding.eq_token,
+            expr: binding.expr,
+            diverge: None,
+        }),
+        semi_token: Default::default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_timeout_applies_per_evaluation() {
+        let ctx = EvalContext::with_timeout(std::time::Duration::from_millis(50));
+        let mut interp = Interpreter::with_context(ctx);
+        std::thread::sleep(std::time::Duration::from_millis(60));
+
+        assert_eq!(interp.eval_str("1 + 1").unwrap(), Value::I64(2));
+        assert!(matches!(
+            interp.eval_str("loop {}"),
+            Err(EvalError::Timeout)
+        ));
+        assert_eq!(interp.eval_str("2 + 2").unwrap(), Value::I64(4));
+
+        // A timeout set on the field takes effect from the next evaluation
+        let mut interp = Interpreter::new();
+        interp.context_mut().timeout = Some(std::time::Duration::from_millis(50));
+        assert!(matches!(
+            interp.eval_str("loop {}"),
+            Err(EvalError::Timeout)
+        ));
+    }
+
+    #[test]
+    fn test_new_loads_prelude() {
+        let interp = Interpreter::new();
+        assert!(interp.env().contains("println"));
+    }
+
+    #[test]
+    fn test_eval_str_expression() {
+        let mut interp = Interpreter::new();
+        assert_eq!(interp.eval_str("1 + 2 * 3").unwrap(), Value::I64(7));
+    }
+
+    #[test]
+    fn test_eval_str_defines_persist() {
+        let mut interp = Interpreter::new();
+        interp.eval_str("fn sq(x: i64) -> i64 { x*x }").unwrap();
+        assert_eq!(interp.eval_str("sq(7)").unwrap(), Value::I64(49));
+    }
+
+    #[test]
+    fn test_eval_str_items_and_trailing_expr() {
+        let mut interp = Interpreter::new();
+        let result = interp
+            .eval_str("fn double(x: i64) -> i64 { x * 2 } let y = 4; double(y)")
+            .unwrap();
+        assert_eq!(result, Value::I64(8));
+        assert_eq!(interp.env().get("y"), Some(&Value::I64(4)));
+    }
+
+    #[test]
+    fn test_eval_str_let_statement() {
+        let mut interp = Interpreter::new();
+        assert_eq!(interp.eval_str("let x = 5").unwrap(), Value::Unit);
+        assert_eq!(interp.env().get("x"), Some(&Value::I64(5)));
+    }
+
+    #[test]
+    fn test_eval_str_parse_error() {
+        let mut interp = Interpreter::new();
+        let result = interp.eval_str("let = ;");
+        assert!(matches!(result, Err(EvalError::ParseError { .. })));
+    }
+
+    #[test]
+    fn test_define_builtin() {
+        let mut interp = Interpreter::new();
+        interp.define_builtin(BuiltinFn {
+            name: "answer".to_string(),
+            arity: 0,
+            func: Arc::new(|_| Ok(Value::I64(42))),
+            hooks: None,
+        });
+        assert_eq!(interp.eval_str("answer()").unwrap(), Value::I64(42));
+    }
+
+    #[test]
+    fn test_eval_str_expands_macros() {
+        let mut interp = Interpreter::new();
+        interp.macros_mut().define_user_macro(
+            "double",
+            vec!["x".to_string()],
+            Arc::new(|args| {
+                Ok(Value::vec(vec![
+                    Value::string("*"),
+                    args[0].clone(),
+                    Value::I64(2),
+                ]))
+            }),
+        );
+
+        assert_eq!(interp.eval_str("double!(21)").unwrap(), Value::I64(42));
+        assert_eq!(
+            interp.eval_str("let x = 5; double!(x + 1)").unwrap(),
+            Value::I64(12)
+        );
+    }
+}
@@ -0,0 +1,374 @@
+//! Compilation escape hatch
+//!
+//! Compiles an interpreted function to a native shared library with
+//! `rustc`, loads it, and wraps the exported symbol in a [`CompiledFn`]
+//! that `call_value` can dispatch to like any other callable.
+//!
+//! Types are erased at runtime, so compiled functions use the
+//! interpreter's default integer type: every parameter and the return
+//! value are `i64`. Up to four parameters are supported.
+//!
+//! Compiled code is built with overflow checks, and a panic in it (such
+//! as an overflow) is caught at the library boundary and reported as an
+//! error rather than unwinding into the host.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use libloading::Library;
+use quote::quote;
+
+use crate::{CompiledFn, CompiledFnPtr, EvalError, FunctionValue, LanguageFrontend, Value};
+
+/// Maximum number of parameters a compiled function may take.
+const MAX_COMPILED_ARITY: usize = 4;
+
+/// Distinguishes output directories of compilations within one process.
+static BUILD_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// The symbol each compiled library exports.
+const ENTRY_SYMBOL: &str = "treebeard_entry";
+
+/// A loaded library, whose build directory is removed once it is dropped.
+struct LoadedLibrary {
+    lib: Option<Library>,
+    build_dir: PathBuf,
+}
+
+impl Drop for LoadedLibrary {
+    fn drop(&mut self) {
+        // Unload before deleting the file
+        drop(self.lib.take());
+        let _ = std::fs::remove_dir_all(&self.build_dir);
+    }
+}
+
+/// Compile `func` to a native library and load it.
+///
+/// # Errors
+///
+/// Returns `CompileError` if the function is variadic, has too many
+/// parameters, or a name that is not a plain identifier, if `rustc` is
+/// unavailable or rejects the generated source, or if the resulting
+/// library cannot be loaded.
+pub fn compile_function(
+    func: &FunctionValue,
+    frontend: &dyn LanguageFrontend,
+) -> Result<CompiledFn, EvalError> {
+    let fail = |message: String| EvalError::CompileError {
+        name: func.name.clone(),
+        message,
+        span: None,
+    };
+
+    if func.variadic {
+        return Err(fail("variadic functions cannot be compiled".to_string()));
+    }
+    if func.params.len() > MAX_COMPILED_ARITY {
+        return Err(fail(format!(
+            "compiled functions take at most {} parameters, found {}",
+            MAX_COMPILED_ARITY,
+            func.params.len()
+        )));
+    }
+
+    let source = emit_source(func, frontend).map_err(fail)?;
+
+    let build_dir = std::env::temp_dir().join(format!(
+        "treebeard-jit-{}-{}",
+        std::process::id(),
+        BUILD_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::create_dir_all(&build_dir).map_err(|e| fail(e.to_string()))?;
+
+    let src_path = build_dir.join(format!("{}.rs", func.name));
+    let lib_path = build_dir.join(format!(
+        "{}{}{}",
+        std::env::consts::DLL_PREFIX,
+        func.name,
+        std::env::consts::DLL_SUFFIX
+    ));
+    let entry = std::fs::write(&src_path, source)
+        .map_err(|e| e.to_string())
+        .and_then(|()| run_rustc(&src_path, &lib_path))
+        .and_then(|()| load_entry(&lib_path, &build_dir, func.params.len()));
+    let entry = match entry {
+        Ok(entry) => entry,
+        Err(message) => {
+            let _ = std::fs::remove_dir_all(&build_dir);
+            return Err(fail(message));
+        }
+    };
+
+    Ok(CompiledFn {
+        name: func.name.clone(),
+        arity: func.params.len(),
+        lib_path,
+        func: Some(entry),
+    })
+}
+
+/// Render `func` as a standalone Rust function, exported through an
+/// `extern "C"` entry point that catches panics.
+///
+/// The entry point takes the function's parameters followed by a flag it
+/// sets if the function panicked.
+fn emit_source(func: &FunctionValue, frontend: &dyn LanguageFrontend) -> Result<String, String> {
+    let name = plain_ident(&func.name)?;
+    let params = func
+        .params
+        .iter()
+        .map(|p| plain_ident(p))
+        .collect::<Result<Vec<_>, _>>()?;
+    let body = func.body.as_ref();
+    let entry = quote::format_ident!("{}", ENTRY_SYMBOL);
+
+    let items = quote! {
+        fn #name(#(#params: i64),*) -> i64 #body
+
+        #[no_mangle]
+        pub extern "C" fn #entry(#(#params: i64,)* panicked: *mut bool) -> i64 {
+            match std::panic::catch_unwind(|| #name(#(#params),*)) {
+                Ok(value) => value,
+                Err(_) => {
+                    // SAFETY: the caller passes a pointer to a live flag
+                    unsafe { *panicked = true };
+                    0
+                }
+            }
+        }
+    };
+
+    Ok(format!(
+        "// Generated by treebeard from the {} frontend\n{}\n",
+        frontend.name(),
+        items
+    ))
+}
+
+/// Parse `name` as an identifier that is neither a keyword nor raw.
+fn plain_ident(name: &str) -> Result<syn::Ident, String> {
+    match syn::parse_str::<syn::Ident>(name) {
+        Ok(ident) if !name.starts_with("r#") => Ok(ident),
+        _ => Err(format!("`{}` is not a plain identifier", name)),
+    }
+}
+
+/// Invoke `rustc` to build a cdylib from `src`.
+fn run_rustc(src: &Path, out: &Path) -> Result<(), String> {
+    let output = Command::new("rustc")
+        .args([
+            "--crate-type=cdylib",
+            "--edition=2021",
+            "-O",
+            "-C",
+            "overflow-checks=on",
+            "-o",
+        ])
+        .arg(out)
+        .arg(src)
+        .output()
+        .map_err(|e| format!("could not run rustc: {}", e))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).into_owned())
+    }
+}
+
+/// Load the entry point from the library at `path` and wrap it.
+///
+/// `build_dir` is removed once the entry point is dropped.
+fn load_entry(path: &PathBuf, build_dir: &Path, arity: usize) -> Result<CompiledFnPtr, String> {
+    // SAFETY: the library was just built by `run_rustc` from source we
+    // generated, so its initializers are the standard Rust runtime ones.
+    let lib = unsafe { Library::new(path) }.map_err(|e| e.to_string())?;
+    let lib = Arc::new(LoadedLibrary {
+        lib: Some(lib),
+        build_dir: build_dir.to_path_buf(),
+    });
+    let library = lib.lib.as_ref().ok_or("library is not loaded")?;
+
+    type F0 = unsafe extern "C" fn(*mut bool) -> i64;
+    type F1 = unsafe extern "C" fn(i64, *mut bool) -> i64;
+    type F2 = unsafe extern "C" fn(i64, i64, *mut bool) -> i64;
+    type F3 = unsafe extern "C" fn(i64, i64, i64, *mut bool) -> i64;
+    type F4 = unsafe extern "C" fn(i64, i64, i64, i64, *mut bool) -> i64;
+
+    // SAFETY: `emit_source` declares the entry point as `extern "C"` with
+    // exactly `arity` i64 parameters, a flag pointer and an i64 return,
+    // matching each signature below. The pointer stays valid because each
+    // closure captures `lib` (the `let _ = &lib` lines), keeping the
+    // library loaded. Panics are caught inside the entry point.
+    unsafe {
+        let name = ENTRY_SYMBOL.as_bytes();
+        let entry: CompiledFnPtr = match arity {
+            0 => {
+                let f = *library.get::<F0>(name).map_err(|e| e.to_string())?;
+                let lib = lib.clone();
+                Arc::new(move |_: &[Value]| {
+                    let _ = &lib;
+                    let mut panicked = false;
+                    finish(f(&mut panicked), panicked)
+                })
+            }
+            1 => {
+                let f = *library.get::<F1>(name).map_err(|e| e.to_string())?;
+                let lib = lib.clone();
+                Arc::new(move |args: &[Value]| {
+                    let _ = &lib;
+                    let a = int_args::<1>(args)?;
+                    let mut panicked = false;
+                    finish(f(a[0], &mut panicked), panicked)
+                })
+            }
+            2 => {
+                let f = *library.get::<F2>(name).map_err(|e| e.to_string())?;
+                let lib = lib.clone();
+                Arc::new(move |args: &[Value]| {
+                    let _ = &lib;
+                    let a = int_args::<2>(args)?;
+                    let mut panicked = false;
+                    finish(f(a[0], a[1], &mut panicked), panicked)
+                })
+            }
+            3 => {
+                let f = *library.get::<F3>(name).map_err(|e| e.to_string())?;
+                let lib = lib.clone();
+                Arc::new(move |args: &[Value]| {
+                    let _ = &lib;
+                    let a = int_args::<3>(args)?;
+                    let mut panicked = false;
+                    finish(f(a[0], a[1], a[2], &mut panicked), panicked)
+                })
+            }
+            4 => {
+                let f = *library.get::<F4>(name).map_err(|e| e.to_string())?;
+                let lib = lib.clone();
+                Arc::new(move |args: &[Value]| {
+                    let _ = &lib;
+                    let a = int_args::<4>(args)?;
+                    let mut panicked = false;
+                    finish(f(a[0], a[1], a[2], a[3], &mut panicked), panicked)
+                })
+            }
+            n => return Err(format!("unsupported arity {}", n)),
+        };
+        Ok(entry)
+    }
+}
+
+/// Turn a compiled function's result into a Value, or an error if it
+/// panicked.
+fn finish(value: i64, panicked: bool) -> Result<Value, String> {
+    if panicked {
+        Err("compiled code panicked (for example on integer overflow)".to_string())
+    } else {
+        Ok(Value::I64(value))
+    }
+}
+
+/// Convert interpreter arguments to the `i64`s a compiled function takes.
+fn int_args<const N: usize>(args: &[Value]) -> Result<[i64; N], String> {
+    let mut out = [0; N];
+    for (slot, arg) in out.iter_mut().zip(args) {
+        *slot = arg.as_i64().ok_or_else(|| {
+            format!(
+                "compiled functions take `i64` arguments, found `{}`",
+                crate::error::type_name(arg)
+            )
+        })?;
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::call::call_value;
+    use crate::frontends::RustFrontend;
+    use crate::{Environment, EvalContext};
+
+    fn rustc_available() -> bool {
+        Command::new("rustc").arg("--version").output().is_ok()
+    }
+
+    #[test]
+    fn test_emit_source() {
+        let body: syn::Block = syn::parse_str("{ a + b }").unwrap();
+        let func = FunctionValue::new("add".to_string(), vec!["a".into(), "b".into()], body);
+        let src = emit_source(&func, &RustFrontend::new()).unwrap();
+
+        assert!(src.contains("Rust frontend"));
+        assert!(src.contains("fn add (a : i64 , b : i64) -> i64"));
+        assert!(src.contains("extern \"C\" fn treebeard_entry (a : i64 , b : i64 , panicked"));
+    }
+
+    #[test]
+    fn test_invalid_names_rejected() {
+        let body: syn::Block = syn::parse_str("{ 0 }").unwrap();
+        for (name, param) in [("fn", "a"), ("f", "match"), ("r#f", "a"), ("f", "1a")] {
+            let func = FunctionValue::new(name.to_string(), vec![param.to_string()], body.clone());
+            let result = compile_function(&func, &RustFrontend::new());
+            assert!(
+                matches!(result, Err(EvalError::CompileError { .. })),
+                "{} {}",
+                name,
+                param
+            );
+        }
+    }
+
+    #[test]
+    fn test_too_many_params() {
+        let body: syn::Block = syn::parse_str("{ 0 }").unwrap();
+        let params = (0..5).map(|i| format!("p{}", i)).collect();
+        let func = FunctionValue::new("wide".to_string(), params, body);
+
+        let result = compile_function(&func, &RustFrontend::new());
+        assert!(matches!(result, Err(EvalError::CompileError { .. })));
+    }
+
+    #[test]
+    fn test_compile_and_call_add() {
+        if !rustc_available() {
+            return;
+        }
+
+        let body: syn::Block = syn::parse_str("{ a + b }").unwrap();
+        let func = FunctionValue::new("add".to_string(), vec!["a".into(), "b".into()], body);
+        let compiled = compile_function(&func, &RustFrontend::new()).unwrap();
+        let lib_path = compiled.lib_path.clone();
+        assert!(lib_path.exists());
+
+        let mut env = Environment::new();
+        let ctx = EvalContext::default();
+        let compiled = Value::CompiledFn(Arc::new(compiled));
+        let result = call_value(
+            compiled.clone(),
+            vec![Value::I64(2), Value::I64(3)],
+            &mut env,
+            &ctx,
+            None,
+        )
+        .unwrap();
+        assert_eq!(result, Value::I64(5));
+
+        // Overflow panics inside the library instead of wrapping
+        let result = call_value(
+            compiled.clone(),
+            vec![Value::I64(i64::MAX), Value::I64(1)],
+            &mut env,
+            &ctx,
+            None,
+        );
+        assert!(matches!(result, Err(EvalError::BuiltinError { .. })));
+
+        // The build directory goes with the last handle to the library
+        drop(compiled);
+        assert!(!lib_path.exists());
+    }
+}
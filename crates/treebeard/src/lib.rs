@@ -31,22 +31,36 @@ pub mod evaluator;
 pub mod expansion;
 pub mod frontend;
 pub mod frontends;
+pub mod interpreter;
+#[cfg(feature = "jit")]
+pub mod jit;
 pub mod macro_env;
 pub mod ownership;
+pub mod repl;
 pub mod template;
 pub mod value;
 
 // Re-export main types
 pub use context::EvalContext;
-pub use environment::{Binding, BindingMode, Environment, ScopeGuard};
+pub use environment::{Binding, BindingMode, Environment, PreludeBuilder, ScopeGuard};
 pub use error::{EnvironmentError, EvalError, Result, TreebeardError};
-pub use eval::{eval_block, eval_block_stmts, eval_expr, eval_stmt, ControlFlow, Evaluate};
-pub use frontend::{LanguageFrontend, MacroError, ParseError, ReplCommand, SourceLocation};
+pub use eval::{
+    eval_block, eval_block_stmts, eval_expr, eval_stmt, method_completions, ControlFlow, Evaluate,
+};
+pub use frontend::{
+    LanguageFrontend, MacroError, ParseError, ReplCommand, SourceExpr, SourceLocation,
+};
+pub use interpreter::Interpreter;
 pub use macro_env::{MacroBody, MacroDefinition, MacroEnvironment};
-pub use template::{Template, TemplateBindings, TemplateMetadata, TemplateNode};
+pub use repl::Repl;
+pub use template::{
+    syn_item_to_value, template_from_expr, value_to_syn_expr, value_to_syn_item, Template,
+    TemplateBindings, TemplateMetadata, TemplateNode,
+};
 pub use value::{
-    BuiltinFn, BuiltinFnPtr, ClosureValue, CompiledFn, EnumData, EnumValue, FunctionValue,
-    HashableValue, StructValue, Value, ValueRef, ValueRefMut,
+    BuiltinAllocFn, BuiltinControlFnPtr, BuiltinFn, BuiltinFnPtr, BuiltinHooks, ClosureValue,
+    CompiledFn, CompiledFnPtr, EnumData, EnumValue, FunctionValue, HashableValue, IterValue,
+    StructValue, Value, ValueRef, ValueRefMut,
 };
 
 /// Treebeard version
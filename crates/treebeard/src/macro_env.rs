@@ -26,6 +26,7 @@
 
 use indexmap::IndexMap;
 use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 /// Type alias for native macro transformation functions.
@@ -132,18 +133,29 @@ impl fmt::Debug for MacroBody {
 /// // Look up a macro
 /// assert!(env.get_macro("when").is_none());
 /// ```
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct MacroEnvironment {
     /// Macro definitions, indexed by name
     /// Using IndexMap to preserve definition order
     macros: IndexMap<String, Arc<MacroDefinition>>,
 
     /// Gensym counter for generating unique symbols (Stage 3.5)
-    gensym_counter: u64,
+    ///
+    /// Shared with child scopes and clones so their symbols never collide.
+    gensym_counter: Arc<AtomicU64>,
 
     /// Parent environment for nested scopes (Stage 3.4)
     /// Macros can be defined in local scopes
     parent: Option<Box<MacroEnvironment>>,
+
+    /// Maximum nesting of expansions in `expand_all`
+    max_expansion_depth: usize,
+}
+
+impl Default for MacroEnvironment {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl MacroEnvironment {
@@ -151,8 +163,17 @@ impl MacroEnvironment {
     pub fn new() -> Self {
         Self {
             macros: IndexMap::new(),
-            gensym_counter: 0,
+            gensym_counter: Arc::new(AtomicU64::new(0)),
             parent: None,
+            max_expansion_depth: 100,
+        }
+    }
+
+    /// Create a macro environment with a custom expansion depth limit.
+    pub fn with_max_expansion_depth(max_depth: usize) -> Self {
+        Self {
+            max_expansion_depth: max_depth,
+            ..Self::new()
         }
     }
 
@@ -163,7 +184,8 @@ impl MacroEnvironment {
     pub fn with_parent(parent: MacroEnvironment) -> Self {
         Self {
             macros: IndexMap::new(),
-            gensym_counter: parent.gensym_counter,
+            gensym_counter: parent.gensym_counter.clone(),
+            max_expansion_depth: parent.max_expansion_depth,
             parent: Some(Box::new(parent)),
         }
     }
@@ -228,7 +250,9 @@ impl MacroEnvironment {
     /// Call a macro with the given arguments and return the expanded result.
     ///
     /// This method handles all three macro body types:
-    /// - Native: Calls the native Rust function (expects syn::Item arguments)
+    /// - Native: Converts each argument to a `syn::Item`, calls the native
+    ///   Rust function, and returns the resulting items as a `Vec` of their
+    ///   Rust source strings
     /// - Template: Expands the template with bindings from arguments
     /// - UserDefined: Calls the user-defined function with Value arguments
     ///
@@ -253,13 +277,23 @@ impl MacroEnvironment {
             .ok_or_else(|| format!("Macro '{}' not found", name))?;
 
         match &macro_def.body {
-            MacroBody::Native(_) => {
-                // Native macros expect syn::Item arguments
-                // This is a simplified implementation - full implementation would
-                // convert Values to syn::Items
-                Err(format!(
-                    "Native macro '{}' cannot be expanded with Value arguments",
-                    name
+            MacroBody::Native(func) => {
+                // Arguments may be item source strings or the list forms
+                // understood by `value_to_syn_item` (`["fn", ...]`, `["const", ...]`)
+                let items = args
+                    .iter()
+                    .map(crate::template::value_to_syn_item)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| format!("Native macro '{}' argument: {}", name, e))?;
+
+                let output =
+                    func(&items).map_err(|e| format!("Native macro '{}' failed: {}", name, e))?;
+
+                Ok(crate::Value::vec(
+                    output
+                        .iter()
+                        .map(crate::template::syn_item_to_value)
+                        .collect(),
                 ))
             }
             MacroBody::Template(template) => {
@@ -279,9 +313,9 @@ impl MacroEnvironment {
                     bindings.bind(param.clone(), arg.clone());
                 }
 
-                // Expand the template
+                // Expand the template, drawing gensyms from this environment
                 template
-                    .expand(&bindings)
+                    .expand_with_gensym(&bindings, &mut |base| self.fresh_symbol(base))
                     .map_err(|e| format!("Template expansion failed: {}", e))
             }
             MacroBody::UserDefined(func) => {
@@ -291,6 +325,52 @@ impl MacroEnvironment {
         }
     }
 
+    /// Expand every macro invocation in a Value until none remain.
+    ///
+    /// A `Vec` whose first element is a string naming a macro is an
+    /// invocation; the remaining elements are its arguments. The output of
+    /// each expansion is expanded again, and nested lists are walked, so
+    /// macros may expand to other macro calls.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any expansion fails, or a macro-overflow error
+    /// if expansions nest deeper than the maximum expansion depth (for
+    /// example, a macro that expands to a call of itself).
+    pub fn expand_all(&self, value: &crate::Value) -> Result<crate::Value, String> {
+        self.expand_at_depth(value, 0)
+    }
+
+    fn expand_at_depth(&self, value: &crate::Value, depth: usize) -> Result<crate::Value, String> {
+        let crate::Value::Vec(items) = value else {
+            return Ok(value.clone());
+        };
+
+        if let Some((crate::Value::String(head), args)) = items.split_first() {
+            if self.has_macro(head) {
+                if depth >= self.max_expansion_depth {
+                    return Err(format!(
+                        "macro overflow: expanding '{}' exceeded maximum expansion depth ({})",
+                        head, self.max_expansion_depth
+                    ));
+                }
+                let expanded = self.expand_macro(head, args)?;
+                return self.expand_at_depth(&expanded, depth + 1);
+            }
+        }
+
+        let items = items
+            .iter()
+            .map(|item| self.expand_at_depth(item, depth))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(crate::Value::vec(items))
+    }
+
+    /// Get the maximum expansion depth.
+    pub fn max_expansion_depth(&self) -> usize {
+        self.max_expansion_depth
+    }
+
     /// Define a user-defined macro with a closure.
     ///
     /// This is a convenience method for creating UserDefined macros.
@@ -338,6 +418,87 @@ impl MacroEnvironment {
         self.define_macro(macro_def);
     }
 
+    /// Register template macros from parsed items.
+    ///
+    /// A macro definition is a `fn` marked `#[defmacro]`. Its parameters
+    /// become the macro parameters, and its body becomes a template in
+    /// which each parameter reference is an unquote placeholder:
+    ///
+    /// ```rust
+    /// use treebeard::{MacroEnvironment, Value};
+    ///
+    /// let items: syn::File = syn::parse_str(
+    ///     "#[defmacro] fn double(x: _) { x + x }",
+    /// ).unwrap();
+    ///
+    /// let mut env = MacroEnvironment::new();
+    /// assert_eq!(env.define_from_items(&items.items).unwrap(), 1);
+    ///
+    /// let expanded = env.expand_macro("double", &[Value::I64(4)]).unwrap();
+    /// assert_eq!(
+    ///     expanded,
+    ///     Value::vec(vec![Value::string("+"), Value::I64(4), Value::I64(4)])
+    /// );
+    /// ```
+    ///
+    /// Other items are ignored. Returns the number of macros registered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a marked function has a `self` or destructuring
+    /// parameter, or uses a parameter where it cannot be substituted.
+    pub fn define_from_items(&mut self, items: &[syn::Item]) -> Result<usize, String> {
+        let mut count = 0;
+
+        for item in items {
+            let syn::Item::Fn(item_fn) = item else {
+                continue;
+            };
+            if !item_fn
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident("defmacro"))
+            {
+                continue;
+            }
+
+            let name = item_fn.sig.ident.to_string();
+            let params = item_fn
+                .sig
+                .inputs
+                .iter()
+                .map(|input| match input {
+                    syn::FnArg::Typed(pat_type) => match pat_type.pat.as_ref() {
+                        syn::Pat::Ident(ident) => Ok(ident.ident.to_string()),
+                        _ => Err(format!("Macro '{}' has a destructuring parameter", name)),
+                    },
+                    syn::FnArg::Receiver(_) => Err(format!("Macro '{}' cannot take `self`", name)),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let body = syn::Expr::Block(syn::ExprBlock {
+                attrs: Vec::new(),
+                label: None,
+                block: item_fn.block.as_ref().clone(),
+            });
+            let root = crate::template::template_from_expr(&body, &params)
+                .map_err(|e| format!("Macro '{}': {}", name, e))?;
+            let template = crate::template::Template::with_metadata(
+                root,
+                crate::template::TemplateMetadata::new().with_macro_name(name.clone()),
+            );
+
+            self.define_macro(MacroDefinition::new(
+                name,
+                params,
+                MacroBody::Template(template),
+            ));
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
     /// Get all macro names defined in this environment (not including parent).
     pub fn macro_names(&self) -> Vec<&str> {
         self.macros.keys().map(|s| s.as_str()).collect()
@@ -367,14 +528,20 @@ impl MacroEnvironment {
     /// assert!(sym1.starts_with("temp_G"));
     /// ```
     pub fn gensym(&mut self, base: &str) -> String {
-        let id = self.gensym_counter;
-        self.gensym_counter += 1;
+        self.fresh_symbol(base)
+    }
+
+    /// Generate a unique symbol without needing exclusive access.
+    ///
+    /// Used by template expansion, which only borrows the environment.
+    fn fresh_symbol(&self, base: &str) -> String {
+        let id = self.gensym_counter.fetch_add(1, Ordering::Relaxed);
         format!("{}_G{}", base, id)
     }
 
     /// Reset the gensym counter (useful for testing).
     pub fn reset_gensym(&mut self) {
-        self.gensym_counter = 0;
+        self.gensym_counter.store(0, Ordering::Relaxed);
     }
 
     /// Get the number of macros defined (not including parent).
@@ -396,8 +563,9 @@ impl MacroEnvironment {
     pub fn clone_without_parent(&self) -> Self {
         Self {
             macros: self.macros.clone(),
-            gensym_counter: self.gensym_counter,
+            gensym_counter: Arc::new(AtomicU64::new(self.gensym_counter.load(Ordering::Relaxed))),
             parent: None,
+            max_expansion_depth: self.max_expansion_depth,
         }
     }
 }
@@ -408,7 +576,7 @@ impl fmt::Debug for MacroEnvironment {
             f,
             "MacroEnvironment {{ macros: {:?}, gensym_counter: {}, has_parent: {} }}",
             self.macro_names(),
-            self.gensym_counter,
+            self.gensym_counter.load(Ordering::Relaxed),
             self.parent.is_some()
         )
     }
@@ -812,4 +980,241 @@ mod tests {
             _ => panic!("Expected Vec with 3 elements"),
         }
     }
+
+    #[test]
+    fn test_expand_native_macro_with_values() {
+        let mut env = MacroEnvironment::new();
+        env.define_macro(MacroDefinition::new(
+            "in_module".to_string(),
+            vec!["items".to_string()],
+            MacroBody::Native(Arc::new(|items| {
+                Ok(vec![syn::parse_quote!(mod wrapper { #(#items)* })])
+            })),
+        ));
+
+        let args = [
+            Value::string("fn one() -> i64 { 1 }"),
+            Value::vec(vec![
+                Value::string("const"),
+                Value::string("TWO"),
+                Value::I64(2),
+            ]),
+        ];
+        let result = env.expand_macro("in_module", &args).unwrap();
+
+        let Value::Vec(items) = result else {
+            panic!("expected Vec, got {:?}", result);
+        };
+        assert_eq!(items.len(), 1);
+        match crate::template::value_to_syn_item(&items[0]).unwrap() {
+            syn::Item::Mod(m) => {
+                assert_eq!(m.ident, "wrapper");
+                assert_eq!(m.content.unwrap().1.len(), 2);
+            }
+            other => panic!("expected module, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_expand_native_macro_bad_argument() {
+        let mut env = MacroEnvironment::new();
+        env.define_macro(MacroDefinition::new(
+            "noop".to_string(),
+            vec![],
+            MacroBody::Native(Arc::new(|items| Ok(items.to_vec()))),
+        ));
+
+        let result = env.expand_macro("noop", &[Value::I64(1)]);
+        assert!(result.unwrap_err().contains("argument"));
+    }
+
+    #[test]
+    fn test_expand_all_nested_macros() {
+        let mut env = MacroEnvironment::new();
+        env.define_user_macro(
+            "double",
+            vec!["x".to_string()],
+            Arc::new(|args| {
+                Ok(Value::vec(vec![
+                    Value::string("+"),
+                    args[0].clone(),
+                    args[0].clone(),
+                ]))
+            }),
+        );
+        env.define_user_macro(
+            "quadruple",
+            vec!["x".to_string()],
+            Arc::new(|args| {
+                Ok(Value::vec(vec![
+                    Value::string("double"),
+                    Value::vec(vec![Value::string("double"), args[0].clone()]),
+                ]))
+            }),
+        );
+
+        let input = Value::vec(vec![Value::string("quadruple"), Value::string("n")]);
+        let result = env.expand_all(&input).unwrap();
+
+        let double_n = Value::vec(vec![
+            Value::string("+"),
+            Value::string("n"),
+            Value::string("n"),
+        ]);
+        assert_eq!(
+            result,
+            Value::vec(vec![Value::string("+"), double_n.clone(), double_n])
+        );
+    }
+
+    #[test]
+    fn test_expand_all_self_referential_macro_overflows() {
+        let mut env = MacroEnvironment::with_max_expansion_depth(16);
+        assert_eq!(env.max_expansion_depth(), 16);
+        env.define_user_macro(
+            "forever",
+            vec![],
+            Arc::new(|_| Ok(Value::vec(vec![Value::string("forever")]))),
+        );
+
+        let input = Value::vec(vec![Value::string("forever")]);
+        let err = env.expand_all(&input).unwrap_err();
+        assert!(err.contains("macro overflow"), "{}", err);
+        assert!(err.contains("'forever'"));
+    }
+
+    #[test]
+    fn test_child_inherits_max_expansion_depth() {
+        let parent = MacroEnvironment::with_max_expansion_depth(7);
+        let child = MacroEnvironment::with_parent(parent);
+        assert_eq!(child.max_expansion_depth(), 7);
+        assert_eq!(MacroEnvironment::default().max_expansion_depth(), 100);
+    }
+
+    #[test]
+    fn test_template_gensym_is_hygienic() {
+        let mut env = MacroEnvironment::new();
+        // (let tmp ,x (+ tmp tmp)) with `tmp` renamed
+        let template = Template::new(TemplateNode::list(vec![
+            TemplateNode::literal(Value::string("let")),
+            TemplateNode::gensym("tmp"),
+            TemplateNode::unquote("x"),
+            TemplateNode::list(vec![
+                TemplateNode::literal(Value::string("+")),
+                TemplateNode::gensym("tmp"),
+                TemplateNode::gensym("tmp"),
+            ]),
+        ]));
+        env.define_macro(MacroDefinition::new(
+            "twice".to_string(),
+            vec!["x".to_string()],
+            MacroBody::Template(template),
+        ));
+
+        let symbols = |value: &Value| -> Vec<Value> {
+            let Value::Vec(items) = value else {
+                panic!("expected Vec");
+            };
+            let Value::Vec(sum) = &items[3] else {
+                panic!("expected Vec");
+            };
+            vec![items[1].clone(), sum[1].clone(), sum[2].clone()]
+        };
+
+        let first = symbols(&env.expand_macro("twice", &[Value::string("tmp")]).unwrap());
+        let second = symbols(&env.expand_macro("twice", &[Value::string("tmp")]).unwrap());
+
+        // One symbol per expansion, distinct from the user's `tmp`
+        assert!(first.iter().all(|s| s == &first[0]));
+        assert!(second.iter().all(|s| s == &second[0]));
+        assert_ne!(first[0], Value::string("tmp"));
+        assert_ne!(first[0], second[0]);
+    }
+
+    #[test]
+    fn test_define_from_items_matches_hand_built_template() {
+        let file: syn::File = syn::parse_str(
+            "#[defmacro]
+             fn when(cond: _, body: _) {
+                 if cond { body } else { () }
+             }
+
+             fn not_a_macro() {}",
+        )
+        .unwrap();
+
+        let mut parsed = MacroEnvironment::new();
+        assert_eq!(parsed.define_from_items(&file.items).unwrap(), 1);
+        assert!(!parsed.has_macro("not_a_macro"));
+
+        let mut manual = MacroEnvironment::new();
+        manual.define_macro(MacroDefinition::new(
+            "when".to_string(),
+            vec!["cond".to_string(), "body".to_string()],
+            MacroBody::Template(Template::new(TemplateNode::list(vec![
+                TemplateNode::literal(Value::string("if")),
+                TemplateNode::unquote("cond"),
+                TemplateNode::unquote("body"),
+                TemplateNode::literal(Value::Unit),
+            ]))),
+        ));
+
+        let args = [Value::Bool(true), Value::I64(1)];
+        assert_eq!(
+            parsed.expand_macro("when", &args).unwrap(),
+            manual.expand_macro("when", &args).unwrap()
+        );
+        assert_eq!(parsed.get_macro("when").unwrap().params, ["cond", "body"]);
+    }
+
+    #[test]
+    fn test_define_from_items_substitutes_unary_operands() {
+        let file: syn::File = syn::parse_str("#[defmacro] fn neg(x: _) { -x }").unwrap();
+        let mut env = MacroEnvironment::new();
+        env.define_from_items(&file.items).unwrap();
+
+        let expanded = env.expand_macro("neg", &[Value::I64(4)]).unwrap();
+        assert_eq!(
+            expanded,
+            Value::vec(vec![Value::string("-"), Value::I64(4)])
+        );
+        let expr = crate::template::value_to_syn_expr(&expanded).unwrap();
+        assert_eq!(quote::quote!(#expr).to_string(), "- 4");
+    }
+
+    #[test]
+    fn test_define_from_items_rejects_unsubstitutable_params() {
+        let file: syn::File = syn::parse_str("#[defmacro] fn first(v: _) { v[0] }").unwrap();
+        let err = MacroEnvironment::new()
+            .define_from_items(&file.items)
+            .unwrap_err();
+        assert!(err.contains("`v`"), "{}", err);
+
+        // Names that are not parameters are kept as source
+        let file: syn::File = syn::parse_str("#[defmacro] fn first(v: _) { w[0] + v }").unwrap();
+        assert_eq!(
+            MacroEnvironment::new().define_from_items(&file.items),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn test_define_from_items_rejects_self() {
+        let file: syn::File = syn::parse_str("impl X { #[defmacro] fn m(&self) {} }").unwrap();
+        let syn::Item::Impl(imp) = &file.items[0] else {
+            panic!("expected impl");
+        };
+        let syn::ImplItem::Fn(method) = &imp.items[0] else {
+            panic!("expected fn");
+        };
+        let item = syn::Item::Fn(syn::ItemFn {
+            attrs: method.attrs.clone(),
+            vis: syn::Visibility::Inherited,
+            sig: method.sig.clone(),
+            block: Box::new(method.block.clone()),
+        });
+
+        let mut env = MacroEnvironment::new();
+        assert!(env.define_from_items(&[item]).is_err());
+    }
 }
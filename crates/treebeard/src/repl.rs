@@ -0,0 +1,227 @@
+//! Interactive read-eval-print sessions
+//!
+//! [`Repl`] feeds source to an [`Interpreter`] one line at a time. Lines
+//! with unbalanced delimiters are buffered until the input is complete,
+//! definitions persist between lines, and the result of the last
+//! evaluation is bound to `_`.
+
+use crate::{BindingMode, EvalError, Interpreter, Value};
+
+/// The name the previous result is bound to.
+const LAST_VALUE: &str = "_";
+
+/// An interactive session over an [`Interpreter`].
+///
+/// # Example
+///
+/// ```
+/// use treebeard::{Repl, Value};
+///
+/// let mut repl = Repl::new();
+/// repl.eval_line("fn sq(x: i64) -> i64 { x * x }").unwrap();
+/// assert_eq!(repl.eval_line("sq(7)").unwrap(), Value::I64(49));
+/// assert_eq!(repl.eval_line("_ + 1").unwrap(), Value::I64(50));
+/// ```
+#[derive(Debug, Default)]
+pub struct Repl {
+    interp: Interpreter,
+    history: Vec<String>,
+    pending: String,
+    last: Option<Value>,
+}
+
+impl Repl {
+    /// Create a session over a fresh interpreter.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a session over an existing interpreter.
+    pub fn with_interpreter(interp: Interpreter) -> Self {
+        Self {
+            interp,
+            ..Self::default()
+        }
+    }
+
+    /// Feed one line of input.
+    ///
+    /// If the input so far has unclosed delimiters, the line is buffered
+    /// and `NeedMoreInput` is returned; the next line continues it. Once
+    /// complete, the accumulated input is recorded in the history,
+    /// evaluated, and its result bound to `_`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NeedMoreInput` for incomplete input, or any error raised
+    /// while parsing or evaluating the completed input.
+    pub fn eval_line(&mut self, line: &str) -> Result<Value, EvalError> {
+        if !self.pending.is_empty() {
+            self.pending.push('\n');
+        }
+        self.pending.push_str(line);
+
+        if is_incomplete(&self.pending) {
+            return Err(EvalError::NeedMoreInput);
+        }
+
+        let src = std::mem::take(&mut self.pending);
+        if src.trim().is_empty() {
+            return Ok(Value::Unit);
+        }
+        self.history.push(src.clone());
+
+        let value = self.interp.eval_str(&src)?;
+        self.bind_last(value.clone());
+        Ok(value)
+    }
+
+    /// Whether a partial input is waiting for more lines.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Discard any partially entered input.
+    pub fn clear_pending(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Get the complete inputs evaluated so far, oldest first.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Get the result of the most recent successful evaluation.
+    pub fn last_value(&self) -> Option<&Value> {
+        self.last.as_ref()
+    }
+
+    /// Get the underlying interpreter.
+    pub fn interpreter(&self) -> &Interpreter {
+        &self.interp
+    }
+
+    /// Get the underlying interpreter mutably.
+    pub fn interpreter_mut(&mut self) -> &mut Interpreter {
+        &mut self.interp
+    }
+
+    fn bind_last(&mut self, value: Value) {
+        let env = self.interp.env_mut();
+        if env.assign(LAST_VALUE, value.clone()).is_err() {
+            env.define_with_mode(LAST_VALUE.to_string(), value.clone(), BindingMode::Mutable);
+        }
+        self.last = Some(value);
+    }
+}
+
+/// Whether `src` opens more delimiters than it closes.
+///
+/// String and character literals and `//` comments are skipped. Input
+/// that closes too many delimiters is not incomplete; the parser reports
+/// it instead.
+fn is_incomplete(src: &str) -> bool {
+    let mut depth: i64 = 0;
+    let mut chars = src.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            '"' => {
+                let mut closed = false;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => {
+                            closed = true;
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                if !closed {
+                    return true;
+                }
+            }
+            '\'' => {
+                // Skip `'x'` and `'\n'`; a lone `'` is a lifetime or label.
+                let mut lookahead = chars.clone();
+                let literal = match lookahead.next() {
+                    Some('\\') => lookahead.next().is_some() && lookahead.next() == Some('\''),
+                    Some(_) => lookahead.next() == Some('\''),
+                    None => false,
+                };
+                if literal {
+                    chars = lookahead;
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    depth > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definitions_persist_across_lines() {
+        let mut repl = Repl::new();
+        repl.eval_line("fn add(a: i64, b: i64) -> i64 { a + b }")
+            .unwrap();
+        assert_eq!(repl.eval_line("add(2, 3)").unwrap(), Value::I64(5));
+        assert_eq!(repl.history().len(), 2);
+    }
+
+    #[test]
+    fn test_underscore_is_previous_result() {
+        let mut repl = Repl::new();
+        repl.eval_line("20 + 1").unwrap();
+        assert_eq!(repl.last_value(), Some(&Value::I64(21)));
+        assert_eq!(repl.eval_line("_ * 2").unwrap(), Value::I64(42));
+        assert_eq!(repl.eval_line("_").unwrap(), Value::I64(42));
+    }
+
+    #[test]
+    fn test_underscore_before_any_result() {
+        let mut repl = Repl::new();
+        let result = repl.eval_line("_");
+        assert!(matches!(result, Err(EvalError::UndefinedVariable { .. })));
+    }
+
+    #[test]
+    fn test_unbalanced_braces_need_more_input() {
+        let mut repl = Repl::new();
+        let result = repl.eval_line("fn inc(x: i64) -> i64 {");
+        assert!(matches!(result, Err(EvalError::NeedMoreInput)));
+        assert!(repl.is_pending());
+
+        repl.eval_line("    x + 1").unwrap_err();
+        repl.eval_line("}").unwrap();
+        assert!(!repl.is_pending());
+        assert_eq!(repl.history().len(), 1);
+        assert_eq!(repl.eval_line("inc(1)").unwrap(), Value::I64(2));
+    }
+
+    #[test]
+    fn test_is_incomplete() {
+        assert!(is_incomplete("foo(1, "));
+        assert!(is_incomplete("\"open string"));
+        assert!(!is_incomplete("\"{\""));
+        assert!(!is_incomplete("'{'"));
+        assert!(!is_incomplete("1 // {"));
+        assert!(!is_incomplete("1 }"));
+    }
+}
@@ -18,6 +18,10 @@
 //! - **Unquote (,)**: Evaluates expression and substitutes single value
 //! - **Unquote-splicing (,@)**: Evaluates expression and splices sequence
 //!
+//! Quasiquotes nest: an inner quasiquote raises the depth by one, and
+//! unquotes only substitute at depth 0. Deeper unquotes are kept as literal
+//! `(unquote name)` forms for the generated code to expand later.
+//!
 //! # Example (Conceptual)
 //!
 //! ```text
@@ -31,8 +35,10 @@
 //! This is part of Phase 3: Macro System (Stage 3.2)
 
 use crate::{EvalError, Value};
+use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
+use syn::parse::Parser;
 
 /// A template for constructing `syn` AST with placeholders.
 ///
@@ -80,6 +86,46 @@ impl Template {
     pub fn expand(&self, bindings: &TemplateBindings) -> Result<Value, EvalError> {
         self.root.expand(bindings)
     }
+
+    /// Expand the template, generating fresh symbols with `gensym`.
+    ///
+    /// `gensym` is called once per distinct [`TemplateNode::Gensym`] base in
+    /// this expansion; every occurrence of that base gets the same symbol.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvalError` under the same conditions as [`expand`](Self::expand).
+    pub fn expand_with_gensym(
+        &self,
+        bindings: &TemplateBindings,
+        gensym: &mut dyn FnMut(&str) -> String,
+    ) -> Result<Value, EvalError> {
+        let mut state = ExpandState {
+            bindings,
+            symbols: HashMap::new(),
+            gensym,
+        };
+        self.root.expand_at(&mut state, 0)
+    }
+}
+
+/// Per-expansion state: bindings plus the symbols generated so far.
+struct ExpandState<'a> {
+    bindings: &'a TemplateBindings,
+    symbols: HashMap<String, String>,
+    gensym: &'a mut dyn FnMut(&str) -> String,
+}
+
+impl ExpandState<'_> {
+    /// The symbol for `base` in this expansion, generated on first use.
+    fn symbol(&mut self, base: &str) -> String {
+        if let Some(symbol) = self.symbols.get(base) {
+            return symbol.clone();
+        }
+        let symbol = (self.gensym)(base);
+        self.symbols.insert(base.to_string(), symbol.clone());
+        symbol
+    }
 }
 
 impl fmt::Debug for Template {
@@ -154,6 +200,21 @@ pub enum TemplateNode {
     /// During expansion, this becomes a Vec<Value>.
     List(Vec<TemplateNode>),
 
+    /// Hygienic symbol - replaced by a generated unique name
+    ///
+    /// Every `Gensym` with the same base in one expansion becomes the same
+    /// symbol, and separate expansions get different symbols, so names a
+    /// macro introduces cannot capture the caller's variables.
+    /// Example: `tmp` → `tmp_G7`
+    Gensym(String),
+
+    /// Nested quasiquote - raises the quoting depth for its contents
+    ///
+    /// Unquotes inside are preserved rather than substituted, and the node
+    /// itself expands to a `(quasiquote ...)` form.
+    /// Example: `` `(a ,x) `` inside a template keeps `,x` for later
+    Quasiquote(Box<TemplateNode>),
+
     /// Quoted item - wraps a syn::Item directly
     ///
     /// This is used when the frontend has already parsed to syn AST
@@ -182,6 +243,16 @@ impl TemplateNode {
         TemplateNode::List(nodes)
     }
 
+    /// Create a hygienic symbol node.
+    pub fn gensym(base: impl Into<String>) -> Self {
+        TemplateNode::Gensym(base.into())
+    }
+
+    /// Create a nested quasiquote node.
+    pub fn quasiquote(node: TemplateNode) -> Self {
+        TemplateNode::Quasiquote(Box::new(node))
+    }
+
     /// Create an item template node.
     pub fn item(item: syn::Item) -> Self {
         // ALLOW: syn::Item is Send + Sync (it's just AST data),
@@ -199,10 +270,44 @@ impl TemplateNode {
     /// # Errors
     ///
     /// Returns `EvalError` if expansion fails.
+    ///
+    /// Gensym nodes are numbered from zero, so their symbols are only
+    /// unique within this one expansion; use
+    /// [`Template::expand_with_gensym`] for symbols unique across expansions.
     pub fn expand(&self, bindings: &TemplateBindings) -> Result<Value, EvalError> {
+        let mut counter = 0;
+        let mut gensym = |base: &str| {
+            counter += 1;
+            format!("{}_G{}", base, counter - 1)
+        };
+        let mut state = ExpandState {
+            bindings,
+            symbols: HashMap::new(),
+            gensym: &mut gensym,
+        };
+        self.expand_at(&mut state, 0)
+    }
+
+    /// Expand this node at a quasiquote nesting depth.
+    ///
+    /// Placeholders are only substituted at depth 0.
+    fn expand_at(&self, state: &mut ExpandState<'_>, depth: usize) -> Result<Value, EvalError> {
+        let bindings = state.bindings;
         match self {
             TemplateNode::Literal(value) => Ok(value.clone()),
 
+            TemplateNode::Gensym(base) => Ok(Value::string(state.symbol(base))),
+
+            TemplateNode::Quasiquote(inner) => Ok(Value::vec(vec![
+                Value::string("quasiquote"),
+                inner.expand_at(state, depth + 1)?,
+            ])),
+
+            TemplateNode::Unquote(name) if depth > 0 => Ok(preserved("unquote", name)),
+            TemplateNode::UnquoteSplicing(name) if depth > 0 => {
+                Ok(preserved("unquote-splicing", name))
+            }
+
             TemplateNode::Unquote(name) => {
                 bindings.get(name).ok_or_else(|| EvalError::TemplateError {
                     message: format!("Unquote placeholder '{}' not found in bindings", name),
@@ -224,7 +329,7 @@ impl TemplateNode {
 
                 for node in nodes {
                     match node {
-                        TemplateNode::UnquoteSplicing(name) => {
+                        TemplateNode::UnquoteSplicing(name) if depth == 0 => {
                             // Look up the value
                             let value =
                                 bindings.get(name).ok_or_else(|| EvalError::TemplateError {
@@ -241,7 +346,7 @@ impl TemplateNode {
                         }
                         _ => {
                             // Regular node - just expand and add
-                            let expanded = node.expand(bindings)?;
+                            let expanded = node.expand_at(state, depth)?;
                             result.push(expanded);
                         }
                     }
@@ -250,16 +355,16 @@ impl TemplateNode {
                 Ok(Value::vec(result))
             }
 
-            TemplateNode::Item(item) => {
-                // Items are wrapped in a special value type
-                // This is a placeholder - in practice, frontends will need to
-                // convert syn::Item to appropriate Value representations
-                Ok(Value::String(Arc::new(format!("{:?}", item))))
-            }
+            TemplateNode::Item(item) => Ok(syn_item_to_value(item)),
         }
     }
 }
 
+/// A placeholder kept as literal structure inside a nested quasiquote.
+fn preserved(form: &str, name: &str) -> Value {
+    Value::vec(vec![Value::string(form), Value::string(name)])
+}
+
 impl fmt::Debug for TemplateNode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -267,6 +372,8 @@ impl fmt::Debug for TemplateNode {
             TemplateNode::Unquote(name) => write!(f, "Unquote({})", name),
             TemplateNode::UnquoteSplicing(name) => write!(f, "Splice({})", name),
             TemplateNode::List(nodes) => write!(f, "List({:?})", nodes),
+            TemplateNode::Gensym(base) => write!(f, "Gensym({})", base),
+            TemplateNode::Quasiquote(node) => write!(f, "Quasiquote({:?})", node),
             TemplateNode::Item(_) => write!(f, "Item(<syn>)"),
         }
     }
@@ -359,6 +466,431 @@ impl ValueExt for Value {
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════
+// Value → syn Conversion
+// ═══════════════════════════════════════════════════════════════════════
+//
+// Expanded templates are list-shaped Values. A `Vec` whose first element is
+// a string is a form, `[head, args...]`:
+//
+//   ["if", cond, then]            if cond { then }
+//   ["if", cond, then, else]      if cond { then } else { else }
+//   ["block", stmts...]           { stmts... }   (also "do", "progn")
+//   ["let", name, value]          let name = value;   (in blocks)
+//   ["lit", value]                a literal; strings become string literals
+//   ["call", f, args...]          f(args...)
+//   [op, a, b]                    a op b   for binary operators like "+"
+//   [name, args...]               name(args...)
+//
+// Any other string is parsed as Rust source, so identifiers, paths, and
+// whole expressions can be spliced in as text. Integers, floats, bools,
+// chars, and unit become literals.
+//
+// Items use the forms:
+//
+//   ["fn", name, [params...], body...]   fn name(params: _) { body... }
+//   ["const", name, value]               const name: _ = value;
+//
+// or a string holding the item's Rust source.
+
+/// Convert an expanded template Value into a `syn` expression.
+///
+/// # Errors
+///
+/// Returns `TemplateError` if the Value does not describe an expression.
+pub fn value_to_syn_expr(value: &Value) -> Result<syn::Expr, EvalError> {
+    match value {
+        Value::String(src) => parse_source(src, "expression"),
+        Value::Vec(items) => match items.split_first() {
+            Some((Value::String(head), args)) => form_to_expr(head, args),
+            Some(_) => Err(template_error(format!(
+                "form must start with a name, got {:?}",
+                items[0]
+            ))),
+            None => Ok(syn::parse_quote!(())),
+        },
+        other => literal_to_expr(other),
+    }
+}
+
+/// Convert an expanded template Value into a `syn` item.
+///
+/// # Errors
+///
+/// Returns `TemplateError` if the Value does not describe an item.
+pub fn value_to_syn_item(value: &Value) -> Result<syn::Item, EvalError> {
+    let Value::Vec(items) = value else {
+        return match value {
+            Value::String(src) => parse_source(src, "item"),
+            other => Err(template_error(format!("expected item, got {:?}", other))),
+        };
+    };
+
+    match items.as_slice() {
+        [Value::String(head), Value::String(name), Value::Vec(params), body @ ..]
+            if head.as_str() == "fn" =>
+        {
+            let name = ident(name)?;
+            let params = params
+                .iter()
+                .map(|p| match p {
+                    Value::String(p) => ident(p),
+                    other => Err(template_error(format!(
+                        "fn parameter must be a name, got {:?}",
+                        other
+                    ))),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let body = values_to_block(body)?;
+            Ok(syn::parse_quote!(fn #name(#(#params: _),*) #body))
+        }
+        [Value::String(head), Value::String(name), value] if head.as_str() == "const" => {
+            let name = ident(name)?;
+            let value = value_to_syn_expr(value)?;
+            Ok(syn::parse_quote!(const #name: _ = #value;))
+        }
+        _ => Err(template_error(format!(
+            "unrecognised item form {:?}",
+            value
+        ))),
+    }
+}
+
+/// Convert a `syn` item to a Value holding its Rust source.
+///
+/// This is the inverse of [`value_to_syn_item`] for string Values.
+pub fn syn_item_to_value(item: &syn::Item) -> Value {
+    Value::string(quote::quote!(#item).to_string())
+}
+
+/// Build an expression from a `[head, args...]` form.
+fn form_to_expr(head: &str, args: &[Value]) -> Result<syn::Expr, EvalError> {
+    let exprs = || {
+        args.iter()
+            .map(value_to_syn_expr)
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    match (head, args) {
+        ("if", [cond, then]) => {
+            let cond = value_to_syn_expr(cond)?;
+            let then = value_to_block(then)?;
+            Ok(syn::parse_quote!(if #cond #then))
+        }
+        ("if", [cond, then, otherwise]) => {
+            let cond = value_to_syn_expr(cond)?;
+            let then = value_to_block(then)?;
+            let otherwise = value_to_block(otherwise)?;
+            Ok(syn::parse_quote!(if #cond #then else #otherwise))
+        }
+        ("block" | "do" | "progn", stmts) => {
+            let block = values_to_block(stmts)?;
+            Ok(syn::parse_quote!(#block))
+        }
+        ("lit", [value]) => match value {
+            Value::String(s) => {
+                let lit = syn::LitStr::new(s, proc_macro2::Span::call_site());
+                Ok(syn::parse_quote!(#lit))
+            }
+            other => literal_to_expr(other),
+        },
+        ("call", [f, ..]) => {
+            let f = value_to_syn_expr(f)?;
+            let args = exprs()?.split_off(1);
+            Ok(syn::parse_quote!(#f(#(#args),*)))
+        }
+        (op @ ("-" | "!" | "*"), [value]) => {
+            let op: syn::UnOp = syn::parse_str(op).map_err(|e| template_error(e.to_string()))?;
+            let value = operand(value_to_syn_expr(value)?);
+            Ok(syn::parse_quote!(#op #value))
+        }
+        (op, [left, right]) if is_binary_op(op) => {
+            let op: syn::BinOp = syn::parse_str(op).map_err(|e| template_error(e.to_string()))?;
+            let left = operand(value_to_syn_expr(left)?);
+            let right = operand(value_to_syn_expr(right)?);
+            Ok(syn::parse_quote!(#left #op #right))
+        }
+        (name, _) => {
+            let f: syn::ExprPath = parse_source(name, "function name")?;
+            let args = exprs()?;
+            Ok(syn::parse_quote!(#f(#(#args),*)))
+        }
+    }
+}
+
+/// Parenthesize an operand unless it is atomic, so nested forms keep
+/// their structure regardless of operator precedence.
+fn operand(expr: syn::Expr) -> syn::Expr {
+    match expr {
+        syn::Expr::Lit(_)
+        | syn::Expr::Path(_)
+        | syn::Expr::Call(_)
+        | syn::Expr::MethodCall(_)
+        | syn::Expr::Paren(_)
+        | syn::Expr::Tuple(_) => expr,
+        other => syn::parse_quote!((#other)),
+    }
+}
+
+/// Convert a Value to a block, reusing `block` forms rather than nesting.
+fn value_to_block(value: &Value) -> Result<syn::Block, EvalError> {
+    match value {
+        Value::Vec(items) if matches!(items.first(), Some(Value::String(h)) if matches!(h.as_str(), "block" | "do" | "progn")) => {
+            values_to_block(&items[1..])
+        }
+        other => values_to_block(std::slice::from_ref(other)),
+    }
+}
+
+/// Convert a sequence of statement Values to a block whose last expression
+/// is its value.
+fn values_to_block(values: &[Value]) -> Result<syn::Block, EvalError> {
+    let mut stmts = Vec::with_capacity(values.len());
+    for (i, value) in values.iter().enumerate() {
+        let is_last = i + 1 == values.len();
+        stmts.push(value_to_stmt(value, is_last)?);
+    }
+    Ok(syn::Block {
+        brace_token: Default::default(),
+        stmts,
+    })
+}
+
+/// Convert a statement Value; `let` forms become local bindings.
+fn value_to_stmt(value: &Value, is_last: bool) -> Result<syn::Stmt, EvalError> {
+    if let Value::Vec(items) = value {
+        if let [Value::String(head), Value::String(name), init] = items.as_slice() {
+            if head.as_str() == "let" {
+                let pat: syn::Pat = syn::Pat::parse_single
+                    .parse_str(name)
+                    .map_err(|e| template_error(e.to_string()))?;
+                let init = value_to_syn_expr(init)?;
+                return Ok(syn::parse_quote!(let #pat = #init;));
+            }
+        }
+    }
+
+    let expr = value_to_syn_expr(value)?;
+    Ok(if is_last {
+        syn::Stmt::Expr(expr, None)
+    } else {
+        syn::Stmt::Expr(expr, Some(Default::default()))
+    })
+}
+
+/// Convert a scalar Value to a literal expression.
+fn literal_to_expr(value: &Value) -> Result<syn::Expr, EvalError> {
+    let span = proc_macro2::Span::call_site();
+    let lit: syn::Lit = match value {
+        Value::Unit => return Ok(syn::parse_quote!(())),
+        Value::Bool(b) => syn::LitBool::new(*b, span).into(),
+        Value::Char(c) => syn::LitChar::new(*c, span).into(),
+        Value::String(s) => syn::LitStr::new(s, span).into(),
+        Value::F64(f) => syn::LitFloat::new(&format!("{:?}", f), span).into(),
+        other => match other.as_i64() {
+            Some(n) if n < 0 => {
+                let lit = syn::LitInt::new(&n.unsigned_abs().to_string(), span);
+                return Ok(syn::parse_quote!(-#lit));
+            }
+            Some(n) => syn::LitInt::new(&n.to_string(), span).into(),
+            None => {
+                return Err(template_error(format!(
+                    "cannot convert {:?} to an expression",
+                    other
+                )))
+            }
+        },
+    };
+    Ok(syn::parse_quote!(#lit))
+}
+
+fn is_binary_op(op: &str) -> bool {
+    matches!(
+        op,
+        "+" | "-"
+            | "*"
+            | "/"
+            | "%"
+            | "=="
+            | "!="
+            | "<"
+            | "<="
+            | ">"
+            | ">="
+            | "&&"
+            | "||"
+            | "&"
+            | "|"
+            | "^"
+            | "<<"
+            | ">>"
+    )
+}
+
+fn ident(name: &str) -> Result<syn::Ident, EvalError> {
+    syn::parse_str(name).map_err(|_| template_error(format!("`{}` is not an identifier", name)))
+}
+
+fn parse_source<T: syn::parse::Parse>(src: &str, what: &str) -> Result<T, EvalError> {
+    syn::parse_str(src).map_err(|e| template_error(format!("invalid {} `{}`: {}", what, src, e)))
+}
+
+fn template_error(message: String) -> EvalError {
+    EvalError::TemplateError {
+        message,
+        span: None,
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// syn → Template Conversion
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Build a template from a macro body expression.
+///
+/// The expression is translated into the list forms understood by
+/// [`value_to_syn_expr`], with every reference to a name in `params`
+/// becoming an unquote placeholder. Expressions without a list form are
+/// kept as Rust source strings.
+///
+/// # Errors
+///
+/// Returns `TemplateError` if a name in `params` appears inside an
+/// expression kept as source, where it could not be substituted.
+pub fn template_from_expr(expr: &syn::Expr, params: &[String]) -> Result<TemplateNode, EvalError> {
+    let lit = |s: &str| TemplateNode::literal(Value::string(s));
+    let convert = |e: &syn::Expr| template_from_expr(e, params);
+
+    let node = match expr {
+        syn::Expr::Path(path) if path.qself.is_none() => match path.path.get_ident() {
+            Some(ident) if params.iter().any(|p| ident == p) => {
+                TemplateNode::unquote(ident.to_string())
+            }
+            _ => TemplateNode::literal(Value::string(quote::quote!(#path).to_string())),
+        },
+
+        syn::Expr::Lit(lit_expr) => match &lit_expr.lit {
+            syn::Lit::Str(s) => TemplateNode::list(vec![
+                lit("lit"),
+                TemplateNode::literal(Value::string(s.value())),
+            ]),
+            syn::Lit::Int(i) => match i.base10_parse::<i64>() {
+                Ok(n) => TemplateNode::literal(Value::I64(n)),
+                Err(_) => TemplateNode::literal(Value::string(i.to_string())),
+            },
+            syn::Lit::Float(f) => match f.base10_parse::<f64>() {
+                Ok(n) => TemplateNode::literal(Value::F64(n)),
+                Err(_) => TemplateNode::literal(Value::string(f.to_string())),
+            },
+            syn::Lit::Bool(b) => TemplateNode::literal(Value::Bool(b.value)),
+            syn::Lit::Char(c) => TemplateNode::literal(Value::Char(c.value())),
+            other => TemplateNode::literal(Value::string(quote::quote!(#other).to_string())),
+        },
+
+        syn::Expr::Tuple(tuple) if tuple.elems.is_empty() => TemplateNode::literal(Value::Unit),
+
+        syn::Expr::Paren(paren) => convert(&paren.expr)?,
+
+        syn::Expr::Unary(unary) => {
+            let op = &unary.op;
+            TemplateNode::list(vec![
+                lit(&quote::quote!(#op).to_string()),
+                convert(&unary.expr)?,
+            ])
+        }
+
+        syn::Expr::Binary(binary) => {
+            let op = &binary.op;
+            TemplateNode::list(vec![
+                lit(&quote::quote!(#op).to_string()),
+                convert(&binary.left)?,
+                convert(&binary.right)?,
+            ])
+        }
+
+        syn::Expr::If(if_expr) => {
+            let mut nodes = vec![
+                lit("if"),
+                convert(&if_expr.cond)?,
+                template_from_block(&if_expr.then_branch, params)?,
+            ];
+            if let Some((_, otherwise)) = &if_expr.else_branch {
+                nodes.push(convert(otherwise)?);
+            }
+            TemplateNode::list(nodes)
+        }
+
+        syn::Expr::Block(block) => template_from_block(&block.block, params)?,
+
+        syn::Expr::Call(call) => {
+            let mut nodes = match call.func.as_ref() {
+                syn::Expr::Path(path) if path.path.get_ident().is_some() => {
+                    vec![convert(&call.func)?]
+                }
+                func => vec![lit("call"), convert(func)?],
+            };
+            for arg in &call.args {
+                nodes.push(convert(arg)?);
+            }
+            TemplateNode::list(nodes)
+        }
+
+        other => source_literal(quote::quote!(#other), params)?,
+    };
+    Ok(node)
+}
+
+/// Build a template from a block; a lone trailing expression is unwrapped.
+fn template_from_block(block: &syn::Block, params: &[String]) -> Result<TemplateNode, EvalError> {
+    if let [syn::Stmt::Expr(expr, None)] = block.stmts.as_slice() {
+        return template_from_expr(expr, params);
+    }
+
+    let mut nodes = vec![TemplateNode::literal(Value::string("block"))];
+    for stmt in &block.stmts {
+        match stmt {
+            syn::Stmt::Local(syn::Local {
+                pat,
+                init: Some(init),
+                ..
+            }) => {
+                nodes.push(TemplateNode::list(vec![
+                    TemplateNode::literal(Value::string("let")),
+                    TemplateNode::literal(Value::string(quote::quote!(#pat).to_string())),
+                    template_from_expr(&init.expr, params)?,
+                ]));
+            }
+            syn::Stmt::Expr(expr, _) => nodes.push(template_from_expr(expr, params)?),
+            other => nodes.push(source_literal(quote::quote!(#other), params)?),
+        }
+    }
+    Ok(TemplateNode::list(nodes))
+}
+
+/// Keep `tokens` as a Rust source string, refusing any that mention a
+/// parameter, which would otherwise be left unsubstituted.
+fn source_literal(
+    tokens: proc_macro2::TokenStream,
+    params: &[String],
+) -> Result<TemplateNode, EvalError> {
+    if let Some(param) = params.iter().find(|p| mentions(&tokens, p)) {
+        return Err(template_error(format!(
+            "cannot substitute parameter `{}` into `{}`",
+            param, tokens
+        )));
+    }
+    Ok(TemplateNode::literal(Value::string(tokens.to_string())))
+}
+
+/// Whether `tokens` contain the identifier `name`, at any depth.
+fn mentions(tokens: &proc_macro2::TokenStream, name: &str) -> bool {
+    tokens.clone().into_iter().any(|tree| match tree {
+        proc_macro2::TokenTree::Ident(ident) => ident == name,
+        proc_macro2::TokenTree::Group(group) => mentions(&group.stream(), name),
+        _ => false,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -633,6 +1165,76 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_template_nested_quasiquote() {
+        // Simulate: `(defmacro m (y) `(list ,y ,x))` where `x` is bound now
+        // but the inner `,y` belongs to the generated macro.
+        let template = Template::new(TemplateNode::list(vec![
+            TemplateNode::literal(Value::string("defmacro")),
+            TemplateNode::unquote("x"),
+            TemplateNode::quasiquote(TemplateNode::list(vec![
+                TemplateNode::literal(Value::string("list")),
+                TemplateNode::unquote("y"),
+                TemplateNode::splice("ys"),
+            ])),
+        ]));
+
+        let mut bindings = TemplateBindings::new();
+        bindings.bind("x", Value::I64(1));
+        bindings.bind("y", Value::I64(2));
+        let result = template.expand(&bindings).unwrap();
+
+        let s = Value::string;
+        assert_eq!(
+            result,
+            Value::vec(vec![
+                s("defmacro"),
+                Value::I64(1),
+                Value::vec(vec![
+                    s("quasiquote"),
+                    Value::vec(vec![
+                        s("list"),
+                        Value::vec(vec![s("unquote"), s("y")]),
+                        Value::vec(vec![s("unquote-splicing"), s("ys")]),
+                    ]),
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_template_gensym_once_per_expansion() {
+        let template = Template::new(TemplateNode::list(vec![
+            TemplateNode::gensym("tmp"),
+            TemplateNode::gensym("tmp"),
+            TemplateNode::gensym("acc"),
+        ]));
+
+        let mut next = 0;
+        let mut gensym = |base: &str| {
+            next += 1;
+            format!("{}_{}", base, next)
+        };
+        let result = template
+            .expand_with_gensym(&TemplateBindings::new(), &mut gensym)
+            .unwrap();
+
+        assert_eq!(
+            result,
+            Value::vec(vec![
+                Value::string("tmp_1"),
+                Value::string("tmp_1"),
+                Value::string("acc_2"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_template_quasiquote_debug() {
+        let node = TemplateNode::quasiquote(TemplateNode::unquote("x"));
+        assert_eq!(format!("{:?}", node), "Quasiquote(Unquote(x))");
+    }
+
     #[test]
     fn test_value_as_sequence_vec() {
         let v = Value::vec(vec![Value::I64(1), Value::I64(2)]);
@@ -660,4 +1262,96 @@ mod tests {
         let result = v.as_sequence();
         assert!(result.is_err());
     }
+
+    fn eval_expr(expr: &syn::Expr, env: &mut crate::Environment) -> Value {
+        crate::eval_expr(expr, env, &crate::EvalContext::default()).unwrap()
+    }
+
+    #[test]
+    fn test_when_macro_expands_to_runnable_expr() {
+        let mut macros = crate::MacroEnvironment::new();
+        macros.define_macro(crate::MacroDefinition::new(
+            "when".to_string(),
+            vec!["cond".to_string(), "body".to_string()],
+            crate::MacroBody::Template(Template::new(TemplateNode::list(vec![
+                TemplateNode::literal(Value::string("if")),
+                TemplateNode::unquote("cond"),
+                TemplateNode::unquote("body"),
+                TemplateNode::literal(Value::I64(0)),
+            ]))),
+        ));
+
+        let cond = Value::vec(vec![Value::string(">"), Value::string("x"), Value::I64(1)]);
+        let expanded = macros
+            .expand_macro("when", &[cond, Value::I64(42)])
+            .unwrap();
+        let expr = value_to_syn_expr(&expanded).unwrap();
+
+        let mut env = crate::Environment::new();
+        env.define("x".to_string(), Value::I64(5));
+        assert_eq!(eval_expr(&expr, &mut env), Value::I64(42));
+        env.define("x".to_string(), Value::I64(0));
+        assert_eq!(eval_expr(&expr, &mut env), Value::I64(0));
+    }
+
+    #[test]
+    fn test_value_to_syn_expr_forms() {
+        let s = Value::string;
+        let cases = [
+            (Value::I64(-3), "- 3"),
+            (Value::vec(vec![s("lit"), s("hi")]), "\"hi\""),
+            (Value::vec(vec![s("+"), s("a"), Value::I64(1)]), "a + 1"),
+            (Value::vec(vec![s("max"), s("a"), s("b")]), "max (a , b)"),
+            (
+                Value::vec(vec![
+                    s("block"),
+                    Value::vec(vec![s("let"), s("y"), Value::I64(2)]),
+                    s("y"),
+                ]),
+                "{ let y = 2 ; y }",
+            ),
+        ];
+        for (value, expected) in cases {
+            let expr = value_to_syn_expr(&value).unwrap();
+            assert_eq!(quote::quote!(#expr).to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_value_to_syn_expr_invalid() {
+        assert!(value_to_syn_expr(&Value::string("let = ;")).is_err());
+        assert!(value_to_syn_expr(&Value::vec(vec![Value::I64(1)])).is_err());
+    }
+
+    #[test]
+    fn test_value_to_syn_item_fn() {
+        let s = Value::string;
+        let value = Value::vec(vec![
+            s("fn"),
+            s("add"),
+            Value::vec(vec![s("a"), s("b")]),
+            Value::vec(vec![s("+"), s("a"), s("b")]),
+        ]);
+        let item = value_to_syn_item(&value).unwrap();
+        assert!(matches!(item, syn::Item::Fn(_)));
+
+        let mut env = crate::Environment::new();
+        crate::eval::item::eval_item(&item, &mut env, &crate::EvalContext::default()).unwrap();
+        let call: syn::Expr = syn::parse_str("add(2, 3)").unwrap();
+        assert_eq!(eval_expr(&call, &mut env), Value::I64(5));
+    }
+
+    #[test]
+    fn test_item_node_round_trips() {
+        let node = TemplateNode::item(syn::parse_quote!(
+            fn one() -> i64 {
+                1
+            }
+        ));
+        let value = node.expand(&TemplateBindings::new()).unwrap();
+        assert!(matches!(
+            value_to_syn_item(&value).unwrap(),
+            syn::Item::Fn(_)
+        ));
+    }
 }
@@ -7,6 +7,16 @@ use super::Value;
 /// Type alias for builtin function pointers to reduce complexity
 pub type BuiltinFnPtr = Arc<dyn Fn(&[Value]) -> Result<Value, String> + Send + Sync>;
 
+/// Type alias for control builtins, whose errors are raised as they are
+pub type BuiltinControlFnPtr =
+    Arc<dyn Fn(&[Value]) -> Result<Value, crate::EvalError> + Send + Sync>;
+
+/// Type alias for estimates of the heap bytes a builtin call allocates
+pub type BuiltinAllocFn = Arc<dyn Fn(&[Value]) -> usize + Send + Sync>;
+
+/// Type alias for the entry point of a loaded compiled function
+pub type CompiledFnPtr = Arc<dyn Fn(&[Value]) -> Result<Value, String> + Send + Sync>;
+
 /// A user-defined function parsed from syn::ItemFn.
 ///
 /// Stores the AST directly for interpretation.
@@ -15,14 +25,34 @@ pub struct FunctionValue {
     /// Function name
     pub name: String,
 
-    /// Parameter names (types are erased at runtime)
+    /// Parameter names
     pub params: Vec<String>,
 
+    /// Declared parameter types, as written (`Self` for the receiver).
+    /// Arguments are coerced to these on call; empty if not recorded.
+    pub param_types: Vec<String>,
+
+    /// Declared return type, as written. The result is coerced to it;
+    /// `None` if there is none or it was not recorded.
+    pub return_type: Option<String>,
+
     /// The function body (stored as syn AST)
     pub body: Arc<syn::Block>,
 
     /// Number of times this function has been called (for JIT heuristics)
     pub call_count: u64,
+
+    /// Whether this is a method taking `&mut self` (its `self` is written
+    /// back to the receiver after the call)
+    pub mut_self: bool,
+
+    /// Whether the last parameter collects any extra arguments into a `Vec`
+    pub variadic: bool,
+
+    /// The item scope the function was defined in (see
+    /// [`Environment::item_scope`](crate::Environment::item_scope)). Its
+    /// body sees the items of that scope; `0` for top-level functions.
+    pub item_scope: u64,
 }
 
 impl FunctionValue {
@@ -31,19 +61,68 @@ impl FunctionValue {
         Self {
             name,
             params,
+            param_types: Vec::new(),
+            return_type: None,
             // ALLOW: syn::Block is Send + Sync (it's just AST data),
             // but clippy can't verify this automatically
             #[allow(clippy::arc_with_non_send_sync)]
             body: Arc::new(body),
             call_count: 0,
+            mut_self: false,
+            variadic: false,
+            item_scope: 0,
+        }
+    }
+
+    /// Mark this function as a `&mut self` method (builder pattern)
+    pub fn with_mut_self(mut self) -> Self {
+        self.mut_self = true;
+        self
+    }
+
+    /// Record the declared parameter types (builder pattern)
+    pub fn with_param_types(mut self, param_types: Vec<String>) -> Self {
+        self.param_types = param_types;
+        self
+    }
+
+    /// Record the declared return type (builder pattern)
+    pub fn with_return_type(mut self, return_type: Option<String>) -> Self {
+        self.return_type = return_type;
+        self
+    }
+
+    /// Record the item scope the function was defined in (builder pattern)
+    pub fn with_item_scope(mut self, item_scope: u64) -> Self {
+        self.item_scope = item_scope;
+        self
+    }
+
+    /// Mark the last parameter as a rest parameter (builder pattern)
+    ///
+    /// Arguments beyond the other parameters are bound to it as a `Vec`,
+    /// which is empty when there are none. Source code declares one as
+    /// `rest: ...` at the end of the parameter list.
+    pub fn with_variadic(mut self) -> Self {
+        self.variadic = true;
+        self
+    }
+
+    /// Get the number of arguments this function requires.
+    ///
+    /// For variadic functions this excludes the rest parameter.
+    pub fn min_arity(&self) -> usize {
+        if self.variadic {
+            self.params.len().saturating_sub(1)
+        } else {
+            self.params.len()
         }
     }
 }
 
 /// A closure with captured environment.
 ///
-/// Closures capture variables from their defining scope.
-/// This will be fully implemented in Phase 5.
+/// Closures capture variables from their defining scope by value.
 #[derive(Debug, Clone)]
 pub struct ClosureValue {
     /// Parameter names
@@ -57,6 +136,20 @@ pub struct ClosureValue {
     pub captures: Arc<Vec<(String, Value)>>,
 }
 
+impl ClosureValue {
+    /// Create a new closure value
+    pub fn new(params: Vec<String>, body: syn::Expr, captures: Vec<(String, Value)>) -> Self {
+        Self {
+            params,
+            // ALLOW: syn::Expr is Send + Sync (it's just AST data),
+            // but clippy can't verify this automatically
+            #[allow(clippy::arc_with_non_send_sync)]
+            body: Arc::new(body),
+            captures: Arc::new(captures),
+        }
+    }
+}
+
 /// A built-in native function.
 ///
 /// These are Rust functions exposed to the interpreter.
@@ -71,6 +164,70 @@ pub struct BuiltinFn {
     /// The actual function pointer
     /// Uses a trait object for flexibility
     pub func: BuiltinFnPtr,
+
+    /// Optional behaviour beyond `func` (`None` for most builtins, which
+    /// keeps `Value` small)
+    pub hooks: Option<Arc<BuiltinHooks>>,
+}
+
+/// Optional behaviour a builtin declares beyond its function.
+#[derive(Clone, Default)]
+pub struct BuiltinHooks {
+    /// Estimates the heap bytes a call allocates from its arguments, so
+    /// the memory budget can be checked before calling (`None` for
+    /// builtins whose results are small)
+    pub allocation: Option<BuiltinAllocFn>,
+
+    /// For control builtins such as `exit` and `panic`, the function the
+    /// interpreter calls instead of `func`, whose errors (such as `Exit`
+    /// or `Panic`) end evaluation as they are
+    pub control: Option<BuiltinControlFnPtr>,
+}
+
+impl BuiltinFn {
+    /// Create a control builtin, whose errors the interpreter raises as
+    /// they are rather than wrapping them in `BuiltinError`.
+    ///
+    /// Its `func` reports them as messages, for callers outside the
+    /// interpreter.
+    pub fn control(
+        name: impl Into<String>,
+        arity: i32,
+        func: impl Fn(&[Value]) -> Result<Value, crate::EvalError> + Send + Sync + 'static,
+    ) -> Self {
+        let control: BuiltinControlFnPtr = Arc::new(func);
+        let report = Arc::clone(&control);
+        Self {
+            name: name.into(),
+            arity,
+            func: Arc::new(move |args| report(args).map_err(|e| e.to_string())),
+            hooks: Some(Arc::new(BuiltinHooks {
+                allocation: None,
+                control: Some(control),
+            })),
+        }
+    }
+
+    /// Declare how many heap bytes a call allocates (builder pattern)
+    pub fn with_allocation(
+        mut self,
+        estimate: impl Fn(&[Value]) -> usize + Send + Sync + 'static,
+    ) -> Self {
+        let mut hooks = self.hooks.as_deref().cloned().unwrap_or_default();
+        hooks.allocation = Some(Arc::new(estimate));
+        self.hooks = Some(Arc::new(hooks));
+        self
+    }
+
+    /// The estimate of the heap bytes a call allocates, if declared
+    pub fn allocation(&self) -> Option<&BuiltinAllocFn> {
+        self.hooks.as_ref()?.allocation.as_ref()
+    }
+
+    /// The function a control builtin raises its errors through
+    pub fn control_fn(&self) -> Option<&BuiltinControlFnPtr> {
+        self.hooks.as_ref()?.control.as_ref()
+    }
 }
 
 impl std::fmt::Debug for BuiltinFn {
@@ -81,8 +238,8 @@ impl std::fmt::Debug for BuiltinFn {
 
 /// A compiled native function (loaded via dlopen).
 ///
-/// This is the "escape hatch" to rustc for performance.
-/// Will be implemented in Phase 6.
+/// This is the "escape hatch" to rustc for performance. Produced by
+/// `jit::compile_function` when the `jit` feature is enabled.
 #[derive(Clone)]
 pub struct CompiledFn {
     /// Function name
@@ -94,8 +251,8 @@ pub struct CompiledFn {
     /// Path to the compiled library
     pub lib_path: std::path::PathBuf,
 
-    /// Function pointer placeholder (requires unsafe for actual implementation)
-    pub _marker: std::marker::PhantomData<()>,
+    /// Entry point into the loaded library (`None` if not loaded)
+    pub func: Option<CompiledFnPtr>,
 }
 
 impl std::fmt::Debug for CompiledFn {
@@ -127,6 +284,7 @@ mod tests {
             name: "test_builtin".to_string(),
             arity: 2,
             func: Arc::new(|_| Ok(Value::Unit)),
+            hooks: None,
         };
         let debug_str = format!("{:?}", builtin);
         assert_eq!(debug_str, "BuiltinFn(test_builtin)");
@@ -138,7 +296,7 @@ mod tests {
             name: "test_compiled".to_string(),
             arity: 1,
             lib_path: std::path::PathBuf::from("/path/to/lib.so"),
-            _marker: std::marker::PhantomData,
+            func: None,
         };
         let debug_str = format!("{:?}", compiled);
         assert!(debug_str.contains("CompiledFn"));
@@ -138,6 +138,8 @@ impl fmt::Debug for Value {
                 Err(e) => write!(f, "Err({:?})", e),
             },
 
+            Value::Iter(iter) => write!(f, "<iter, {} left>", iter.remaining().len()),
+
             Value::Function(func) => write!(f, "<fn {}>", func.name),
             Value::Closure(_) => write!(f, "<closure>"),
             Value::BuiltinFn(b) => write!(f, "<builtin {}>", b.name),
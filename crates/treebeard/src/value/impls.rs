@@ -64,6 +64,68 @@ impl Value {
         Value::Result(Arc::new(Err(value)))
     }
 
+    /// Create a `std::cmp::Ordering` value, the `Ordering::Less`,
+    /// `Ordering::Equal` or `Ordering::Greater` variant of the prelude's
+    /// `Ordering` enum
+    pub fn ordering(ord: std::cmp::Ordering) -> Self {
+        let variant = match ord {
+            std::cmp::Ordering::Less => "Less",
+            std::cmp::Ordering::Equal => "Equal",
+            std::cmp::Ordering::Greater => "Greater",
+        };
+        Value::enumeration(EnumValue::unit("Ordering", variant))
+    }
+
+    /// Create a shared reference to a value
+    pub fn reference(value: Value) -> Self {
+        Value::Ref(ValueRef {
+            value: Arc::new(value),
+            tag: 0,
+        })
+    }
+
+    /// Create a mutable reference to a value
+    pub fn reference_mut(value: Value) -> Self {
+        Value::RefMut(ValueRefMut {
+            value: Arc::new(std::sync::RwLock::new(value)),
+            tag: 0,
+        })
+    }
+
+    /// Get the `Default` value for a type name, such as `i64`, `String`,
+    /// or `Vec<T>`.
+    ///
+    /// Generic arguments are ignored, so `Vec<i64>` and `Vec` both give an
+    /// empty vec. Returns `None` for types without a known default.
+    pub fn default_for_type_name(name: &str) -> Option<Value> {
+        let base = name.split('<').next().unwrap_or(name).trim();
+        let value = match base {
+            "()" => Value::Unit,
+            "bool" => Value::Bool(false),
+            "char" => Value::Char('\0'),
+            "i8" => Value::I8(0),
+            "i16" => Value::I16(0),
+            "i32" => Value::I32(0),
+            "i64" => Value::I64(0),
+            "i128" => Value::I128(0),
+            "isize" => Value::Isize(0),
+            "u8" => Value::U8(0),
+            "u16" => Value::U16(0),
+            "u32" => Value::U32(0),
+            "u64" => Value::U64(0),
+            "u128" => Value::U128(0),
+            "usize" => Value::Usize(0),
+            "f32" => Value::F32(0.0),
+            "f64" => Value::F64(0.0),
+            "String" | "&str" => Value::string(""),
+            "Vec" => Value::vec(Vec::new()),
+            "HashMap" => Value::HashMap(Arc::new(HashMap::new())),
+            "Option" => Value::none(),
+            _ => return None,
+        };
+        Some(value)
+    }
+
     // ═══════════════════════════════════════════════════════════════════
     // Type Predicates
     // ═══════════════════════════════════════════════════════════════════
@@ -111,6 +173,36 @@ impl Value {
         matches!(self, Value::String(_))
     }
 
+    /// Check if value is truthy, for frontends with truthy conditions.
+    ///
+    /// `false`, zero, NaN, empty strings and collections, and `None` are
+    /// falsy; everything else is truthy.
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Bool(b) => *b,
+            Value::I8(n) => *n != 0,
+            Value::I16(n) => *n != 0,
+            Value::I32(n) => *n != 0,
+            Value::I64(n) => *n != 0,
+            Value::I128(n) => *n != 0,
+            Value::Isize(n) => *n != 0,
+            Value::U8(n) => *n != 0,
+            Value::U16(n) => *n != 0,
+            Value::U32(n) => *n != 0,
+            Value::U64(n) => *n != 0,
+            Value::U128(n) => *n != 0,
+            Value::Usize(n) => *n != 0,
+            Value::F32(x) => *x != 0.0 && !x.is_nan(),
+            Value::F64(x) => *x != 0.0 && !x.is_nan(),
+            Value::String(s) => !s.is_empty(),
+            Value::Bytes(b) => !b.is_empty(),
+            Value::Vec(v) | Value::Array(v) => !v.is_empty(),
+            Value::HashMap(m) => !m.is_empty(),
+            Value::Option(opt) => opt.is_some(),
+            _ => true,
+        }
+    }
+
     /// Check if value is callable (function, closure, or builtin)
     pub fn is_callable(&self) -> bool {
         matches!(
@@ -183,6 +275,21 @@ impl Value {
         }
     }
 
+    /// Extract an `Ordering` variant
+    pub fn as_ordering(&self) -> Option<std::cmp::Ordering> {
+        match self {
+            Value::Enum(e) if e.type_name == "Ordering" && e.data == EnumData::Unit => {
+                match e.variant.as_str() {
+                    "Less" => Some(std::cmp::Ordering::Less),
+                    "Equal" => Some(std::cmp::Ordering::Equal),
+                    "Greater" => Some(std::cmp::Ordering::Greater),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
     /// Extract vec or array as slice
     pub fn as_vec(&self) -> Option<&[Value]> {
         match self {
@@ -191,6 +298,450 @@ impl Value {
             _ => None,
         }
     }
+
+    /// Get the value behind a shared reference.
+    ///
+    /// Returns `None` for anything else, including `RefMut`, whose value
+    /// is only reachable through its lock; see [`deref_mut`](Self::deref_mut).
+    pub fn deref(&self) -> Option<&Value> {
+        match self {
+            Value::Ref(r) => Some(&r.value),
+            _ => None,
+        }
+    }
+
+    /// Lock the value behind a mutable reference for reading.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TypeError` if the value is not a `RefMut` or its lock is
+    /// poisoned.
+    pub fn deref_ref(&self) -> Result<std::sync::RwLockReadGuard<'_, Value>, crate::EvalError> {
+        match self {
+            Value::RefMut(r) => r.value.read().map_err(|_| crate::EvalError::TypeError {
+                message: "failed to acquire read lock on RefMut".to_string(),
+                span: None,
+            }),
+            other => Err(crate::EvalError::TypeError {
+                message: format!(
+                    "expected a mutable reference, got {}",
+                    crate::error::type_name(other)
+                ),
+                span: None,
+            }),
+        }
+    }
+
+    /// Lock the value behind a mutable reference for reading and writing.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TypeError` if the value is not a `RefMut` or its lock is
+    /// poisoned.
+    pub fn deref_mut(&self) -> Result<std::sync::RwLockWriteGuard<'_, Value>, crate::EvalError> {
+        match self {
+            Value::RefMut(r) => r.value.write().map_err(|_| crate::EvalError::TypeError {
+                message: "failed to acquire write lock on RefMut".to_string(),
+                span: None,
+            }),
+            other => Err(crate::EvalError::TypeError {
+                message: format!(
+                    "expected a mutable reference, got {}",
+                    crate::error::type_name(other)
+                ),
+                span: None,
+            }),
+        }
+    }
+
+    /// Convert to `i64`, accepting any integer in range and whole floats.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TypeError` for non-numeric values, integers outside the
+    /// `i64` range, and floats with a fractional part.
+    pub fn into_i64(self) -> Result<i64, crate::EvalError> {
+        let converted = match &self {
+            Value::I64(n) => Some(*n),
+            Value::I128(n) => i64::try_from(*n).ok(),
+            Value::U64(n) => i64::try_from(*n).ok(),
+            Value::U128(n) => i64::try_from(*n).ok(),
+            Value::Usize(n) => i64::try_from(*n).ok(),
+            Value::F32(x) => whole_f64_to_i64(f64::from(*x)),
+            Value::F64(x) => whole_f64_to_i64(*x),
+            other => other.as_i64(),
+        };
+        converted.ok_or_else(|| crate::EvalError::TypeError {
+            message: format!(
+                "cannot convert {} `{}` to i64",
+                crate::error::type_name(&self),
+                self
+            ),
+            span: None,
+        })
+    }
+
+    /// Convert to a `String`: strings are unwrapped, anything else is
+    /// rendered with `Display`.
+    pub fn into_string(self) -> String {
+        match self {
+            Value::String(s) => Arc::unwrap_or_clone(s),
+            other => other.to_string(),
+        }
+    }
+
+    /// Coerce a value to the type named by an annotation such as `u8`.
+    ///
+    /// Integers convert to any integer type that can hold them, and
+    /// `f64` narrows to `f32`. `()`, `bool`, `char`, `String` and `&str`
+    /// must already match. Other names, such as generics and user types,
+    /// leave the value unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IntegerOverflow` if an integer is out of the target's
+    /// range, and `TypeError` if a value cannot become that type.
+    pub fn coerce_to_type_name(&self, ty: &str) -> Result<Value, crate::EvalError> {
+        let mismatch = || crate::EvalError::TypeError {
+            message: format!(
+                "expected `{}`, found `{}`",
+                ty,
+                crate::error::type_name(self)
+            ),
+            span: None,
+        };
+
+        let kind_matches = match ty {
+            "()" => Some(matches!(self, Value::Unit)),
+            "bool" => Some(matches!(self, Value::Bool(_))),
+            "char" => Some(matches!(self, Value::Char(_))),
+            "String" => Some(matches!(self, Value::String(_))),
+            "&str" => Some(matches!(self.deref().unwrap_or(self), Value::String(_))),
+            _ => None,
+        };
+        if let Some(kind_matches) = kind_matches {
+            return if kind_matches {
+                Ok(self.clone())
+            } else {
+                Err(mismatch())
+            };
+        }
+
+        if !matches!(Value::default_for_type_name(ty), Some(default) if default.is_numeric())
+            || crate::error::type_name(self) == ty
+        {
+            return Ok(self.clone());
+        }
+
+        let coerced = match (self, self.integer_as_i128()) {
+            (Value::F64(x), _) if ty == "f32" => Some(Value::F32(*x as f32)),
+            (_, Some(n)) => {
+                let converted = match ty {
+                    "i8" => i8::try_from(n).ok().map(Value::I8),
+                    "i16" => i16::try_from(n).ok().map(Value::I16),
+                    "i32" => i32::try_from(n).ok().map(Value::I32),
+                    "i64" => i64::try_from(n).ok().map(Value::I64),
+                    "i128" => Some(Value::I128(n)),
+                    "isize" => isize::try_from(n).ok().map(Value::Isize),
+                    "u8" => u8::try_from(n).ok().map(Value::U8),
+                    "u16" => u16::try_from(n).ok().map(Value::U16),
+                    "u32" => u32::try_from(n).ok().map(Value::U32),
+                    "u64" => u64::try_from(n).ok().map(Value::U64),
+                    "u128" => u128::try_from(n).ok().map(Value::U128),
+                    "usize" => usize::try_from(n).ok().map(Value::Usize),
+                    _ => None,
+                };
+                if converted.is_none() && ty != "f32" && ty != "f64" {
+                    return Err(crate::EvalError::IntegerOverflow { span: None });
+                }
+                converted
+            }
+            _ => None,
+        };
+
+        coerced.ok_or_else(mismatch)
+    }
+
+    /// Widen any integer except a `u128` above `i128::MAX` to `i128`.
+    fn integer_as_i128(&self) -> Option<i128> {
+        match self {
+            Value::I128(n) => Some(*n),
+            Value::U64(n) => Some(i128::from(*n)),
+            Value::U128(n) => i128::try_from(*n).ok(),
+            Value::Usize(n) => i128::try_from(*n).ok(),
+            other => other.as_i64().map(i128::from),
+        }
+    }
+
+    /// Get the elements of a vec or array for in-place mutation.
+    ///
+    /// Elements shared with other values are copied first, so those
+    /// values are unaffected by the mutation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TypeError` if the value is not a vec or array.
+    pub fn vec_make_mut(&mut self) -> Result<&mut Vec<Value>, crate::EvalError> {
+        match self {
+            Value::Vec(v) | Value::Array(v) => Ok(Arc::make_mut(v)),
+            other => Err(crate::EvalError::TypeError {
+                message: format!("expected a vec, got {}", crate::error::type_name(other)),
+                span: None,
+            }),
+        }
+    }
+}
+
+/// `x` as an `i64` if it is a whole number in range.
+fn whole_f64_to_i64(x: f64) -> Option<i64> {
+    // i64::MAX rounds up to 2^63 as a float, so the upper bound is exclusive
+    (x.fract() == 0.0 && x >= i64::MIN as f64 && x < i64::MAX as f64).then_some(x as i64)
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// Reflection
+// ═══════════════════════════════════════════════════════════════════
+
+impl Value {
+    /// Get the variant name of an enum, `Option` or `Result` value.
+    pub fn variant_name(&self) -> Option<String> {
+        match self {
+            Value::Enum(e) => Some(e.variant.clone()),
+            Value::Option(opt) => Some(if opt.is_some() { "Some" } else { "None" }.to_string()),
+            Value::Result(res) => Some(if res.is_ok() { "Ok" } else { "Err" }.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Get the field names of a struct in definition order. Tuple struct
+    /// fields are named `"0"`, `"1"`, and so on.
+    pub fn field_names(&self) -> Option<Vec<String>> {
+        match self {
+            Value::Struct(s) => Some(s.fields.keys().cloned().collect()),
+            _ => None,
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// Deep Copies
+// ═══════════════════════════════════════════════════════════════════
+
+impl Value {
+    /// Copy this value, rebuilding every nested allocation.
+    ///
+    /// `Clone` is shallow: the copy shares its `Arc`s with the original.
+    /// That is enough for data values, which copy on write, so it is what
+    /// the `clone` method uses. The difference shows through the shared
+    /// state that does not copy on write: a deep copy gets its own
+    /// `&mut` referents and its own iterator cursors.
+    ///
+    /// Callables are immutable and stay shared.
+    pub fn deep_clone(&self) -> Value {
+        let all = |items: &[Value]| items.iter().map(Value::deep_clone).collect::<Vec<_>>();
+
+        match self {
+            Value::String(s) => Value::string(s.as_str()),
+            Value::Bytes(b) => Value::bytes(b.as_slice()),
+            Value::Vec(items) => Value::vec(all(items)),
+            Value::Tuple(items) => Value::tuple(all(items)),
+            Value::Array(items) => Value::array(all(items)),
+            Value::Struct(s) => Value::structure(StructValue {
+                type_name: s.type_name.clone(),
+                fields: s
+                    .fields
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.deep_clone()))
+                    .collect(),
+                is_tuple_struct: s.is_tuple_struct,
+            }),
+            Value::Enum(e) => Value::enumeration(EnumValue {
+                type_name: e.type_name.clone(),
+                variant: e.variant.clone(),
+                data: match &e.data {
+                    EnumData::Unit => EnumData::Unit,
+                    EnumData::Tuple(items) => EnumData::Tuple(all(items)),
+                    EnumData::Struct(fields) => EnumData::Struct(
+                        fields
+                            .iter()
+                            .map(|(k, v)| (k.clone(), v.deep_clone()))
+                            .collect(),
+                    ),
+                },
+            }),
+            Value::HashMap(map) => Value::HashMap(Arc::new(
+                map.iter()
+                    .map(|(k, v)| (HashableValue(k.0.deep_clone()), v.deep_clone()))
+                    .collect(),
+            )),
+            Value::Option(opt) => {
+                Value::Option(Arc::new(opt.as_ref().as_ref().map(Value::deep_clone)))
+            }
+            Value::Result(res) => Value::Result(Arc::new(match res.as_ref() {
+                Ok(v) => Ok(v.deep_clone()),
+                Err(e) => Err(e.deep_clone()),
+            })),
+            Value::Iter(iter) => Value::Iter(IterValue::new(Arc::new(all(iter.remaining())))),
+            Value::Ref(r) => Value::reference(r.value.deep_clone()),
+            Value::RefMut(r) => {
+                let value = match r.value.read() {
+                    Ok(value) => value.deep_clone(),
+                    Err(poisoned) => poisoned.into_inner().deep_clone(),
+                };
+                Value::reference_mut(value)
+            }
+            _ => self.clone(),
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// Memory Estimates
+// ═══════════════════════════════════════════════════════════════════
+
+impl Value {
+    /// Estimate the heap bytes this value holds, recursively.
+    ///
+    /// Counts string and byte capacities and the element storage of
+    /// collections, plus whatever the elements hold. Storage shared
+    /// between values is counted once per value that reaches it, so the
+    /// estimate errs high. Callables count as zero.
+    pub fn heap_size(&self) -> usize {
+        const SLOT: usize = std::mem::size_of::<Value>();
+        let all = |items: &[Value]| items.iter().map(Value::heap_size).sum::<usize>();
+        let fields = |fields: &indexmap::IndexMap<String, Value>| {
+            fields
+                .iter()
+                .map(|(k, v)| std::mem::size_of::<String>() + k.capacity() + SLOT + v.heap_size())
+                .sum::<usize>()
+        };
+
+        match self {
+            Value::String(s) => s.capacity(),
+            Value::Bytes(b) => b.capacity(),
+            Value::Vec(items) | Value::Tuple(items) | Value::Array(items) => {
+                items.capacity() * SLOT + all(items)
+            }
+            Value::Struct(s) => fields(&s.fields),
+            Value::Enum(e) => match &e.data {
+                EnumData::Unit => 0,
+                EnumData::Tuple(items) => items.capacity() * SLOT + all(items),
+                EnumData::Struct(f) => fields(f),
+            },
+            Value::HashMap(map) => map
+                .iter()
+                .map(|(k, v)| 2 * SLOT + k.0.heap_size() + v.heap_size())
+                .sum(),
+            Value::Option(opt) => SLOT + opt.as_ref().as_ref().map_or(0, Value::heap_size),
+            Value::Result(res) => {
+                SLOT + match res.as_ref() {
+                    Ok(v) | Err(v) => v.heap_size(),
+                }
+            }
+            Value::Iter(iter) => iter.remaining().len() * SLOT + all(iter.remaining()),
+            Value::Ref(r) => SLOT + r.value.heap_size(),
+            Value::RefMut(r) => SLOT + r.value.read().map_or(0, |v| v.heap_size()),
+            _ => 0,
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// Ordering
+// ═══════════════════════════════════════════════════════════════════
+
+impl Value {
+    /// Order two values of the same type, as the comparison operators do.
+    ///
+    /// Sequences compare lexicographically and `None` sorts before `Some`.
+    /// Returns `None` for values of different types, types without a
+    /// natural order (structs, maps, callables), or floats involving NaN.
+    pub fn compare(&self, other: &Value) -> Option<std::cmp::Ordering> {
+        self.partial_order(other).ok().flatten()
+    }
+
+    /// Like [`compare`](Self::compare), but reports why two values cannot
+    /// be ordered.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TypeError` for values of different types, types without a
+    /// natural order, or a comparison involving NaN.
+    pub fn try_cmp(&self, other: &Value) -> Result<std::cmp::Ordering, crate::EvalError> {
+        self.partial_order(other)?
+            .ok_or_else(|| self.unordered_error(other))
+    }
+
+    /// Order two values, reporting NaN comparisons as `Ok(None)`
+    /// (unordered) and values that have no common order as an error.
+    ///
+    /// [`compare`](Self::compare) and [`try_cmp`](Self::try_cmp) are both
+    /// built on this.
+    pub(crate) fn partial_order(
+        &self,
+        other: &Value,
+    ) -> Result<Option<std::cmp::Ordering>, crate::EvalError> {
+        use std::cmp::Ordering;
+
+        let ord = match (self, other) {
+            (Value::Unit, Value::Unit) => Ordering::Equal,
+            (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+            (Value::Char(a), Value::Char(b)) => a.cmp(b),
+
+            (Value::I8(a), Value::I8(b)) => a.cmp(b),
+            (Value::I16(a), Value::I16(b)) => a.cmp(b),
+            (Value::I32(a), Value::I32(b)) => a.cmp(b),
+            (Value::I64(a), Value::I64(b)) => a.cmp(b),
+            (Value::I128(a), Value::I128(b)) => a.cmp(b),
+            (Value::Isize(a), Value::Isize(b)) => a.cmp(b),
+            (Value::U8(a), Value::U8(b)) => a.cmp(b),
+            (Value::U16(a), Value::U16(b)) => a.cmp(b),
+            (Value::U32(a), Value::U32(b)) => a.cmp(b),
+            (Value::U64(a), Value::U64(b)) => a.cmp(b),
+            (Value::U128(a), Value::U128(b)) => a.cmp(b),
+            (Value::Usize(a), Value::Usize(b)) => a.cmp(b),
+
+            (Value::F32(a), Value::F32(b)) => return Ok(a.partial_cmp(b)),
+            (Value::F64(a), Value::F64(b)) => return Ok(a.partial_cmp(b)),
+
+            (Value::String(a), Value::String(b)) => a.cmp(b),
+            (Value::Bytes(a), Value::Bytes(b)) => a.cmp(b),
+
+            (Value::Vec(a), Value::Vec(b))
+            | (Value::Tuple(a), Value::Tuple(b))
+            | (Value::Array(a), Value::Array(b)) => {
+                for (x, y) in a.iter().zip(b.iter()) {
+                    match x.partial_order(y)? {
+                        Some(Ordering::Equal) => continue,
+                        ord => return Ok(ord),
+                    }
+                }
+                a.len().cmp(&b.len())
+            }
+
+            (Value::Option(a), Value::Option(b)) => match (a.as_ref(), b.as_ref()) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Less,
+                (Some(_), None) => Ordering::Greater,
+                (Some(a), Some(b)) => return a.partial_order(b),
+            },
+
+            _ => return Err(self.unordered_error(other)),
+        };
+        Ok(Some(ord))
+    }
+
+    /// The error for two values that cannot be ordered.
+    fn unordered_error(&self, other: &Value) -> crate::EvalError {
+        crate::EvalError::TypeError {
+            message: format!(
+                "cannot compare `{}` with `{}`",
+                crate::error::type_name(self),
+                crate::error::type_name(other)
+            ),
+            span: None,
+        }
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════
@@ -260,6 +811,9 @@ impl PartialEq for Value {
             (Value::Option(a), Value::Option(b)) => a == b,
             (Value::Result(a), Value::Result(b)) => a == b,
 
+            // Iterators are equal if they have the same elements left
+            (Value::Iter(a), Value::Iter(b)) => a.remaining() == b.remaining(),
+
             // Functions are equal if they're the same Arc
             (Value::Function(a), Value::Function(b)) => Arc::ptr_eq(a, b),
             (Value::Closure(a), Value::Closure(b)) => Arc::ptr_eq(a, b),
@@ -422,6 +976,222 @@ impl<T: Into<Value>, E: Into<Value>> From<Result<T, E>> for Value {
 mod tests {
     use super::*;
 
+    // Equality
+    #[test]
+    fn test_eq_nested_data() {
+        let a = Value::vec(vec![Value::some(Value::tuple(vec![Value::I64(1)]))]);
+        let b = Value::vec(vec![Value::some(Value::tuple(vec![Value::I64(1)]))]);
+        let c = Value::vec(vec![Value::some(Value::tuple(vec![Value::I64(2)]))]);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_eq_closures_by_identity() {
+        // ALLOW: ClosureValue is Send + Sync (syn::Expr is Send + Sync),
+        // but clippy can't verify this automatically
+        #[allow(clippy::arc_with_non_send_sync)]
+        let make = || {
+            Value::Closure(Arc::new(ClosureValue::new(
+                vec![],
+                syn::parse_quote!(1),
+                vec![],
+            )))
+        };
+        let f = make();
+        assert_eq!(f, f.clone());
+        assert_ne!(f, make());
+    }
+
+    // Deep copies
+    #[test]
+    fn test_deep_clone_does_not_share_allocations() {
+        let mut original = Value::vec(vec![
+            Value::string("a"),
+            Value::reference_mut(Value::I64(1)),
+        ]);
+        let copy = original.deep_clone();
+
+        let (Value::Vec(a), Value::Vec(b)) = (&original, &copy) else {
+            panic!("expected vecs");
+        };
+        assert!(!Arc::ptr_eq(a, b));
+        assert_eq!(a[0], b[0]);
+
+        // Writing through the original's `&mut` leaves the copy alone
+        if let Value::RefMut(r) = &a[1] {
+            *r.value.write().unwrap() = Value::I64(2);
+        }
+        if let Value::RefMut(r) = &b[1] {
+            assert_eq!(*r.value.read().unwrap(), Value::I64(1));
+        }
+
+        // As does mutating the original's elements
+        if let Value::Vec(items) = &mut original {
+            Arc::make_mut(items).push(Value::Unit);
+        }
+        assert_eq!(copy.as_vec().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_deep_clone_iterator_has_own_cursor() {
+        let iter = Value::Iter(IterValue::new(Arc::new(vec![Value::I64(1), Value::I64(2)])));
+        let copy = iter.deep_clone();
+
+        if let Value::Iter(mut it) = iter {
+            it.next();
+        }
+        let Value::Iter(copy) = copy else {
+            panic!("expected an iterator");
+        };
+        assert_eq!(copy.remaining().len(), 2);
+    }
+
+    // Coercing conversions
+    #[test]
+    fn test_into_i64() {
+        assert_eq!(Value::F64(3.0).into_i64().unwrap(), 3);
+        assert_eq!(Value::U8(7).into_i64().unwrap(), 7);
+        assert_eq!(Value::Usize(9).into_i64().unwrap(), 9);
+        assert!(Value::F64(3.5).into_i64().is_err());
+        assert!(Value::F64(f64::NAN).into_i64().is_err());
+        assert!(Value::F64(1e20).into_i64().is_err());
+        assert!(Value::U64(u64::MAX).into_i64().is_err());
+        assert!(Value::string("3").into_i64().is_err());
+    }
+
+    #[test]
+    fn test_coerce_to_type_name() {
+        assert_eq!(
+            Value::I64(5).coerce_to_type_name("u8").unwrap(),
+            Value::U8(5)
+        );
+        assert_eq!(
+            Value::U8(5).coerce_to_type_name("i128").unwrap(),
+            Value::I128(5)
+        );
+        assert_eq!(
+            Value::F64(1.5).coerce_to_type_name("f32").unwrap(),
+            Value::F32(1.5)
+        );
+        assert_eq!(
+            Value::string("s").coerce_to_type_name("String").unwrap(),
+            Value::string("s")
+        );
+        assert!(matches!(
+            Value::I64(5).coerce_to_type_name("char"),
+            Err(crate::EvalError::TypeError { .. })
+        ));
+        assert!(matches!(
+            Value::I64(5).coerce_to_type_name("String"),
+            Err(crate::EvalError::TypeError { .. })
+        ));
+        assert_eq!(
+            Value::string("s").coerce_to_type_name("&str").unwrap(),
+            Value::string("s")
+        );
+        assert!(matches!(
+            Value::I64(300).coerce_to_type_name("u8"),
+            Err(crate::EvalError::IntegerOverflow { .. })
+        ));
+        assert!(matches!(
+            Value::I64(-1).coerce_to_type_name("usize"),
+            Err(crate::EvalError::IntegerOverflow { .. })
+        ));
+        assert!(matches!(
+            Value::I64(1).coerce_to_type_name("f64"),
+            Err(crate::EvalError::TypeError { .. })
+        ));
+        assert!(matches!(
+            Value::Bool(true).coerce_to_type_name("u8"),
+            Err(crate::EvalError::TypeError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_into_string() {
+        assert_eq!(Value::string("hi").into_string(), "hi");
+        assert_eq!(Value::I64(42).into_string(), "42");
+        assert_eq!(Value::Char('c').into_string(), "c");
+    }
+
+    #[test]
+    fn test_is_truthy() {
+        for falsy in [
+            Value::Bool(false),
+            Value::I64(0),
+            Value::U8(0),
+            Value::F64(0.0),
+            Value::string(""),
+            Value::vec(vec![]),
+            Value::none(),
+        ] {
+            assert!(!falsy.is_truthy(), "{:?} should be falsy", falsy);
+        }
+        for truthy in [
+            Value::Bool(true),
+            Value::I64(-1),
+            Value::string("0"),
+            Value::vec(vec![Value::I64(0)]),
+            Value::some(Value::Bool(false)),
+            Value::Unit,
+        ] {
+            assert!(truthy.is_truthy(), "{:?} should be truthy", truthy);
+        }
+    }
+
+    // Reflection
+    #[test]
+    fn test_variant_name() {
+        let green = Value::enumeration(EnumValue::unit("Color", "Green"));
+        assert_eq!(green.variant_name().as_deref(), Some("Green"));
+        assert_eq!(Value::none().variant_name().as_deref(), Some("None"));
+        assert_eq!(
+            Value::err(Value::Unit).variant_name().as_deref(),
+            Some("Err")
+        );
+        assert_eq!(Value::I64(1).variant_name(), None);
+    }
+
+    #[test]
+    fn test_field_names() {
+        let point = Value::structure(
+            StructValue::new("Point")
+                .with_field("x", Value::I64(1))
+                .with_field("y", Value::I64(2)),
+        );
+        assert_eq!(
+            point.field_names(),
+            Some(vec!["x".to_string(), "y".to_string()])
+        );
+        assert_eq!(Value::Unit.field_names(), None);
+    }
+
+    // In-place mutation
+    #[test]
+    fn test_vec_make_mut_copies_shared_elements() {
+        let mut a = Value::vec(vec![Value::I64(1)]);
+        let b = a.clone();
+
+        a.vec_make_mut().unwrap().push(Value::I64(2));
+        assert_eq!(a.as_vec().unwrap().len(), 2);
+        assert_eq!(b.as_vec().unwrap(), &[Value::I64(1)]);
+
+        assert!(Value::I64(1).vec_make_mut().is_err());
+    }
+
+    // Memory estimates
+    #[test]
+    fn test_heap_size() {
+        let slot = std::mem::size_of::<Value>();
+        assert_eq!(Value::I64(1).heap_size(), 0);
+        assert_eq!(Value::string("abcd").heap_size(), 4);
+
+        let v = Value::vec(vec![Value::I64(1), Value::string("ab")]);
+        assert_eq!(v.heap_size(), 2 * slot + 2);
+        assert!(Value::vec(vec![v.clone()]).heap_size() > v.heap_size());
+    }
+
     // Constructors
     #[test]
     fn test_string_constructor() {
@@ -509,6 +1279,26 @@ mod tests {
     }
 
     // Extractors
+    #[test]
+    fn test_deref_and_deref_mut() {
+        let shared = Value::reference(Value::I64(1));
+        assert_eq!(shared.deref(), Some(&Value::I64(1)));
+        assert!(shared.deref_mut().is_err());
+        assert_eq!(Value::I64(1).deref(), None);
+
+        let target = Value::reference_mut(Value::I64(1));
+        let alias = target.clone();
+        assert_eq!(target.deref(), None);
+        *target.deref_mut().unwrap() = Value::I64(5);
+        assert_eq!(*alias.deref_mut().unwrap(), Value::I64(5));
+
+        // Reads share the lock
+        let first = target.deref_ref().unwrap();
+        assert_eq!(*alias.deref_ref().unwrap(), Value::I64(5));
+        drop(first);
+        assert!(shared.deref_ref().is_err());
+    }
+
     #[test]
     fn test_as_bool() {
         assert_eq!(Value::Bool(true).as_bool(), Some(true));
@@ -570,6 +1360,30 @@ mod tests {
         assert_ne!(v1, v3);
     }
 
+    #[test]
+    fn test_partialeq_hashmap_ignores_insertion_order() {
+        let map = |entries: &[(&str, Value)]| {
+            let mut m = HashMap::new();
+            for (k, v) in entries {
+                m.insert(HashableValue(Value::string(*k)), v.clone());
+            }
+            Value::HashMap(Arc::new(m))
+        };
+
+        let inner_ab = map(&[("a", Value::I64(1)), ("b", Value::I64(2))]);
+        let inner_ba = map(&[("b", Value::I64(2)), ("a", Value::I64(1))]);
+        assert_eq!(inner_ab, inner_ba);
+
+        let outer1 = map(&[("x", inner_ab.clone()), ("y", Value::Unit)]);
+        let outer2 = map(&[("y", Value::Unit), ("x", inner_ba)]);
+        assert_eq!(outer1, outer2);
+
+        let changed = map(&[("a", Value::I64(1)), ("b", Value::I64(3))]);
+        let outer3 = map(&[("x", changed), ("y", Value::Unit)]);
+        assert_ne!(outer1, outer3);
+        assert_ne!(inner_ab, map(&[("a", Value::I64(1))]));
+    }
+
     #[test]
     fn test_partialeq_option() {
         let some1 = Value::some(Value::I64(42));
@@ -642,4 +1456,79 @@ mod tests {
             _ => panic!("Expected Result"),
         }
     }
+
+    #[test]
+    fn test_default_for_type_name() {
+        assert_eq!(Value::default_for_type_name("i64"), Some(Value::I64(0)));
+        assert_eq!(
+            Value::default_for_type_name("bool"),
+            Some(Value::Bool(false))
+        );
+        assert_eq!(
+            Value::default_for_type_name("String"),
+            Some(Value::string(""))
+        );
+        assert_eq!(
+            Value::default_for_type_name("Vec<i64>"),
+            Some(Value::vec(Vec::new()))
+        );
+        assert_eq!(Value::default_for_type_name("fn"), None);
+        assert_eq!(Value::default_for_type_name("Point"), None);
+    }
+
+    #[test]
+    fn test_compare() {
+        use std::cmp::Ordering;
+
+        assert_eq!(Value::I64(1).compare(&Value::I64(2)), Some(Ordering::Less));
+        assert_eq!(
+            Value::string("b").compare(&Value::string("a")),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(Value::F64(f64::NAN).compare(&Value::F64(1.0)), None);
+        assert_eq!(
+            Value::F64(-0.0).compare(&Value::F64(0.0)),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            Value::tuple(vec![Value::I64(1), Value::I64(2)])
+                .compare(&Value::tuple(vec![Value::I64(1), Value::I64(3)])),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            Value::none().compare(&Value::some(Value::I64(0))),
+            Some(Ordering::Less)
+        );
+        assert_eq!(Value::I64(1).compare(&Value::string("1")), None);
+        assert_eq!(Value::I64(1).compare(&Value::I32(1)), None);
+    }
+
+    #[test]
+    fn test_try_cmp_sorts_mixed_length_tuples() {
+        let tuple = |items: &[i64]| Value::tuple(items.iter().copied().map(Value::I64).collect());
+        let mut values = vec![tuple(&[2]), tuple(&[1, 5]), tuple(&[1]), tuple(&[1, 2, 3])];
+        values.sort_by(|a, b| a.try_cmp(b).unwrap());
+        assert_eq!(
+            values,
+            vec![tuple(&[1]), tuple(&[1, 2, 3]), tuple(&[1, 5]), tuple(&[2])]
+        );
+    }
+
+    #[test]
+    fn test_try_cmp_errors() {
+        use std::cmp::Ordering;
+
+        assert_eq!(
+            Value::Bool(false).try_cmp(&Value::Bool(true)).unwrap(),
+            Ordering::Less
+        );
+        let err = Value::I64(1).try_cmp(&Value::string("1")).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("cannot compare `i64` with `String`"));
+        assert!(Value::F64(f64::NAN).try_cmp(&Value::F64(1.0)).is_err());
+        assert!(Value::tuple(vec![Value::F64(f64::NAN)])
+            .try_cmp(&Value::tuple(vec![Value::F64(1.0)]))
+            .is_err());
+    }
 }
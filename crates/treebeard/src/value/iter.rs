@@ -0,0 +1,88 @@
+//! Iterator state for `.iter()` values
+
+use std::sync::Arc;
+
+use super::Value;
+
+/// A cursor over the elements of a collection.
+///
+/// The iterator borrows its source by sharing the collection's `Arc`, so
+/// creating one copies no elements. Mutating the collection afterwards
+/// copies on write, leaving the iterator's view unchanged.
+///
+/// Each clone has its own cursor, so binding or passing an iterator
+/// copies its position rather than aliasing it.
+#[derive(Debug, Clone)]
+pub struct IterValue {
+    items: Arc<Vec<Value>>,
+    pos: usize,
+}
+
+impl IterValue {
+    /// Create an iterator positioned at the start of `items`.
+    pub fn new(items: Arc<Vec<Value>>) -> Self {
+        Self { items, pos: 0 }
+    }
+
+    /// Create an iterator over a value, if it is iterable.
+    ///
+    /// Vecs and arrays share their elements, strings yield chars, and
+    /// hashmaps yield `(key, value)` tuples in unspecified order. An
+    /// iterator is copied, keeping its position.
+    pub fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Vec(items) | Value::Array(items) => Some(Self::new(Arc::clone(items))),
+            Value::String(s) => Some(Self::new(Arc::new(s.chars().map(Value::Char).collect()))),
+            Value::HashMap(map) => Some(Self::new(Arc::new(
+                map.iter()
+                    .map(|(k, v)| Value::tuple(vec![k.0.clone(), v.clone()]))
+                    .collect(),
+            ))),
+            Value::Iter(iter) => Some(iter.clone()),
+            _ => None,
+        }
+    }
+
+    /// The elements not yet yielded.
+    pub fn remaining(&self) -> &[Value] {
+        &self.items[self.pos.min(self.items.len())..]
+    }
+}
+
+impl Iterator for IterValue {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        let item = self.items.get(self.pos)?.clone();
+        self.pos += 1;
+        Some(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_have_own_cursor() {
+        let mut a = IterValue::new(Arc::new(vec![Value::I64(1), Value::I64(2)]));
+        assert_eq!(a.next(), Some(Value::I64(1)));
+        let mut b = a.clone();
+
+        assert_eq!(a.next(), Some(Value::I64(2)));
+        assert_eq!(a.next(), None);
+        assert!(a.remaining().is_empty());
+        assert_eq!(b.next(), Some(Value::I64(2)));
+    }
+
+    #[test]
+    fn test_source_mutation_does_not_affect_iterator() {
+        let mut source = Value::vec(vec![Value::I64(1)]);
+        let iter = IterValue::from_value(&source).unwrap();
+
+        if let Value::Vec(items) = &mut source {
+            Arc::make_mut(items).push(Value::I64(2));
+        }
+        assert_eq!(iter.remaining(), &[Value::I64(1)]);
+    }
+}
@@ -5,11 +5,16 @@ mod compound;
 mod display;
 mod hashable;
 mod impls;
+mod iter;
 mod refs;
 
-pub use callable::{BuiltinFn, BuiltinFnPtr, ClosureValue, CompiledFn, FunctionValue};
+pub use callable::{
+    BuiltinAllocFn, BuiltinControlFnPtr, BuiltinFn, BuiltinFnPtr, BuiltinHooks, ClosureValue,
+    CompiledFn, CompiledFnPtr, FunctionValue,
+};
 pub use compound::{EnumData, EnumValue, StructValue};
 pub use hashable::HashableValue;
+pub use iter::IterValue;
 pub use refs::{ValueRef, ValueRefMut};
 
 use std::collections::HashMap;
@@ -102,6 +107,9 @@ pub enum Value {
     /// Result<T, E> - special-cased for ergonomics
     Result(Arc<Result<Value, Value>>),
 
+    /// Iterator over a collection's elements
+    Iter(IterValue),
+
     // ═══════════════════════════════════════════════════════════════════
     // Tier 3: Callable Types (defined but not fully implemented this stage)
     // ═══════════════════════════════════════════════════════════════════
@@ -115,7 +123,7 @@ pub enum Value {
     BuiltinFn(BuiltinFn),
 
     /// Compiled native function (escape hatch)
-    CompiledFn(CompiledFn),
+    CompiledFn(Arc<CompiledFn>),
 
     // ═══════════════════════════════════════════════════════════════════
     // References (for ownership tracking - Phase 5)
@@ -193,6 +193,7 @@ fn test_environment_get_mut() {
 // ═══════════════════════════════════════════════════════════════════════
 
 #[test]
+#[allow(deprecated)]
 fn test_environment_call_depth() {
     let mut env = Environment::with_max_call_depth(5);
 
@@ -210,6 +211,7 @@ fn test_environment_call_depth() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_environment_stack_overflow() {
     let mut env = Environment::with_max_call_depth(3);
 
@@ -354,10 +354,11 @@ fn test_unsupported_cast() {
     assert!(matches!(result, Err(EvalError::UnsupportedExpr { .. })));
 }
 
+// Closures are now supported
 #[test]
-fn test_unsupported_closure() {
+fn test_closure_expression() {
     let result = eval("|x| x + 1");
-    assert!(matches!(result, Err(EvalError::UnsupportedExpr { .. })));
+    assert!(matches!(result, Ok(Value::Closure(_))));
 }
 
 #[test]
@@ -394,10 +395,11 @@ fn test_index_undefined() {
     assert!(result.is_err()); // Undefined variable arr
 }
 
+// Let expressions evaluate to whether the pattern matched
 #[test]
-fn test_unsupported_let_guard() {
-    let result = eval("let x = 5");
-    assert!(matches!(result, Err(EvalError::UnsupportedExpr { .. })));
+fn test_let_expression() {
+    assert_eq!(eval("let x = 5").unwrap(), Value::Bool(true));
+    assert_eq!(eval("let 1 = 2").unwrap(), Value::Bool(false));
 }
 
 #[test]
@@ -413,10 +415,11 @@ fn test_range() {
     assert!(result.is_ok());
 }
 
+// References are now supported
 #[test]
-fn test_unsupported_reference() {
-    let result = eval("&x");
-    assert!(matches!(result, Err(EvalError::UnsupportedExpr { .. })));
+fn test_reference_expression() {
+    let result = eval("&1");
+    assert!(matches!(result, Ok(Value::Ref(_))));
 }
 
 // Array repeat syntax is now supported in Stage 1.6
@@ -443,10 +446,13 @@ fn test_struct_literal() {
     assert!(result.is_ok());
 }
 
+// The `?` operator is now supported
 #[test]
-fn test_unsupported_try() {
+fn test_try_expression() {
     let result = eval("foo?");
-    assert!(matches!(result, Err(EvalError::UnsupportedExpr { .. })));
+    assert!(matches!(result, Err(EvalError::UndefinedVariable { .. })));
+    let result = eval("1?");
+    assert!(matches!(result, Err(EvalError::TypeError { .. })));
 }
 
 #[test]
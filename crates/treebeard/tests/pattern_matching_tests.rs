@@ -405,6 +405,34 @@ fn test_pattern_reference_mut() {
     assert_eq!(bindings.len(), 1);
 }
 
+#[test]
+fn test_pattern_reference_through_ref() {
+    let bindings = test_match("&n", &Value::reference(Value::I64(5)))
+        .unwrap()
+        .unwrap();
+    assert_eq!(bindings[0].1, Value::I64(5));
+
+    let value = Value::reference_mut(Value::some(Value::I64(1)));
+    let bindings = test_match("&mut Some(v)", &value).unwrap().unwrap();
+    assert_eq!(bindings[0].1, Value::I64(1));
+}
+
+#[test]
+fn test_pattern_ref_bindings() {
+    let value = Value::some(Value::I64(3));
+
+    let bindings = test_match("Some(ref v)", &value).unwrap().unwrap();
+    let Value::Ref(r) = &bindings[0].1 else {
+        panic!("expected a reference binding");
+    };
+    assert_eq!(*r.value, Value::I64(3));
+    assert!(!bindings[0].2);
+
+    // Writes through `ref mut` could not reach the scrutinee
+    let result = test_match("Some(ref mut v)", &value);
+    assert!(matches!(result, Err(EvalError::UnsupportedExpr { .. })));
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 // Rest Pattern Tests
 // ═══════════════════════════════════════════════════════════════════════